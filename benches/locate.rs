@@ -0,0 +1,44 @@
+use std::hint;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use rand::{Rng, SeedableRng};
+use triangulate::tests::util;
+use triangulate::PolygonList;
+
+fn sample_points(polygon_list: &[Vec<[f32; 2]>], count: usize) -> Vec<[f32; 2]> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    let vertices = polygon_list.iter().flatten();
+    let min = vertices.clone().fold([f32::MAX; 2], |min, v| [min[0].min(v[0]), min[1].min(v[1])]);
+    let max = vertices.fold([f32::MIN; 2], |max, v| [max[0].max(v[0]), max[1].max(v[1])]);
+    (0..count).map(|_| [rng.gen_range(min[0]..max[0]), rng.gen_range(min[1]..max[1])]).collect()
+}
+
+// cargo bench --profile=release-symbols -F "_benchmarking rayon" --bench locate
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let polygon_list = util::load_polygon_list(util::countries_path().join("russia.txt").to_str().unwrap()).unwrap();
+    let traps = polygon_list.trapezoidize().expect("Trapezoidation failed");
+    let points = sample_points(&polygon_list, 1_000_000);
+
+    c.bench_function("russia_locate", |b| b.iter(|| {
+        let locations: Vec<_> = points.iter().map(|p| traps.locate(p).expect("Location failed")).collect();
+        hint::black_box(locations);
+    }));
+
+    c.bench_function("russia_classify_points", |b| b.iter(|| {
+        let locations = traps.classify_points(&points).expect("Location failed");
+        hint::black_box(locations);
+    }));
+
+    #[cfg(feature = "rayon")]
+    c.bench_function("russia_par_classify_points", |b| b.iter(|| {
+        let locations = traps.par_classify_points(&points).expect("Location failed");
+        hint::black_box(locations);
+    }));
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = criterion_benchmark
+}
+criterion_main!(benches);
@@ -0,0 +1,44 @@
+use std::{alloc::{GlobalAlloc, Layout, System}, sync::atomic::{AtomicUsize, Ordering}};
+
+use triangulate::tests::util;
+use triangulate::PolygonList;
+
+// Counts the bytes currently allocated, and the most allocated at once
+struct CountingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(current, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// cargo bench -F _benchmarking --bench memory
+fn main() {
+    for name in ["russia.txt", "antarctica.txt"] {
+        let polygon_list = util::load_polygon_list(util::countries_path().join(name).to_str().unwrap()).unwrap();
+        let vertices = polygon_list.vertex_count() as f64;
+
+        let before = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(before, Ordering::Relaxed);
+        let traps = polygon_list.trapezoidize().expect("Trapezoidation failed");
+        let peak = PEAK.load(Ordering::Relaxed) - before;
+        let retained = CURRENT.load(Ordering::Relaxed) - before;
+        drop(traps);
+
+        println!("{name}: {} vertices, peak {peak} B ({:.0} B/vertex), retained {retained} B ({:.0} B/vertex)",
+            vertices, peak as f64 / vertices, retained as f64 / vertices);
+    }
+}
@@ -4,7 +4,9 @@ use criterion::{Criterion, criterion_group, criterion_main};
 use triangulate::ListFormat;
 use triangulate::formats;
 use triangulate::tests::util;
+use triangulate::Polygon;
 use triangulate::PolygonList;
+use triangulate::Triangulator;
 
 // cargo bench --profile=release-symbols -F "_benchmarking"
 pub fn criterion_benchmark(c: &mut Criterion) {
@@ -20,6 +22,177 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     }));
 }
 
+// Many small islands, which each restarted the insertion order and vertex location schedule when rings were inserted separately
+pub fn criterion_benchmark_archipelagos(c: &mut Criterion) {
+    let mut group = c.benchmark_group("archipelagos");
+    for name in ["indonesia", "philippines", "japan", "norway"] {
+        let polygon_list = util::load_polygon_list(util::countries_path().join(format!("{name}.txt")).to_str().unwrap()).unwrap();
+
+        group.bench_function(name, |b| b.iter(|| {
+            let mut output = Vec::<[_; 3]>::new();
+            let builder = formats::IndexedListFormat::new(&mut output).into_fan_format();
+            polygon_list.triangulate(builder).expect("Triangulation failed");
+
+            hint::black_box(output);
+        }));
+    }
+
+    // All of them at once, so the independent groups can be triangulated in parallel
+    #[cfg(feature = "rayon")]
+    {
+        let polygon_list: Vec<Vec<[f32; 2]>> = ["indonesia", "philippines", "japan", "norway"].into_iter()
+            .flat_map(|name| util::load_polygon_list(util::countries_path().join(format!("{name}.txt")).to_str().unwrap()).unwrap())
+            .collect();
+
+        group.bench_function("combined", |b| b.iter(|| {
+            let mut output = Vec::<[_; 3]>::new();
+            let builder = formats::IndexedListFormat::new(&mut output).into_fan_format();
+            polygon_list.triangulate(builder).expect("Triangulation failed");
+
+            hint::black_box(output);
+        }));
+        group.bench_function("combined_par", |b| b.iter(|| {
+            let mut output = Vec::<[_; 3]>::new();
+            let builder = formats::IndexedListFormat::new(&mut output).into_fan_format();
+            polygon_list.par_triangulate(builder).expect("Triangulation failed");
+
+            hint::black_box(output);
+        }));
+    }
+    group.finish();
+}
+
+// Stars with 3 to 12 points, as when triangulating many small shapes each frame
+fn small_polygons() -> Vec<Vec<[f32; 2]>> {
+    (0..1000).map(|i| {
+        let points = 3 + i % 10;
+        let (x, y) = ((i % 40) as f32 * 10., (i / 40) as f32 * 10.);
+        (0..points * 2).map(|j| {
+            let r = if j % 2 == 0 { 4. } else { 2. };
+            let (sin, cos) = (std::f32::consts::PI * j as f32 / points as f32).sin_cos();
+            [x + r * cos, y + r * sin]
+        }).collect()
+    }).collect()
+}
+
+pub fn criterion_benchmark_small(c: &mut Criterion) {
+    let polygons = small_polygons();
+    let mut group = c.benchmark_group("small_polygons");
+
+    group.bench_function("fresh", |b| b.iter(|| {
+        let mut output = Vec::<usize>::new();
+        for polygon in polygons.iter() {
+            output.clear();
+            let builder = formats::IndexedListFormat::new(&mut output).into_fan_format();
+            polygon.triangulate(builder).expect("Triangulation failed");
+            hint::black_box(&output);
+        }
+    }));
+
+    let mut triangulator = Triangulator::new();
+    group.bench_function("triangulator", |b| b.iter(|| {
+        let mut output = Vec::<usize>::new();
+        for polygon in polygons.iter() {
+            output.clear();
+            let builder = formats::IndexedListFormat::new(&mut output).into_fan_format();
+            triangulator.triangulate(polygon.as_polygon_list(), builder).expect("Triangulation failed");
+            hint::black_box(&output);
+        }
+    }));
+
+    group.bench_function("auto", |b| b.iter(|| {
+        let mut output = Vec::<usize>::new();
+        for polygon in polygons.iter() {
+            output.clear();
+            let builder = formats::IndexedListFormat::new(&mut output).into_fan_format();
+            polygon.triangulate_with_strategy(builder, triangulate::Strategy::Auto).expect("Triangulation failed");
+            hint::black_box(&output);
+        }
+    }));
+    group.finish();
+}
+
+// A jagged circle with `n` vertices, with a square hole for every entry of `holes`
+fn jagged(n: usize, holes: usize) -> Vec<Vec<[f64; 2]>> {
+    let outer = (0..n).map(|i| {
+        let r = 100. + (i * 7919 % 13) as f64;
+        let (sin, cos) = (std::f64::consts::TAU * i as f64 / n as f64).sin_cos();
+        [r * cos, r * sin]
+    }).collect();
+    let side = (holes as f64).sqrt().ceil() as usize;
+    let cell = 100. / side.max(1) as f64;
+    std::iter::once(outer).chain((0..holes).map(|h| {
+        let (x, y) = ((h % side) as f64 * cell - 50., (h / side) as f64 * cell - 50.);
+        let s = cell * 0.5;
+        vec![[x, y], [x, y + s], [x + s, y + s], [x + s, y]]
+    })).collect()
+}
+
+// The crossover between ear clipping and Seidel's algorithm, used by `Strategy::Auto`
+pub fn criterion_benchmark_strategy(c: &mut Criterion) {
+    use triangulate::Strategy;
+
+    let mut group = c.benchmark_group("strategy");
+    let countries = ["brazil", "china", "australia", "greece"].map(|name| {
+        let polygon_list = util::load_polygon_list(util::countries_path().join(format!("{name}.txt")).to_str().unwrap()).unwrap();
+        (name.to_string(), polygon_list.into_iter().map(|ring| ring.into_iter().map(|v| v.map(f64::from)).collect()).collect())
+    });
+    let synthetic = [(16, 0), (256, 0), (1024, 0), (4096, 0), (16384, 0), (64, 16), (64, 32), (64, 64)]
+        .map(|(n, holes)| (format!("{n}_{holes}"), jagged(n, holes)));
+    for (name, polygon_list) in synthetic.into_iter().chain(countries) {
+        let polygon_list: Vec<Vec<[f64; 2]>> = polygon_list;
+        for strategy in [Strategy::Seidel, Strategy::EarClip, Strategy::Sweep] {
+            group.bench_function(format!("{name}_{strategy:?}"), |b| b.iter(|| {
+                let mut output = Vec::<[_; 3]>::new();
+                let builder = formats::IndexedListFormat::new(&mut output).into_fan_format();
+                polygon_list.triangulate_with_strategy(builder, strategy).expect("Triangulation failed");
+
+                hint::black_box(output);
+            }));
+        }
+    }
+    group.finish();
+}
+
+// Convex and y-monotone rings, which are triangulated without a trapezoidation
+pub fn criterion_benchmark_simple(c: &mut Criterion) {
+    use triangulate::Strategy;
+
+    let mut group = c.benchmark_group("simple_shapes");
+    let convex = [4, 64, 4096].map(|n| (format!("convex_{n}"), (0..n).map(|i| {
+        let (sin, cos) = (std::f64::consts::TAU * i as f64 / n as f64).sin_cos();
+        [100. * cos, 100. * sin]
+    }).collect::<Vec<_>>()));
+    // Both chains zigzag, so the ring splits into many pieces
+    let monotone = [64, 4096].map(|n| (format!("monotone_{n}"), (0..n / 2).map(|i| [(i % 2) as f64 * 3., i as f64])
+        .chain((0..n / 2).rev().map(|i| [10. - (i % 3) as f64, i as f64 + 0.5]))
+        .collect::<Vec<_>>()));
+    for (name, polygon) in convex.into_iter().chain(monotone) {
+        group.bench_function(format!("{name}_trapezoidized"), |b| b.iter(|| {
+            let mut output = Vec::<[_; 3]>::new();
+            let builder = formats::IndexedListFormat::new(&mut output).into_fan_format();
+            polygon.trapezoidize().expect("Trapezoidation failed").triangulate(builder).expect("Triangulation failed");
+
+            hint::black_box(output);
+        }));
+        group.bench_function(format!("{name}_direct"), |b| b.iter(|| {
+            let mut output = Vec::<[_; 3]>::new();
+            let builder = formats::IndexedListFormat::new(&mut output).into_fan_format();
+            polygon.triangulate(builder).expect("Triangulation failed");
+
+            hint::black_box(output);
+        }));
+        group.bench_function(format!("{name}_EarClip"), |b| b.iter(|| {
+            let mut output = Vec::<[_; 3]>::new();
+            let builder = formats::IndexedListFormat::new(&mut output).into_fan_format();
+            polygon.triangulate_with_strategy(builder, Strategy::EarClip).expect("Triangulation failed");
+
+            hint::black_box(output);
+        }));
+    }
+    group.finish();
+}
+
 pub fn criterion_benchmark_earcutr(c: &mut Criterion) {
     use triangulate::Vertex;
 
@@ -34,5 +207,5 @@ pub fn criterion_benchmark_earcutr(c: &mut Criterion) {
     }));
 }
 
-criterion_group!(benches, criterion_benchmark, criterion_benchmark_earcutr);
+criterion_group!(benches, criterion_benchmark, criterion_benchmark_archipelagos, criterion_benchmark_small, criterion_benchmark_strategy, criterion_benchmark_simple, criterion_benchmark_earcutr);
 criterion_main!(benches);
\ No newline at end of file
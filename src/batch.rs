@@ -0,0 +1,206 @@
+use std::{convert::Infallible, ops::Range, slice};
+
+use rand::Rng;
+
+use crate::{ListBuilder, ListFormat, PolygonElement, PolygonList, PolygonListExt, TriangleWinding, Triangulator, TriangulationError, formats::IndexedListFormat};
+
+/// Many separate [PolygonList]s, triangulated one at a time into a single triangle list, as for drawing many shapes with one draw call.
+///
+/// The batch is itself a [PolygonList], indexing the vertices of every item in order with a single `usize`.
+/// For a [Vec] of rings that is the position of the vertex once all the rings are concatenated,
+/// so indexed output can be used directly with the concatenated vertices. Any [ListFormat] can be used, such as
+/// [DeindexedListFormat](crate::formats::DeindexedListFormat) to output each item's vertices directly.
+///
+/// Items are triangulated independently, so they may overlap each other. An item which fails to triangulate is left
+/// out of the output, without affecting the others.
+///
+/// ```
+/// # use triangulate::{Batch, formats::IndexedListFormat};
+/// let square = vec![vec![[0f32, 0.], [1., 0.], [1., 1.], [0., 1.]]];
+/// let line = vec![vec![[0f32, 0.], [1., 1.]]];
+/// let batch = Batch::new([&square, &line, &square]);
+/// let (triangles, items) = batch.triangulate_items(IndexedListFormat::new(Vec::<[usize; 3]>::new())).expect("Triangulation failed");
+/// assert_eq!(triangles.len(), 4);
+/// assert_eq!(items[0].triangles, 0..2);
+/// assert!(items[1].error.is_some());
+/// assert_eq!((items[2].vertices.clone(), items[2].triangles.clone()), (6..10, 2..4));
+/// ```
+pub struct Batch<'p, P: PolygonList<'p> + ?Sized> {
+    items: Vec<&'p P>,
+    indices: Vec<P::Index>,
+    rings: Vec<Range<usize>>,
+    // The first vertex and ring of each item, followed by the totals
+    vertex_starts: Vec<usize>,
+    ring_starts: Vec<usize>,
+}
+
+/// The part of a [Batch] belonging to one item, returned by [Batch::triangulate_items]
+#[derive(Debug)]
+pub struct BatchItem {
+    /// The batch indices of the item's vertices
+    pub vertices: Range<usize>,
+    /// The item's range of triangles, counting from the first triangle output by the batch. Empty if the item failed.
+    pub triangles: Range<usize>,
+    /// Why the item could not be triangulated, if it failed.
+    /// Vertices are identified by their batch indices, and rings by their position within the item
+    pub error: Option<TriangulationError<Infallible, usize>>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> Batch<'p, P> {
+    /// Collect the rings of each item, in order
+    pub fn new(items: impl IntoIterator<Item = &'p P>) -> Self {
+        let mut batch = Self { items: Vec::new(), indices: Vec::new(), rings: Vec::new(), vertex_starts: vec![0], ring_starts: vec![0] };
+        for item in items {
+            let (indices, rings) = PolygonListExt::new(item).ring_ranges();
+            let start = batch.indices.len();
+            batch.rings.extend(rings.into_iter().map(|ring| start + ring.start..start + ring.end));
+            batch.indices.extend(indices);
+            batch.items.push(item);
+            batch.vertex_starts.push(batch.indices.len());
+            batch.ring_starts.push(batch.rings.len());
+        }
+        batch
+    }
+
+    /// The number of items in the batch
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether the batch has no items
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The item and its own index for a batch index
+    pub fn item_index(&self, index: usize) -> (usize, P::Index) {
+        let item = self.vertex_starts.partition_point(|&start| start <= index) - 1;
+        (item, self.indices[index].clone())
+    }
+
+    /// Triangulate each item into the layout specified by `format`, returning its output along with where each item ended up.
+    ///
+    /// Triangles are given to the format as batch indices, item by item in order. An item which fails is left out of the output.
+    /// Fails with [TriangulationError::NoVertices] if none of the items produced any triangles,
+    /// calling [ListBuilder::fail] so the format can roll back its output.
+    pub fn triangulate_items<'b, F: ListFormat<'b, Self>>(&'b self, format: F) -> BatchResult<'b, 'p, P, F> {
+        self.triangulate_items_with_seed(format, rand::thread_rng().gen())
+    }
+
+    /// Triangulate each item into the layout specified by `format`, ordering segment insertion within each item by `seed`.
+    /// See [Batch::triangulate_items].
+    pub fn triangulate_items_with_seed<'b, F: ListFormat<'b, Self>>(&'b self, format: F, seed: u64) -> BatchResult<'b, 'p, P, F> {
+        let mut builder = format.initialize(self)?;
+        let mut triangulator = Triangulator::new();
+        // Each item is triangulated here first, so an item which fails never reaches the builder
+        let mut item_triangles = Vec::<[usize; 3]>::new();
+        let mut items = Vec::with_capacity(self.items.len());
+        let mut triangle_count = 0;
+        for item in 0..self.items.len() {
+            let view = BatchItemView { batch: self, item };
+            let vertices = self.vertex_starts[item]..self.vertex_starts[item + 1];
+            item_triangles.clear();
+            let format = IndexedListFormat::new(&mut item_triangles).into_fan_format();
+            let error = triangulator.triangulate_with_seed(&view, format, seed).err();
+            if error.is_none() {
+                for &[a, b, c] in item_triangles.iter() {
+                    let (b, c) = if F::Builder::WINDING == TriangleWinding::Clockwise { (c, b) } else { (b, c) };
+                    if let Err(error) = builder.add_triangle(a, b, c) {
+                        let error = TriangulationError::FanBuilder(error);
+                        builder.fail(&error);
+                        return Err(error);
+                    }
+                }
+            }
+            let start = triangle_count;
+            triangle_count += item_triangles.len();
+            items.push(BatchItem { vertices, triangles: start..triangle_count, error });
+        }
+
+        if triangle_count == 0 {
+            let error = TriangulationError::NoVertices;
+            builder.fail(&error);
+            return Err(error);
+        }
+        Ok((builder.build()?, items))
+    }
+}
+
+/// The result of [Batch::triangulate_items]: the format's output and where each item ended up
+pub type BatchResult<'b, 'p, P, F> = Result<(<<F as ListFormat<'b, Batch<'p, P>>>::Builder as ListBuilder<'b, Batch<'p, P>>>::Output, Vec<BatchItem>), TriangulationError<<<F as ListFormat<'b, Batch<'p, P>>>::Builder as ListBuilder<'b, Batch<'p, P>>>::Error, usize>>;
+
+impl<'b, 'p: 'b, P: PolygonList<'p> + ?Sized> PolygonList<'b> for Batch<'p, P> {
+    type Vertex = P::Vertex;
+    type Index = usize;
+    type IntoItem = PolygonElement<usize>;
+    type Iter<'i> = BatchIter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'b: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.indices.len()
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'b: 'i {
+        BatchIter::new(&self.rings)
+    }
+
+    fn get_vertex<'a>(&'a self, index: usize) -> &'a Self::Vertex
+    where 'b: 'a {
+        let (item, index) = self.item_index(index);
+        self.items[item].get_vertex(index)
+    }
+}
+
+/// One item of a [Batch], indexed by batch indices so its triangles can be added to the batch's list
+struct BatchItemView<'b, 'p, P: PolygonList<'p> + ?Sized> {
+    batch: &'b Batch<'p, P>,
+    item: usize,
+}
+
+impl<'v, 'b: 'v, 'p: 'b, P: PolygonList<'p> + ?Sized> PolygonList<'v> for BatchItemView<'b, 'p, P> {
+    type Vertex = P::Vertex;
+    type Index = usize;
+    type IntoItem = PolygonElement<usize>;
+    type Iter<'i> = BatchIter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'v: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.batch.vertex_starts[self.item + 1] - self.batch.vertex_starts[self.item]
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'v: 'i {
+        let rings = &self.batch.rings[self.batch.ring_starts[self.item]..self.batch.ring_starts[self.item + 1]];
+        BatchIter { rings: rings.iter(), current: 0..0 }
+    }
+
+    fn get_vertex<'a>(&'a self, index: usize) -> &'a Self::Vertex
+    where 'v: 'a {
+        self.batch.items[self.item].get_vertex(self.batch.indices[index].clone())
+    }
+}
+
+/// The [PolygonList::Iter] type of a [Batch]
+pub struct BatchIter<'i> {
+    rings: slice::Iter<'i, Range<usize>>,
+    current: Range<usize>,
+}
+
+impl<'i> BatchIter<'i> {
+    pub(crate) fn new(rings: &'i [Range<usize>]) -> Self {
+        Self { rings: rings.iter(), current: 0..0 }
+    }
+}
+
+impl Iterator for BatchIter<'_> {
+    type Item = PolygonElement<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(i) = self.current.next() {
+            return Some(PolygonElement::ContinuePolygon(i));
+        }
+        self.current = self.rings.next()?.clone();
+        Some(PolygonElement::NewPolygon)
+    }
+}
@@ -0,0 +1,117 @@
+//! Identifying which precondition a [PolygonList] violates, once triangulating it has failed.
+//!
+//! This runs whenever trapezoidation or triangulation fails with an internal error, including for each failed item of a
+//! [Batch](crate::Batch), and before [DynamicTriangulation::move_vertex](crate::DynamicTriangulation::move_vertex) rebuilds,
+//! but not when a valid input triangulates successfully. Vertices are sorted to find duplicates,
+//! then edges are swept upwards, each tested against the edges which overlap it vertically.
+//!
+//! The sweep is O(*n* log *n*) when edges are short compared to the extent of the polygons, but each edge stays active
+//! until the sweep passes its upper end, so an input with many long edges spanning most of its height, such as a comb
+//! or a spiral, takes O(*n*²) time.
+
+use std::cmp::Ordering;
+
+use num_traits::ToPrimitive;
+
+use crate::{Coords, PolygonList, PolygonListExt, Real, TrapezoidationError, Vertex};
+
+/// Find a ring with fewer than 3 vertices, a pair of vertices with the same coordinates, or a pair of edges which cross or touch.
+///
+/// Returns [None] if the preconditions hold, as far as floating point arithmetic can tell.
+/// Takes O(*n*²) time in the worst case, see the [module documentation](self).
+pub(crate) fn find_violation<'p, P: PolygonList<'p> + ?Sized>(polygon_list: &'p P) -> Option<TrapezoidationError<P::Index>> {
+    let (indices, rings) = match PolygonListExt::new(polygon_list).checked_ring_ranges() {
+        Ok(ring_ranges) => ring_ranges,
+        Err(error) => return Some(error),
+    };
+
+    let coords: Vec<_> = indices.iter()
+        .map(|index| {
+            let v = polygon_list.get_vertex(index.clone());
+            Coords::new(v.x(), v.y())
+        })
+        .collect();
+
+    let mut order: Vec<usize> = (0..coords.len()).collect();
+    order.sort_by(|&a, &b| coords[a].partial_cmp(&coords[b]).unwrap_or(Ordering::Equal));
+    if let Some(pair) = order.windows(2).find(|pair| coords[pair[0]] == coords[pair[1]]) {
+        let (a, b) = (pair[0].min(pair[1]), pair[0].max(pair[1]));
+        return Some(TrapezoidationError::DuplicateVertex { a: indices[a].clone(), b: indices[b].clone() });
+    }
+
+    // Each edge runs from a vertex to the next one around its ring
+    let mut edges: Vec<(usize, usize)> = rings.iter()
+        .flat_map(|ring| ring.clone().map(move |i| (i, if i + 1 == ring.end { ring.start } else { i + 1 })))
+        .collect();
+    let lowest = |&(from, to): &(usize, usize)| if coords[from] < coords[to] { coords[from] } else { coords[to] };
+    let highest = |&(from, to): &(usize, usize)| if coords[from] < coords[to] { coords[to] } else { coords[from] };
+    edges.sort_by(|a, b| lowest(a).partial_cmp(&lowest(b)).unwrap_or(Ordering::Equal));
+
+    // The edges which may still overlap the next edge vertically. Long edges stay here while many others are tested
+    // against them, which makes the sweep quadratic in the worst case
+    let mut active: Vec<(usize, usize)> = Vec::new();
+    for edge in edges {
+        let c_low = lowest(&edge);
+        active.retain(|other| highest(other) >= c_low);
+        for &other in &active {
+            if let Some(point) = crossing(|i| coords[i], edge, other) {
+                let point = [point.x().to_f64().unwrap_or(f64::NAN), point.y().to_f64().unwrap_or(f64::NAN)];
+                return Some(TrapezoidationError::EdgeCrossing {
+                    a: (indices[other.0].clone(), indices[other.1].clone()),
+                    b: (indices[edge.0].clone(), indices[edge.1].clone()),
+                    point,
+                });
+            }
+        }
+        active.push(edge);
+    }
+
+    None
+}
+
+/// Whether the triangle `c0`, `c1`, `c2` turns counterclockwise ([Ordering::Greater]), clockwise, or is flat
+pub(crate) fn orientation<C: Real>(c0: Coords<C>, c1: Coords<C>, c2: Coords<C>) -> Ordering {
+    let area = (c1.x() - c0.x()) * (c2.y() - c0.y()) - (c1.y() - c0.y()) * (c2.x() - c0.x());
+    area.partial_cmp(&C::zero()).unwrap_or(Ordering::Equal)
+}
+
+/// Whether `c`, which is collinear with the segment from `c0` to `c1`, lies on it
+pub(crate) fn within<C: Real>(c0: Coords<C>, c1: Coords<C>, c: Coords<C>) -> bool {
+    c.x() >= c0.x().min(c1.x()) && c.x() <= c0.x().max(c1.x()) && c.y() >= c0.y().min(c1.y()) && c.y() <= c0.y().max(c1.y())
+}
+
+/// A point shared by the edges `a` and `b`, other than the vertex joining them if they are adjacent.
+/// 
+/// Vertices are identified by `I`, and `coords` gives their positions.
+pub(crate) fn crossing<C: Real, I: Copy + PartialEq>(coords: impl Fn(I) -> Coords<C>, a: (I, I), b: (I, I)) -> Option<Coords<C>> {
+    let shared = [a.0, a.1].into_iter().find(|&i| i == b.0 || i == b.1);
+    if let Some(shared) = shared {
+        // Adjacent edges only meet elsewhere if they fold back over each other
+        let c = coords(shared);
+        let c_a = coords(if a.0 == shared { a.1 } else { a.0 });
+        let c_b = coords(if b.0 == shared { b.1 } else { b.0 });
+        return if orientation(c, c_a, c_b) != Ordering::Equal {
+            None
+        } else if within(c, c_a, c_b) {
+            Some(c_b)
+        } else if within(c, c_b, c_a) {
+            Some(c_a)
+        } else {
+            None
+        };
+    }
+
+    let (a0, a1, b0, b1) = (coords(a.0), coords(a.1), coords(b.0), coords(b.1));
+    let (o0, o1) = (orientation(a0, a1, b0), orientation(a0, a1, b1));
+    let (o2, o3) = (orientation(b0, b1, a0), orientation(b0, b1, a1));
+    if o0 != Ordering::Equal && o1 != Ordering::Equal && o0 != o1 && o2 != Ordering::Equal && o3 != Ordering::Equal && o2 != o3 {
+        let denominator = (a1.x() - a0.x()) * (b1.y() - b0.y()) - (a1.y() - a0.y()) * (b1.x() - b0.x());
+        let t = ((b0.x() - a0.x()) * (b1.y() - b0.y()) - (b0.y() - a0.y()) * (b1.x() - b0.x())) / denominator;
+        return Some(Coords::new(a0.x() + t * (a1.x() - a0.x()), a0.y() + t * (a1.y() - a0.y())));
+    }
+
+    // Otherwise, the edges can only meet where a vertex of one lies on the other
+    [(o0, a0, a1, b0), (o1, a0, a1, b1), (o2, b0, b1, a0), (o3, b0, b1, a1)].into_iter()
+        .find(|&(o, c0, c1, c)| o == Ordering::Equal && within(c0, c1, c))
+        .map(|(_, _, _, c)| c)
+}
@@ -0,0 +1,151 @@
+use std::ops::Range;
+
+use num_traits::NumCast;
+
+use crate::{FanFormat, FanResult, PolygonElement, PolygonList, PolygonListExt, Real, TriangulationError, Vertex, VertexIndex, batch::BatchIter, fan_builder_state::FanBuilderState, fan_recorder::{FanRecorder, RecordedFans, with_fan_builder_error}};
+
+/// The direction in which [PolygonList::triangulate_with_direction] sweeps the polygons.
+///
+/// Vertices are ordered along this direction, then across it, so the pieces triangulated as monotone polygons
+/// are monotone with respect to it. Inputs dominated by edges parallel to the sweep line have many ties to break,
+/// so sweeping across the dominant direction of the edges gives fewer degeneracies and better shaped triangles.
+///
+/// [SweepDirection::X] and vectors along either axis only swap and negate coordinates, so they accept exactly the inputs
+/// [PolygonList::triangulate] does. Any other vector rotates a copy of the vertices, rounding the rotated coordinates,
+/// so a vertex very close to an edge may end up on the other side of it, and an input which only just meets the
+/// preconditions can fail as if it didn't.
+///
+/// The direction only applies to triangulation. [PolygonList::trapezoidize] and the queries on its result always sweep along y.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[non_exhaustive]
+pub enum SweepDirection {
+    /// Sweep from the largest y to the smallest, as [PolygonList::triangulate] does
+    #[default]
+    Y,
+    /// Sweep from the largest x to the smallest
+    X,
+    /// Sweep from the vertices furthest along a direction vector to the nearest.
+    /// The vector need not be normalized, but a vector which is zero or not finite is reported as [TriangulationError::InvalidDirection]
+    Vector([f64; 2]),
+}
+
+impl SweepDirection {
+    /// The normalized direction vector, or `None` if it is zero, not finite, or can't be represented by `C`
+    fn vector<C: Real>(self) -> Option<[C; 2]> {
+        let [x, y] = match self {
+            SweepDirection::Y => [0., 1.],
+            SweepDirection::X => [1., 0.],
+            SweepDirection::Vector(vector) => vector,
+        };
+        let length = x.hypot(y);
+        if !(length.is_finite() && length > 0.) {
+            return None;
+        }
+        Some([<C as NumCast>::from(x / length)?, <C as NumCast>::from(y / length)?])
+    }
+}
+
+pub(crate) fn triangulate<'p, P, FB>(polygon_list: &'p P, format: FB, direction: SweepDirection) -> FanResult<'p, P, FB>
+where P: PolygonList<'p> + ?Sized,
+      FB: FanFormat<'p, P> {
+    if direction == SweepDirection::Y {
+        return polygon_list.triangulate(format);
+    }
+    let mut fbs = FanBuilderState::<P, FB>::Uninitialized(format);
+    let Some(vector) = direction.vector() else {
+        return fbs.complete(Err(TriangulationError::InvalidDirection));
+    };
+
+    let view = DirectedView::new(polygon_list, Rotation::new(vector));
+    let fans = view.triangulate(FanRecorder::<P, FB::Builder, usize>::new(RecordedFans::new()));
+    let result = match fans {
+        Ok(fans) => fans.replay_mapped(&mut fbs, polygon_list, |&i| view.indices[i].clone()),
+        Err(error) => Err(with_fan_builder_error(error, |i| view.indices[i].clone())),
+    };
+    fbs.complete(result)
+}
+
+/// The rotation taking the sweep direction to y
+enum Rotation<C> {
+    /// A whole number of counterclockwise quarter turns, which only swap and negate coordinates so are exact
+    QuarterTurns(u8),
+    /// The normalized sweep direction, which is rounded when rotating
+    Vector([C; 2]),
+}
+
+impl<C: Real> Rotation<C> {
+    fn new([dx, dy]: [C; 2]) -> Self {
+        // Normalizing a vector along an axis gives exactly one unit
+        match (dx.is_zero(), dy.is_zero(), dx.is_sign_positive(), dy.is_sign_positive()) {
+            (true, _, _, true) => Self::QuarterTurns(0),
+            (_, true, true, _) => Self::QuarterTurns(1),
+            (true, _, _, false) => Self::QuarterTurns(2),
+            (_, true, false, _) => Self::QuarterTurns(3),
+            _ => Self::Vector([dx, dy]),
+        }
+    }
+
+    fn rotate(&self, x: C, y: C) -> [C; 2] {
+        match *self {
+            Self::QuarterTurns(0) => [x, y],
+            Self::QuarterTurns(1) => [-y, x],
+            Self::QuarterTurns(2) => [-x, -y],
+            Self::QuarterTurns(_) => [y, -x],
+            Self::Vector([dx, dy]) => [dy * x - dx * y, dx * x + dy * y],
+        }
+    }
+}
+
+/// A vertex of a [DirectedView], rotated so the sweep direction points along y
+struct DirectedVertex<C>([C; 2]);
+
+impl<C: Real> Vertex for DirectedVertex<C> {
+    type Coordinate = C;
+
+    fn x(&self) -> C { self.0[0] }
+    fn y(&self) -> C { self.0[1] }
+}
+
+/// A copy of a [PolygonList]'s vertices, rotated so that sweeping along y sweeps the original polygons along the direction vector.
+/// Being a rotation, this keeps the winding of every ring and triangle.
+struct DirectedView<Index, C> {
+    indices: Vec<Index>,
+    vertices: Vec<DirectedVertex<C>>,
+    rings: Vec<Range<usize>>,
+}
+
+impl<Index: VertexIndex, C: Real> DirectedView<Index, C> {
+    fn new<'p, P>(polygon_list: &'p P, rotation: Rotation<C>) -> Self
+    where P: PolygonList<'p, Index = Index, Vertex: Vertex<Coordinate = C>> + ?Sized {
+        let (indices, rings) = PolygonListExt::new(polygon_list).ring_ranges();
+        let vertices = indices.iter()
+            .map(|index| {
+                let vertex = polygon_list.get_vertex(index.clone());
+                DirectedVertex(rotation.rotate(vertex.x(), vertex.y()))
+            })
+            .collect();
+        Self { indices, vertices, rings }
+    }
+}
+
+impl<'v, Index: VertexIndex + 'v, C: Real + 'v> PolygonList<'v> for DirectedView<Index, C> {
+    type Vertex = DirectedVertex<C>;
+    type Index = usize;
+    type IntoItem = PolygonElement<usize>;
+    type Iter<'i> = BatchIter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'v: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'v: 'i {
+        BatchIter::new(&self.rings)
+    }
+
+    fn get_vertex<'a>(&'a self, index: usize) -> &'a Self::Vertex
+    where 'v: 'a {
+        &self.vertices[index]
+    }
+}
@@ -0,0 +1,319 @@
+use std::{cmp::Ordering, collections::HashMap, convert::Infallible, fmt::Debug};
+
+use rand::Rng;
+
+use crate::{Coords, ListFormat, Polygon, PolygonList, Real, Strategy, TriangulationError, diagnose::{self, crossing, orientation, within}, earclip::point_in_ring, formats::IndexedListFormat};
+
+/// Identifies a triangle within a [DynamicTriangulation]. The ids of removed triangles are reused for new triangles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TriangleId(usize);
+
+impl TriangleId {
+    /// The position of the triangle's slot, for use in lookup tables.
+    /// Slots range up to the largest number of triangles the triangulation has had.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// The triangles changed by [DynamicTriangulation::move_vertex]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TriangulationChange {
+    /// The triangles which were removed. Their ids may be reused by `added`.
+    pub removed: Vec<TriangleId>,
+    /// The triangles which were added
+    pub added: Vec<TriangleId>,
+    /// Whether the move could not be handled locally, so every triangle was replaced
+    pub rebuilt: bool,
+}
+
+/// A triangulation of a list of rings which is updated locally as vertices move, for interactive editing.
+///
+/// Triangles and their adjacency are kept, so that moving a vertex only retriangulates the triangles touching it,
+/// falling back to triangulating all rings again when the move reaches beyond them.
+/// Vertices are indexed by `[ring, vertex]`, as for a [Vec] of rings.
+///
+/// ```
+/// # use triangulate::DynamicTriangulation;
+/// let square = vec![vec![[0f32, 0.], [10., 0.], [10., 10.], [0., 10.]]];
+/// let mut triangulation = DynamicTriangulation::new(square).expect("Triangulation failed");
+/// let change = triangulation.move_vertex([0, 2], [12., 11.]).expect("Triangulation failed");
+/// assert!(!change.rebuilt);
+/// assert_eq!(triangulation.triangles().count(), 2);
+/// ```
+pub struct DynamicTriangulation<C: Real> {
+    rings: Vec<Vec<[C; 2]>>,
+    // The vertices of each triangle in counterclockwise order, or None for unused ids
+    triangles: Vec<Option<[[usize; 2]; 3]>>,
+    // The triangle across the edge from corner i to corner i + 1 of each triangle
+    neighbours: Vec<[Option<TriangleId>; 3]>,
+    // A triangle touching each vertex
+    vertex_triangles: Vec<Vec<Option<TriangleId>>>,
+    free: Vec<TriangleId>,
+    seed: u64,
+}
+
+impl<C: Real + Debug> DynamicTriangulation<C> {
+    /// Triangulate `rings`, which must satisfy the same preconditions as any [PolygonList]
+    /// 
+    /// Segment insertion is seeded randomly whenever the rings are triangulated again. The seed is available from [DynamicTriangulation::seed].
+    pub fn new(rings: Vec<Vec<[C; 2]>>) -> Result<Self, TriangulationError<Infallible, [usize; 2]>> {
+        Self::with_seed(rings, rand::thread_rng().gen())
+    }
+
+    /// Triangulate `rings`, ordering segment insertion by `seed` whenever the rings are triangulated again.
+    /// 
+    /// The same `seed`, rings and moves always produce the same triangles.
+    pub fn with_seed(rings: Vec<Vec<[C; 2]>>, seed: u64) -> Result<Self, TriangulationError<Infallible, [usize; 2]>> {
+        let mut triangulation = Self {
+            rings,
+            triangles: Vec::new(),
+            neighbours: Vec::new(),
+            vertex_triangles: Vec::new(),
+            free: Vec::new(),
+            seed,
+        };
+        triangulation.rebuild()?;
+        Ok(triangulation)
+    }
+
+    /// The seed used to order segment insertion
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The current vertex positions of each ring
+    pub fn rings(&self) -> &[Vec<[C; 2]>] {
+        &self.rings
+    }
+
+    /// Iterate over the triangles, with the indices of their vertices in counterclockwise order
+    pub fn triangles(&self) -> impl Iterator<Item = (TriangleId, [[usize; 2]; 3])> + '_ {
+        self.triangles.iter().enumerate().filter_map(|(i, t)| t.map(|t| (TriangleId(i), t)))
+    }
+
+    /// The indices of the triangle's vertices in counterclockwise order, or [None] if `id` has been removed
+    pub fn triangle(&self, id: TriangleId) -> Option<[[usize; 2]; 3]> {
+        self.triangles.get(id.0).copied().flatten()
+    }
+
+    /// The triangles across each edge of the triangle, where entry `i` is across the edge from corner `i` to corner `i + 1`,
+    /// or [None] if `id` has been removed.
+    ///
+    /// Edges of the rings have no triangle across them.
+    pub fn neighbours(&self, id: TriangleId) -> Option<[Option<TriangleId>; 3]> {
+        self.triangle(id).map(|_| self.neighbours[id.0])
+    }
+
+    /// Move the vertex at `index` to `position`, updating the triangulation.
+    ///
+    /// Only the triangles touching the vertex are replaced, unless the vertex leaves the region they cover,
+    /// or its edges would come into contact with other parts of the rings. Then all rings are triangulated again.
+    /// Checking this takes O(*n*) time, which is much faster than triangulating again.
+    ///
+    /// Before triangulating again, the rings are checked against the triangulation preconditions.
+    /// If the moved vertex makes them cross, touch or share a position, the violation is returned as a
+    /// [TrapezoidationError](crate::TrapezoidationError) and the move is undone.
+    ///
+    /// # Panics
+    /// Panics if `index` is not the index of a vertex.
+    pub fn move_vertex(&mut self, index: [usize; 2], position: [C; 2]) -> Result<TriangulationChange, TriangulationError<Infallible, [usize; 2]>> {
+        let old_position = self.rings[index[0]][index[1]];
+        if let Some(change) = self.move_locally(index, position) {
+            return Ok(change);
+        }
+
+        self.rings[index[0]][index[1]] = position;
+        // Triangulating invalid rings doesn't always fail, so check them first
+        if let Some(violation) = diagnose::find_violation(&self.rings) {
+            self.rings[index[0]][index[1]] = old_position;
+            return Err(TriangulationError::TrapezoidationError(violation));
+        }
+        let removed: Vec<_> = self.triangles().map(|(id, _)| id).collect();
+        match self.rebuild() {
+            Ok(()) => Ok(TriangulationChange { removed, added: self.triangles().map(|(id, _)| id).collect(), rebuilt: true }),
+            Err(error) => {
+                self.rings[index[0]][index[1]] = old_position;
+                Err(error)
+            }
+        }
+    }
+
+    fn position(&self, [ring, vertex]: [usize; 2]) -> [C; 2] {
+        self.rings[ring][vertex]
+    }
+
+    fn rebuild(&mut self) -> Result<(), TriangulationError<Infallible, [usize; 2]>> {
+        let mut indices = Vec::<[usize; 2]>::new();
+        // Always go through the trapezoidation, rather than fanning single convex rings from their first vertex,
+        // so the triangles don't change shape depending on whether the rings are currently convex
+        self.rings.trapezoidize_with_seed(self.seed)
+            .map_err(TriangulationError::TrapezoidationError)?
+            .triangulate(IndexedListFormat::new(&mut indices).into_fan_format())?;
+
+        self.triangles = indices.chunks_exact(3).map(|t| Some([t[0], t[1], t[2]])).collect();
+        self.neighbours = vec![[None; 3]; self.triangles.len()];
+        self.vertex_triangles = self.rings.iter().map(|ring| vec![None; ring.len()]).collect();
+        self.free.clear();
+        let ids: Vec<_> = (0..self.triangles.len()).map(TriangleId).collect();
+        self.connect(&ids);
+        Ok(())
+    }
+
+    // Set the neighbours of the given triangles across the edges they share with each other,
+    // and record them as the triangle touching each of their vertices
+    fn connect(&mut self, ids: &[TriangleId]) {
+        let mut edges = HashMap::with_capacity(ids.len() * 3);
+        for &id in ids {
+            if let Some(t) = self.triangles[id.0] {
+                for corner in 0..3 {
+                    edges.insert((t[corner], t[(corner + 1) % 3]), (id, corner));
+                    self.vertex_triangles[t[corner][0]][t[corner][1]] = Some(id);
+                }
+            }
+        }
+        for (&(a, b), &(id, corner)) in &edges {
+            if let Some(&(id_other, _)) = edges.get(&(b, a)) {
+                self.neighbours[id.0][corner] = Some(id_other);
+            }
+        }
+    }
+
+    // The triangles around a vertex in counterclockwise order, with the vertices opposite it.
+    // The vertex must be on the boundary of the triangulation, so the triangles form a fan between two ring edges
+    fn fan(&self, index: [usize; 2]) -> Option<(Vec<TriangleId>, Vec<[usize; 2]>)> {
+        let start = self.vertex_triangles[index[0]][index[1]]?;
+        let corner = |id: TriangleId| self.triangles[id.0].and_then(|t| t.iter().position(|&v| v == index));
+
+        // Turn clockwise until the ring edge
+        let mut id = start;
+        while let Some(next) = self.neighbours[id.0][corner(id)?] {
+            if next == start {
+                return None;
+            }
+            id = next;
+        }
+
+        let mut fan = Vec::new();
+        let mut link = Vec::new();
+        loop {
+            let (t, c) = (self.triangles[id.0]?, corner(id)?);
+            if link.is_empty() {
+                link.push(t[(c + 1) % 3]);
+            }
+            link.push(t[(c + 2) % 3]);
+            fan.push(id);
+            match self.neighbours[id.0][(c + 2) % 3] {
+                Some(next) => id = next,
+                None => return Some((fan, link)),
+            }
+            if fan.len() > self.triangles.len() {
+                return None;
+            }
+        }
+    }
+
+    // Retriangulate only the triangles around the vertex, if it stays clear of everything else
+    fn move_locally(&mut self, index: [usize; 2], position: [C; 2]) -> Option<TriangulationChange> {
+        let (fan, link) = self.fan(index)?;
+        let (w_first, w_last) = (link[0], link[link.len() - 1]);
+
+        // The region covered by the new triangles, in counterclockwise order
+        let cavity: Vec<[C; 2]> = [position].into_iter().chain(link.iter().map(|&w| self.position(w))).collect();
+        let area = (0..cavity.len())
+            .map(|i| cross(cavity[i], cavity[(i + 1) % cavity.len()]))
+            .fold(C::zero(), |sum, a| sum + a);
+        if area <= C::zero() {
+            return None;
+        }
+
+        // The new ring edges must not touch the rest of the cavity, or any other ring edge
+        let new_edges = [(w_first, index), (index, w_last)];
+        for &(a, b) in &new_edges {
+            let link_edges = link.windows(2).map(|w| (w[0], w[1]));
+            let ring_edges = self.rings.iter().enumerate().flat_map(|(ring, vertices)| {
+                (0..vertices.len()).map(move |i| ([ring, i], [ring, (i + 1) % vertices.len()]))
+            }).filter(|&(c, d)| c != index && d != index);
+            for (c, d) in link_edges.chain(ring_edges) {
+                let coords = |v| {
+                    let [x, y] = if v == index { position } else { self.position(v) };
+                    Coords::new(x, y)
+                };
+                if crossing(coords, (a, b), (c, d)).is_some() {
+                    return None;
+                }
+            }
+        }
+
+        // No other vertex may be inside the cavity
+        let (min, max) = cavity.iter().fold((cavity[0], cavity[0]), |(min, max), p| {
+            ([min[0].min(p[0]), min[1].min(p[1])], [max[0].max(p[0]), max[1].max(p[1])])
+        });
+        for (ring, vertices) in self.rings.iter().enumerate() {
+            for (i, &p) in vertices.iter().enumerate() {
+                let in_bounds = min[0] <= p[0] && p[0] <= max[0] && min[1] <= p[1] && p[1] <= max[1];
+                if in_bounds && [ring, i] != index && !link.contains(&[ring, i]) && in_polygon(p, &cavity) {
+                    return None;
+                }
+            }
+        }
+
+        let corners: Vec<[usize; 2]> = [index].into_iter().chain(link.iter().copied()).collect();
+        let new_triangles = cavity.triangulate_with_strategy(IndexedListFormat::new(Vec::<[usize; 3]>::new()).into_fan_format(), Strategy::EarClip).ok()?;
+        // Ear clipping drops collinear vertices, which would then be left out of the triangles
+        let corner_coords = |i: usize| Coords::new(cavity[i][0], cavity[i][1]);
+        if new_triangles.len() != cavity.len() - 2 || new_triangles.iter().any(|&[a, b, c]| orientation(corner_coords(a), corner_coords(b), corner_coords(c)) != Ordering::Greater) {
+            return None;
+        }
+
+        // The triangles outside the cavity which share an edge with it
+        let outside: Vec<TriangleId> = fan.iter()
+            .filter_map(|&id| {
+                let t = self.triangles[id.0]?;
+                let c = t.iter().position(|&v| v == index)?;
+                self.neighbours[id.0][(c + 1) % 3]
+            })
+            .collect();
+
+        self.rings[index[0]][index[1]] = position;
+        for &id in &fan {
+            self.triangles[id.0] = None;
+            self.neighbours[id.0] = [None; 3];
+        }
+        self.free.extend(fan.iter().rev());
+
+        let added: Vec<TriangleId> = new_triangles.into_iter().map(|t| {
+            let t = t.map(|i| corners[i]);
+            match self.free.pop() {
+                Some(id) => {
+                    self.triangles[id.0] = Some(t);
+                    id
+                }
+                None => {
+                    self.triangles.push(Some(t));
+                    self.neighbours.push([None; 3]);
+                    TriangleId(self.triangles.len() - 1)
+                }
+            }
+        }).collect();
+        let connected: Vec<_> = added.iter().chain(&outside).copied().collect();
+        self.connect(&connected);
+
+        Some(TriangulationChange { removed: fan, added, rebuilt: false })
+    }
+}
+
+fn cross<C: Real>(a: [C; 2], b: [C; 2]) -> C {
+    a[0] * b[1] - a[1] * b[0]
+}
+
+// Whether `p` is inside or on the boundary of the polygon
+fn in_polygon<C: Real>([x, y]: [C; 2], polygon: &[[C; 2]]) -> bool {
+    let p = Coords::new(x, y);
+    let on_boundary = (0..polygon.len()).any(|i| {
+        let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+        let (a, b) = (Coords::new(a[0], a[1]), Coords::new(b[0], b[1]));
+        orientation(a, b, p) == Ordering::Equal && within(a, b, p)
+    });
+    on_boundary || point_in_ring([x, y], polygon)
+}
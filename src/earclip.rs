@@ -0,0 +1,651 @@
+//! Ear clipping, following the approach of [earcut](https://github.com/mapbox/earcut).
+//!
+//! Holes are joined to the ring around them by bridges, then ears are cut from the resulting ring.
+//! Checking whether an ear contains any other vertex is O(*n*), so larger rings index their vertices by z-order,
+//! which only needs to check vertices near the ear in practice.
+
+use std::cmp::Ordering;
+
+use num_traits::NumCast;
+
+use crate::{FanBuilder, FanFormat, FanResult, PolygonList, PolygonListExt, Real, TriangleWinding, TriangulationError, Vertex, fan_builder_state::FanBuilderState};
+
+/// Rings with more vertices than this (including their holes) are indexed by z-order
+const HASH_THRESHOLD: usize = 80;
+
+const NIL: usize = usize::MAX;
+
+struct Node<C> {
+    // The vertex's position in the collected indices
+    i: usize,
+    x: C,
+    y: C,
+    prev: usize,
+    next: usize,
+    z: u32,
+    prev_z: usize,
+    next_z: usize,
+}
+
+pub(crate) fn triangulate<'p, P, FB>(polygon_list: &'p P, format: FB) -> FanResult<'p, P, FB>
+where P: PolygonList<'p> + ?Sized,
+      FB: FanFormat<'p, P> {
+    let mut fbs = FanBuilderState::<P, FB>::Uninitialized(format);
+    let (indices, rings) = match PolygonListExt::new(polygon_list).checked_ring_ranges() {
+        Ok(ring_ranges) => ring_ranges,
+        Err(error) => return fbs.complete(Err(TriangulationError::TrapezoidationError(error))),
+    };
+
+    let coords: Vec<[<P::Vertex as Vertex>::Coordinate; 2]> = indices.iter()
+        .map(|index| {
+            let v = polygon_list.get_vertex(index.clone());
+            [v.x(), v.y()]
+        })
+        .collect();
+    let mut clipper = EarClipper { coords: &coords, nodes: Vec::with_capacity(indices.len() * 3 / 2), min: [<<P::Vertex as Vertex>::Coordinate as num_traits::Zero>::zero(); 2], inv_size: None, triangles: Vec::new() };
+    for (outer, holes) in nest_rings(&coords, &rings) {
+        clipper.triangulate(&rings[outer], holes.iter().map(|&hole| &rings[hole]));
+    }
+
+    let result = clipper.triangles.iter().try_for_each(|&[a, b, c]| {
+        let (b, c) = if FB::Builder::WINDING == TriangleWinding::Clockwise { (c, b) } else { (b, c) };
+        fbs.new_fan(polygon_list, indices[a].clone(), indices[b].clone(), indices[c].clone()).map(|_| ())
+    });
+    fbs.complete(result)
+}
+
+/// Pair each outer ring with the holes directly inside it, by counting the rings around each ring.
+///
+/// As rings can't cross, a ring is inside another exactly when its first vertex is.
+fn nest_rings<C: Real>(coords: &[[C; 2]], rings: &[std::ops::Range<usize>]) -> Vec<(usize, Vec<usize>)> {
+    if rings.len() == 1 {
+        return vec![(0, Vec::new())];
+    }
+
+    let bounds: Vec<_> = rings.iter().map(|ring| {
+        coords[ring.clone()].iter().fold((coords[ring.start], coords[ring.start]), |(min, max), &[x, y]| {
+            ([min[0].min(x), min[1].min(y)], [max[0].max(x), max[1].max(y)])
+        })
+    }).collect();
+    let area = |r: usize| coords[rings[r].clone()].iter().zip(coords[rings[r].clone()].iter().cycle().skip(1))
+        .fold(C::zero(), |sum, (a, b)| sum + a[0] * b[1] - b[0] * a[1])
+        .abs();
+    let contains = |outer: usize, r: usize| {
+        let [px, py] = coords[rings[r].start];
+        let (min, max) = bounds[outer];
+        if px < min[0] || px > max[0] || py < min[1] || py > max[1] {
+            return false;
+        }
+        point_in_ring([px, py], &coords[rings[outer].clone()])
+    };
+
+    // The depth of each ring, and the innermost ring around it
+    let areas: Vec<C> = (0..rings.len()).map(area).collect();
+    let mut parents: Vec<(usize, Option<usize>)> = vec![(0, None); rings.len()];
+    for (r, (depth, parent)) in parents.iter_mut().enumerate() {
+        for outer in 0..rings.len() {
+            if outer != r && contains(outer, r) {
+                *depth += 1;
+                if parent.is_none_or(|parent| areas[outer] < areas[parent]) {
+                    *parent = Some(outer);
+                }
+            }
+        }
+    }
+
+    let mut nested: Vec<(usize, Vec<usize>)> = Vec::new();
+    let mut group_of = vec![NIL; rings.len()];
+    for (r, &(depth, _)) in parents.iter().enumerate() {
+        if depth % 2 == 0 {
+            group_of[r] = nested.len();
+            nested.push((r, Vec::new()));
+        }
+    }
+    for (r, &(depth, parent)) in parents.iter().enumerate() {
+        if let (1, Some(parent)) = (depth % 2, parent) {
+            nested[group_of[parent]].1.push(r);
+        }
+    }
+    nested
+}
+
+/// Whether `p` is inside `ring`, by counting the edges crossed by a ray to its right. Points on the boundary may go either way
+pub(crate) fn point_in_ring<C: Real>([px, py]: [C; 2], ring: &[[C; 2]]) -> bool {
+    let mut inside = false;
+    for (i, a) in ring.iter().enumerate() {
+        let b = ring[(i + 1) % ring.len()];
+        if (a[1] > py) != (b[1] > py) && px < a[0] + (py - a[1]) / (b[1] - a[1]) * (b[0] - a[0]) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+struct EarClipper<'c, C: Real> {
+    coords: &'c [[C; 2]],
+    nodes: Vec<Node<C>>,
+    min: [C; 2],
+    // The scale of the z-order grid, or None if the current ring isn't hashed
+    inv_size: Option<C>,
+    triangles: Vec<[usize; 3]>,
+}
+
+impl<C: Real> EarClipper<'_, C> {
+    fn triangulate<'r>(&mut self, outer: &'r std::ops::Range<usize>, holes: impl Iterator<Item = &'r std::ops::Range<usize>> + Clone) {
+        self.nodes.clear();
+        let Some(mut outer_node) = self.linked_list(outer, true) else { return };
+        let mut vertex_count = outer.len();
+        if self.nodes[outer_node].next == self.nodes[outer_node].prev {
+            return;
+        }
+
+        let mut queue: Vec<usize> = holes.clone().filter_map(|hole| {
+            vertex_count += hole.len();
+            self.linked_list(hole, false).map(|list| self.leftmost(list))
+        }).collect();
+        queue.sort_by(|&a, &b| self.nodes[a].x.partial_cmp(&self.nodes[b].x).unwrap_or(Ordering::Equal));
+        for hole in queue {
+            outer_node = self.eliminate_hole(hole, outer_node);
+        }
+
+        self.inv_size = None;
+        if vertex_count > HASH_THRESHOLD {
+            let points = std::iter::once(outer).chain(holes).flat_map(|ring| &self.coords[ring.clone()]);
+            let (min, max) = points.fold((self.coords[outer.start], self.coords[outer.start]), |(min, max), &[x, y]| {
+                ([min[0].min(x), min[1].min(y)], [max[0].max(x), max[1].max(y)])
+            });
+            let size = (max[0] - min[0]).max(max[1] - min[1]);
+            self.min = min;
+            if size != C::zero() {
+                self.inv_size = Some(<C as NumCast>::from(32767).unwrap() / size);
+            }
+        }
+
+        self.earcut_linked(Some(outer_node), 0);
+    }
+
+    fn insert_node(&mut self, i: usize, last: Option<usize>) -> usize {
+        let [x, y] = self.coords[i];
+        let p = self.nodes.len();
+        let (prev, next) = match last {
+            Some(last) => (last, self.nodes[last].next),
+            None => (p, p),
+        };
+        self.nodes.push(Node { i, x, y, prev, next, z: 0, prev_z: NIL, next_z: NIL });
+        if let Some(last) = last {
+            self.nodes[next].prev = p;
+            self.nodes[last].next = p;
+        }
+        p
+    }
+
+    fn remove_node(&mut self, p: usize) {
+        let Node { prev, next, prev_z, next_z, .. } = self.nodes[p];
+        self.nodes[next].prev = prev;
+        self.nodes[prev].next = next;
+        if prev_z != NIL {
+            self.nodes[prev_z].next_z = next_z;
+        }
+        if next_z != NIL {
+            self.nodes[next_z].prev_z = prev_z;
+        }
+    }
+
+    /// Link the ring's vertices counterclockwise, or clockwise for a hole
+    fn linked_list(&mut self, ring: &std::ops::Range<usize>, counterclockwise: bool) -> Option<usize> {
+        let ring_coords = &self.coords[ring.clone()];
+        let signed_area = ring_coords.iter().zip(ring_coords.iter().cycle().skip(1))
+            .fold(C::zero(), |sum, (a, b)| sum + a[0] * b[1] - b[0] * a[1]);
+        let mut last = None;
+        if counterclockwise == (signed_area > C::zero()) {
+            for i in ring.clone() {
+                last = Some(self.insert_node(i, last));
+            }
+        } else {
+            for i in ring.clone().rev() {
+                last = Some(self.insert_node(i, last));
+            }
+        }
+        let last = last?;
+        if self.equals(last, self.nodes[last].next) {
+            let next = self.nodes[last].next;
+            self.remove_node(last);
+            return Some(next);
+        }
+        Some(last)
+    }
+
+    fn equals(&self, a: usize, b: usize) -> bool {
+        self.nodes[a].x == self.nodes[b].x && self.nodes[a].y == self.nodes[b].y
+    }
+
+    /// Twice the signed area of the triangle, negative when counterclockwise
+    fn area(&self, p: usize, q: usize, r: usize) -> C {
+        let (p, q, r) = (&self.nodes[p], &self.nodes[q], &self.nodes[r]);
+        (q.y - p.y) * (r.x - q.x) - (q.x - p.x) * (r.y - q.y)
+    }
+
+    /// Remove duplicate and collinear vertices between `start` and `end`
+    fn filter_points(&mut self, start: usize, end: Option<usize>) -> usize {
+        let mut end = end.unwrap_or(start);
+        let mut p = start;
+        loop {
+            let (prev, next) = (self.nodes[p].prev, self.nodes[p].next);
+            if self.equals(p, next) || self.area(prev, p, next) == C::zero() {
+                self.remove_node(p);
+                p = prev;
+                end = prev;
+                if p == self.nodes[p].next {
+                    break;
+                }
+            } else {
+                p = next;
+                if p == end {
+                    break;
+                }
+            }
+        }
+        end
+    }
+
+    fn earcut_linked(&mut self, ear: Option<usize>, pass: u8) {
+        let Some(mut ear) = ear else { return };
+        if pass == 0 && self.inv_size.is_some() {
+            self.index_curve(ear);
+        }
+
+        let mut stop = ear;
+        while self.nodes[ear].prev != self.nodes[ear].next {
+            let (prev, next) = (self.nodes[ear].prev, self.nodes[ear].next);
+            let is_ear = if self.inv_size.is_some() { self.is_ear_hashed(ear) } else { self.is_ear(ear) };
+            if is_ear {
+                self.triangles.push([self.nodes[prev].i, self.nodes[ear].i, self.nodes[next].i]);
+                self.remove_node(ear);
+                // Skipping the next vertex leads to fewer sliver triangles
+                ear = self.nodes[next].next;
+                stop = ear;
+                continue;
+            }
+
+            ear = next;
+            if ear == stop {
+                // No ears were found in a full loop, so try to recover
+                match pass {
+                    0 => {
+                        let ear = self.filter_points(ear, None);
+                        self.earcut_linked(Some(ear), 1);
+                    },
+                    1 => {
+                        let ear = self.filter_points(ear, None);
+                        let ear = self.cure_local_intersections(ear);
+                        self.earcut_linked(Some(ear), 2);
+                    },
+                    _ => self.split_earcut(ear),
+                }
+                break;
+            }
+        }
+    }
+
+    fn bounds(&self, a: usize, b: usize, c: usize) -> ([C; 2], [C; 2]) {
+        let (a, b, c) = (&self.nodes[a], &self.nodes[b], &self.nodes[c]);
+        ([a.x.min(b.x).min(c.x), a.y.min(b.y).min(c.y)], [a.x.max(b.x).max(c.x), a.y.max(b.y).max(c.y)])
+    }
+
+    // Whether `p` is a reflex vertex within the triangle, which would stop it being an ear
+    fn blocks_ear(&self, [a, b, c]: [usize; 3], (min, max): ([C; 2], [C; 2]), p: usize) -> bool {
+        let node = &self.nodes[p];
+        node.x >= min[0] && node.x <= max[0] && node.y >= min[1] && node.y <= max[1]
+            && p != a && p != c
+            && self.point_in_triangle(a, b, c, p)
+            && self.area(node.prev, p, node.next) >= C::zero()
+    }
+
+    fn is_ear(&self, ear: usize) -> bool {
+        let (a, b, c) = (self.nodes[ear].prev, ear, self.nodes[ear].next);
+        if self.area(a, b, c) >= C::zero() {
+            return false;
+        }
+        let bounds = self.bounds(a, b, c);
+        let mut p = self.nodes[c].next;
+        while p != a {
+            if self.blocks_ear([a, b, c], bounds, p) {
+                return false;
+            }
+            p = self.nodes[p].next;
+        }
+        true
+    }
+
+    fn is_ear_hashed(&self, ear: usize) -> bool {
+        let (a, b, c) = (self.nodes[ear].prev, ear, self.nodes[ear].next);
+        if self.area(a, b, c) >= C::zero() {
+            return false;
+        }
+        let bounds = self.bounds(a, b, c);
+        let (min_z, max_z) = (self.z_order(bounds.0[0], bounds.0[1]), self.z_order(bounds.1[0], bounds.1[1]));
+
+        // Look for points in the triangle's bounding box, in both directions of z-order
+        let (mut p, mut n) = (self.nodes[ear].prev_z, self.nodes[ear].next_z);
+        while p != NIL && self.nodes[p].z >= min_z && n != NIL && self.nodes[n].z <= max_z {
+            if self.blocks_ear([a, b, c], bounds, p) || self.blocks_ear([a, b, c], bounds, n) {
+                return false;
+            }
+            p = self.nodes[p].prev_z;
+            n = self.nodes[n].next_z;
+        }
+        while p != NIL && self.nodes[p].z >= min_z {
+            if self.blocks_ear([a, b, c], bounds, p) {
+                return false;
+            }
+            p = self.nodes[p].prev_z;
+        }
+        while n != NIL && self.nodes[n].z <= max_z {
+            if self.blocks_ear([a, b, c], bounds, n) {
+                return false;
+            }
+            n = self.nodes[n].next_z;
+        }
+        true
+    }
+
+    /// Cut off triangles where two edges one apart cross, which can remain after removing collinear points
+    fn cure_local_intersections(&mut self, mut start: usize) -> usize {
+        let mut p = start;
+        loop {
+            let a = self.nodes[p].prev;
+            let p_next = self.nodes[p].next;
+            let b = self.nodes[p_next].next;
+            if !self.equals(a, b) && self.intersects(a, p, p_next, b) && self.locally_inside(a, b) && self.locally_inside(b, a) {
+                self.triangles.push([self.nodes[a].i, self.nodes[p].i, self.nodes[b].i]);
+                self.remove_node(p);
+                self.remove_node(p_next);
+                p = b;
+                start = b;
+            }
+            p = self.nodes[p].next;
+            if p == start {
+                break;
+            }
+        }
+        self.filter_points(p, None)
+    }
+
+    /// Split the ring along a valid diagonal, and triangulate each half
+    fn split_earcut(&mut self, start: usize) {
+        let mut a = start;
+        loop {
+            let mut b = self.nodes[self.nodes[a].next].next;
+            while b != self.nodes[a].prev {
+                if self.nodes[a].i != self.nodes[b].i && self.is_valid_diagonal(a, b) {
+                    let c = self.split_polygon(a, b);
+                    let a = self.filter_points(a, Some(self.nodes[a].next));
+                    let c = self.filter_points(c, Some(self.nodes[c].next));
+                    self.earcut_linked(Some(a), 0);
+                    self.earcut_linked(Some(c), 0);
+                    return;
+                }
+                b = self.nodes[b].next;
+            }
+            a = self.nodes[a].next;
+            if a == start {
+                break;
+            }
+        }
+    }
+
+    fn leftmost(&self, start: usize) -> usize {
+        let (mut p, mut leftmost) = (start, start);
+        loop {
+            let (node, left) = (&self.nodes[p], &self.nodes[leftmost]);
+            if node.x < left.x || (node.x == left.x && node.y < left.y) {
+                leftmost = p;
+            }
+            p = node.next;
+            if p == start {
+                return leftmost;
+            }
+        }
+    }
+
+    fn eliminate_hole(&mut self, hole: usize, outer_node: usize) -> usize {
+        let Some(bridge) = self.find_hole_bridge(hole, outer_node) else { return outer_node };
+        let bridge_reverse = self.split_polygon(bridge, hole);
+        self.filter_points(bridge_reverse, Some(self.nodes[bridge_reverse].next));
+        self.filter_points(bridge, Some(self.nodes[bridge].next))
+    }
+
+    /// Find a vertex of the outer ring which can be joined to the hole's leftmost vertex without crossing any edges
+    fn find_hole_bridge(&self, hole: usize, outer_node: usize) -> Option<usize> {
+        let (hx, hy) = (self.nodes[hole].x, self.nodes[hole].y);
+        let mut qx: Option<C> = None;
+        let mut m = None;
+
+        // Find the nearest segment crossed by a ray from the hole to the left, and its leftmost end
+        let mut p = outer_node;
+        loop {
+            let (node, next) = (&self.nodes[p], &self.nodes[self.nodes[p].next]);
+            if hy <= node.y && hy >= next.y && next.y != node.y {
+                let x = node.x + (hy - node.y) * (next.x - node.x) / (next.y - node.y);
+                if x <= hx && qx.is_none_or(|qx| x > qx) {
+                    qx = Some(x);
+                    m = Some(if node.x < next.x { p } else { node.next });
+                    if x == hx {
+                        return m;
+                    }
+                }
+            }
+            p = node.next;
+            if p == outer_node {
+                break;
+            }
+        }
+        let (mut m, qx) = (m?, qx?);
+
+        // Any reflex vertex within the triangle between the hole, the crossing and that end would block the bridge,
+        // so bridge to the one making the smallest angle with the ray instead
+        let stop = m;
+        let (mx, my) = (self.nodes[m].x, self.nodes[m].y);
+        let mut tan_min: Option<C> = None;
+        p = m;
+        loop {
+            let node = &self.nodes[p];
+            let (ax, cx) = if hy < my { (hx, qx) } else { (qx, hx) };
+            if hx >= node.x && node.x >= mx && hx != node.x && point_in_triangle([ax, hy], [mx, my], [cx, hy], [node.x, node.y]) {
+                let tan = (hy - node.y).abs() / (hx - node.x);
+                let m_node = &self.nodes[m];
+                if self.locally_inside(p, hole)
+                    && (tan_min.is_none_or(|tan_min| tan < tan_min) || (tan_min == Some(tan) && (node.x > m_node.x || (node.x == m_node.x && self.sector_contains_sector(m, p))))) {
+                    m = p;
+                    tan_min = Some(tan);
+                }
+            }
+            p = node.next;
+            if p == stop {
+                return Some(m);
+            }
+        }
+    }
+
+    fn sector_contains_sector(&self, m: usize, p: usize) -> bool {
+        self.area(self.nodes[m].prev, m, self.nodes[p].prev) < C::zero() && self.area(self.nodes[p].next, m, self.nodes[m].next) < C::zero()
+    }
+
+    fn index_curve(&mut self, start: usize) {
+        let mut p = start;
+        loop {
+            if self.nodes[p].z == 0 {
+                self.nodes[p].z = self.z_order(self.nodes[p].x, self.nodes[p].y);
+            }
+            self.nodes[p].prev_z = self.nodes[p].prev;
+            self.nodes[p].next_z = self.nodes[p].next;
+            p = self.nodes[p].next;
+            if p == start {
+                break;
+            }
+        }
+        let last = self.nodes[p].prev_z;
+        self.nodes[last].next_z = NIL;
+        self.nodes[p].prev_z = NIL;
+        self.sort_linked(p);
+    }
+
+    /// Sort the z-order links, with Simon Tatham's linked list merge sort
+    fn sort_linked(&mut self, mut list: usize) {
+        let mut in_size = 1;
+        loop {
+            let mut p = list;
+            list = NIL;
+            let mut tail = NIL;
+            let mut merges = 0;
+            while p != NIL {
+                merges += 1;
+                let mut q = p;
+                let mut p_size = 0;
+                for _ in 0..in_size {
+                    p_size += 1;
+                    q = self.nodes[q].next_z;
+                    if q == NIL {
+                        break;
+                    }
+                }
+                let mut q_size = in_size;
+                while p_size > 0 || (q_size > 0 && q != NIL) {
+                    let e;
+                    if p_size != 0 && (q_size == 0 || q == NIL || self.nodes[p].z <= self.nodes[q].z) {
+                        e = p;
+                        p = self.nodes[p].next_z;
+                        p_size -= 1;
+                    } else {
+                        e = q;
+                        q = self.nodes[q].next_z;
+                        q_size -= 1;
+                    }
+                    if tail != NIL {
+                        self.nodes[tail].next_z = e;
+                    } else {
+                        list = e;
+                    }
+                    self.nodes[e].prev_z = tail;
+                    tail = e;
+                }
+                p = q;
+            }
+            self.nodes[tail].next_z = NIL;
+            if merges <= 1 {
+                return;
+            }
+            in_size *= 2;
+        }
+    }
+
+    fn z_order(&self, x: C, y: C) -> u32 {
+        let inv_size = self.inv_size.unwrap_or_else(C::zero);
+        let spread = |v: C| {
+            let mut v = v.to_u32().unwrap_or(0);
+            v = (v | (v << 8)) & 0x00FF00FF;
+            v = (v | (v << 4)) & 0x0F0F0F0F;
+            v = (v | (v << 2)) & 0x33333333;
+            (v | (v << 1)) & 0x55555555
+        };
+        spread((x - self.min[0]) * inv_size) | (spread((y - self.min[1]) * inv_size) << 1)
+    }
+
+    fn point_in_triangle(&self, a: usize, b: usize, c: usize, p: usize) -> bool {
+        let [a, b, c, p] = [a, b, c, p].map(|n| [self.nodes[n].x, self.nodes[n].y]);
+        point_in_triangle(a, b, c, p)
+    }
+
+    fn is_valid_diagonal(&self, a: usize, b: usize) -> bool {
+        let (na, nb) = (&self.nodes[a], &self.nodes[b]);
+        self.nodes[na.next].i != nb.i && self.nodes[na.prev].i != nb.i && !self.intersects_polygon(a, b)
+            && ((self.locally_inside(a, b) && self.locally_inside(b, a) && self.middle_inside(a, b)
+                && (self.area(na.prev, a, nb.prev) != C::zero() || self.area(a, nb.prev, b) != C::zero()))
+                || (self.equals(a, b) && self.area(na.prev, a, na.next) > C::zero() && self.area(nb.prev, b, nb.next) > C::zero()))
+    }
+
+    fn intersects(&self, p1: usize, q1: usize, p2: usize, q2: usize) -> bool {
+        let sign = |v: C| if v > C::zero() { 1 } else if v < C::zero() { -1 } else { 0 };
+        let o1 = sign(self.area(p1, q1, p2));
+        let o2 = sign(self.area(p1, q1, q2));
+        let o3 = sign(self.area(p2, q2, p1));
+        let o4 = sign(self.area(p2, q2, q1));
+        (o1 != o2 && o3 != o4)
+            || (o1 == 0 && self.on_segment(p1, p2, q1))
+            || (o2 == 0 && self.on_segment(p1, q2, q1))
+            || (o3 == 0 && self.on_segment(p2, p1, q2))
+            || (o4 == 0 && self.on_segment(p2, q1, q2))
+    }
+
+    // Whether `q` lies within the bounds of segment `pr`, given the three are collinear
+    fn on_segment(&self, p: usize, q: usize, r: usize) -> bool {
+        let (p, q, r) = (&self.nodes[p], &self.nodes[q], &self.nodes[r]);
+        q.x <= p.x.max(r.x) && q.x >= p.x.min(r.x) && q.y <= p.y.max(r.y) && q.y >= p.y.min(r.y)
+    }
+
+    fn intersects_polygon(&self, a: usize, b: usize) -> bool {
+        let (ai, bi) = (self.nodes[a].i, self.nodes[b].i);
+        let mut p = a;
+        loop {
+            let next = self.nodes[p].next;
+            let (pi, ni) = (self.nodes[p].i, self.nodes[next].i);
+            if pi != ai && ni != ai && pi != bi && ni != bi && self.intersects(p, next, a, b) {
+                return true;
+            }
+            p = next;
+            if p == a {
+                return false;
+            }
+        }
+    }
+
+    /// Whether the diagonal from `a` to `b` starts off inside the ring
+    fn locally_inside(&self, a: usize, b: usize) -> bool {
+        let (prev, next) = (self.nodes[a].prev, self.nodes[a].next);
+        if self.area(prev, a, next) < C::zero() {
+            self.area(a, b, next) >= C::zero() && self.area(a, prev, b) >= C::zero()
+        } else {
+            self.area(a, b, prev) < C::zero() || self.area(a, next, b) < C::zero()
+        }
+    }
+
+    fn middle_inside(&self, a: usize, b: usize) -> bool {
+        let two = C::one() + C::one();
+        let (px, py) = ((self.nodes[a].x + self.nodes[b].x) / two, (self.nodes[a].y + self.nodes[b].y) / two);
+        let mut p = a;
+        let mut inside = false;
+        loop {
+            let (node, next) = (&self.nodes[p], &self.nodes[self.nodes[p].next]);
+            if (node.y > py) != (next.y > py) && next.y != node.y && px < (next.x - node.x) * (py - node.y) / (next.y - node.y) + node.x {
+                inside = !inside;
+            }
+            p = node.next;
+            if p == a {
+                return inside;
+            }
+        }
+    }
+
+    /// Join `a` and `b` with a diagonal, duplicating both so the ring splits in two. Returns the duplicate of `b`
+    fn split_polygon(&mut self, a: usize, b: usize) -> usize {
+        let a2 = self.nodes.len();
+        let b2 = a2 + 1;
+        let (an, bp) = (self.nodes[a].next, self.nodes[b].prev);
+        let (ai, bi) = (self.nodes[a].i, self.nodes[b].i);
+        let [ax, ay] = self.coords[ai];
+        let [bx, by] = self.coords[bi];
+        self.nodes.push(Node { i: ai, x: ax, y: ay, prev: b2, next: an, z: 0, prev_z: NIL, next_z: NIL });
+        self.nodes.push(Node { i: bi, x: bx, y: by, prev: bp, next: a2, z: 0, prev_z: NIL, next_z: NIL });
+        self.nodes[a].next = b;
+        self.nodes[b].prev = a;
+        self.nodes[an].prev = a2;
+        self.nodes[bp].next = b2;
+        b2
+    }
+}
+
+fn point_in_triangle<C: Real>(a: [C; 2], b: [C; 2], c: [C; 2], p: [C; 2]) -> bool {
+    (c[0] - p[0]) * (a[1] - p[1]) >= (a[0] - p[0]) * (c[1] - p[1])
+        && (a[0] - p[0]) * (b[1] - p[1]) >= (b[0] - p[0]) * (a[1] - p[1])
+        && (b[0] - p[0]) * (c[1] - p[1]) >= (c[0] - p[0]) * (b[1] - p[1])
+}
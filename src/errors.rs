@@ -2,31 +2,128 @@ use std::{error, fmt};
 
 use backtrace::Backtrace;
 
-/// Describes an error which occurred during trapezoidation
+use crate::{PolygonList, diagnose};
+
+/// Describes an error which occurred during trapezoidation.
+/// 
+/// `Index` is the [VertexIndex](crate::VertexIndex) of the [PolygonList](crate::PolygonList), identifying the vertices involved.
 #[derive(Debug)]
 #[non_exhaustive]
-pub enum TrapezoidationError {
+pub enum TrapezoidationError<Index> {
     /// A polygon was encountered with fewer than 3 vertices
-    NotEnoughVertices(usize),
-    /// A trapezoidation precondition was violated in the provided [PolygonList](crate::PolygonList), or a trapezoidation bug was encountered.
+    ShortRing {
+        /// The position of the polygon among the non-empty polygons of the [PolygonList](crate::PolygonList)
+        ring: usize,
+        /// The vertices of the polygon
+        vertices: Vec<Index>,
+    },
+    /// The polygons contain more vertices than the trapezoidation can index, which is given by [TrapezoidationError::MAX_VERTICES]
+    TooManyVertices(usize),
+    /// Two edges cross, or one edge touches another
+    EdgeCrossing {
+        /// The vertices at either end of the first edge
+        a: (Index, Index),
+        /// The vertices at either end of the second edge
+        b: (Index, Index),
+        /// A point shared by both edges
+        point: [f64; 2],
+    },
+    /// Two vertices have the same coordinates
+    DuplicateVertex {
+        /// The first vertex
+        a: Index,
+        /// The second vertex
+        b: Index,
+    },
+    /// A trapezoidation precondition was violated in the provided [PolygonList](crate::PolygonList) in a way which could not be identified, 
+    /// or a trapezoidation bug was encountered.
     InternalError(InternalError),
 }
 
-impl error::Error for TrapezoidationError { }
+impl<Index> TrapezoidationError<Index> {
+    /// The most vertices which can be trapezoidized, as the internal structures are indexed with 32 bits
+    pub const MAX_VERTICES: usize = (u32::MAX as usize - 1) / 4;
+
+    /// Replace an internal error with the precondition `polygon_list` violates, if one can be found, 
+    /// otherwise attach the seed to it
+    pub(crate) fn diagnose<'p, P: PolygonList<'p, Index = Index> + ?Sized>(self, polygon_list: &'p P, seed: Option<u64>) -> Self {
+        match self {
+            Self::InternalError(error) => diagnose::find_violation(polygon_list)
+                .unwrap_or_else(|| Self::InternalError(error.with_seed(seed))),
+            err => err,
+        }
+    }
+
+    /// Convert the indices of the vertices involved, for errors found in a view of the [PolygonList](crate::PolygonList)
+    pub(crate) fn map_index<NewIndex>(self, map: impl Fn(Index) -> NewIndex) -> TrapezoidationError<NewIndex> {
+        match self {
+            Self::ShortRing { ring, vertices } => TrapezoidationError::ShortRing { ring, vertices: vertices.into_iter().map(map).collect() },
+            Self::TooManyVertices(vertices) => TrapezoidationError::TooManyVertices(vertices),
+            Self::EdgeCrossing { a, b, point } => TrapezoidationError::EdgeCrossing { a: (map(a.0), map(a.1)), b: (map(b.0), map(b.1)), point },
+            Self::DuplicateVertex { a, b } => TrapezoidationError::DuplicateVertex { a: map(a), b: map(b) },
+            Self::InternalError(error) => TrapezoidationError::InternalError(error),
+        }
+    }
+}
+
+impl<Index: fmt::Debug> error::Error for TrapezoidationError<Index> { }
 
-impl fmt::Display for TrapezoidationError {
+impl<Index: fmt::Debug> fmt::Display for TrapezoidationError<Index> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::NotEnoughVertices(vertices) => write!(f, "Polygon only contains {} vertices", vertices),
+            Self::ShortRing { ring, vertices } => write!(f, "Polygon {} only contains {} vertices", ring, vertices.len()),
+            Self::TooManyVertices(vertices) => write!(f, "Polygons contain {} vertices, more than the maximum of {}", vertices, Self::MAX_VERTICES),
+            Self::EdgeCrossing { a, b, point } => write!(f, "Edge {:?}-{:?} crosses edge {:?}-{:?} at ({}, {})", a.0, a.1, b.0, b.1, point[0], point[1]),
+            Self::DuplicateVertex { a, b } => write!(f, "Vertices {:?} and {:?} have the same coordinates", a, b),
             Self::InternalError(error) => fmt::Display::fmt(error, f),
         }
     }
 }
 
+/// Describes an error which occurred while computing a signed distance field with
+/// [Trapezoidation::signed_distance_field](crate::Trapezoidation::signed_distance_field)
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SdfError<Index> {
+    /// The transform cannot be inverted
+    NonInvertibleTransform,
+    /// The grid has cells whose coordinates cannot be represented by the coordinate type
+    GridTooLarge,
+    /// An error occurred while trapezoidizing the polygons
+    TrapezoidationError(TrapezoidationError<Index>),
+}
+
+impl<Index> From<TrapezoidationError<Index>> for SdfError<Index> {
+    fn from(error: TrapezoidationError<Index>) -> Self {
+        Self::TrapezoidationError(error)
+    }
+}
+
+impl<Index: fmt::Debug> error::Error for SdfError<Index> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::TrapezoidationError(TrapezoidationError::InternalError(error)) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<Index: fmt::Debug> fmt::Display for SdfError<Index> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonInvertibleTransform => write!(f, "Transform is not invertible"),
+            Self::GridTooLarge => write!(f, "Grid coordinates cannot be represented by the coordinate type"),
+            Self::TrapezoidationError(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct InternalError {
     pub msg: String,
     pub backtrace: Backtrace,
+    /// The seed of the random segment order, if one was used. Passing it to `triangulate_with_seed` replays the failure.
+    pub seed: Option<u64>,
 }
 
 impl InternalError {
@@ -36,65 +133,92 @@ impl InternalError {
         Self {
             msg: msg.into(),
             backtrace: Backtrace::new_unresolved(),
+            seed: None,
         }
     }
+
+    pub(crate) fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = self.seed.or(seed);
+        self
+    }
 }
 
 impl fmt::Display for InternalError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}\n{:?}", self.msg, self.backtrace)
+        match self.seed {
+            Some(seed) => write!(f, "{} (seed {})\n{:?}", self.msg, seed, self.backtrace),
+            None => write!(f, "{}\n{:?}", self.msg, self.backtrace),
+        }
     }
 }
 
 impl error::Error for InternalError { }
 
-/// Describes an error which occurred during triangulation
+/// Describes an error which occurred during triangulation.
+/// 
+/// `Index` is the [VertexIndex](crate::VertexIndex) of the [PolygonList](crate::PolygonList), identifying the vertices involved.
 #[derive(Debug)]
 #[non_exhaustive]
-pub enum TriangulationError<FBError: error::Error> {
+pub enum TriangulationError<FBError: error::Error, Index> {
     /// An error occured during the trapezoidation step
-    TrapezoidationError(TrapezoidationError),
+    TrapezoidationError(TrapezoidationError<Index>),
     /// No vertices were included within the [PolygonList](crate::PolygonList)
     NoVertices,
     /// A triangulation precondition was violated in the provided [PolygonList](crate::PolygonList), 
     /// or a triangulation bug was encountered.
     InternalError(InternalError),
-    /// The [FanBuilder](crate::FanBuilder) returned an error.
+    /// The [FanBuilder](crate::FanBuilder) (or [TrapezoidBuilder](crate::TrapezoidBuilder)) returned an error.
     FanBuilder(FBError),
+    /// The cancellation flag of a [TriangulationJob](crate::TriangulationJob) was set before it completed
+    Cancelled,
+    /// The vector of a [SweepDirection::Vector](crate::SweepDirection::Vector) is zero or not finite
+    InvalidDirection,
     #[cfg(feature = "_debugging")]
     SvgOutput(std::io::Error),
 }
 
-impl<FBError: error::Error> TriangulationError<FBError> {
+impl<FBError: error::Error, Index> TriangulationError<FBError, Index> {
     #[inline(always)]
     pub(crate) fn internal(msg: impl Into<String>) -> Self {
-        TriangulationError::InternalError(InternalError {
-            msg: msg.into(),
-            backtrace: Backtrace::new_unresolved(),
-        })
+        TriangulationError::InternalError(InternalError::new(msg))
+    }
+
+    /// Replace an internal error with the precondition `polygon_list` violates, if one can be found, 
+    /// otherwise attach the seed to it
+    pub(crate) fn diagnose<'p, P: PolygonList<'p, Index = Index> + ?Sized>(self, polygon_list: &'p P, seed: Option<u64>) -> Self {
+        match self {
+            Self::InternalError(error) => match diagnose::find_violation(polygon_list) {
+                Some(violation) => Self::TrapezoidationError(violation),
+                None => Self::InternalError(error.with_seed(seed)),
+            },
+            Self::TrapezoidationError(error) => Self::TrapezoidationError(error.diagnose(polygon_list, seed)),
+            err => err,
+        }
     }
 }
 
-impl<FBError: error::Error> From<FBError> for TriangulationError<FBError> {
+impl<FBError: error::Error, Index> From<FBError> for TriangulationError<FBError, Index> {
     fn from(e: FBError) -> Self {
         Self::FanBuilder(e)
     }
 }
 
-impl<FBError: error::Error> fmt::Display for TriangulationError<FBError> {
+impl<FBError: error::Error, Index: fmt::Debug> fmt::Display for TriangulationError<FBError, Index> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::TrapezoidationError(error) => fmt::Display::fmt(error, f),
             Self::NoVertices => write!(f, "Polygon set contains no vertices"),
             Self::InternalError(error) => fmt::Display::fmt(error, f),
             Self::FanBuilder(error) => fmt::Display::fmt(error, f),
+            Self::Cancelled => write!(f, "Triangulation was cancelled"),
+            Self::InvalidDirection => write!(f, "Sweep direction is zero or not finite"),
             #[cfg(feature = "_debugging")]
             Self::SvgOutput(error) => fmt::Display::fmt(error, f),
         }
     }
 }
 
-impl<FBError: error::Error> std::error::Error for TriangulationError<FBError> {
+impl<FBError: error::Error, Index: fmt::Debug> std::error::Error for TriangulationError<FBError, Index> {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::InternalError(error) => Some(error),
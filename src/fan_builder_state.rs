@@ -1,6 +1,6 @@
 use std::{fmt, mem};
 
-use crate::{FanFormat, PolygonList, TriangulationError, FanBuilder};
+use crate::{FanError, FanFormat, FanResult, PolygonList, TriangulationError, FanBuilder};
 
 
 pub(crate) enum FanBuilderState<'p, P: PolygonList<'p> + ?Sized, FB: FanFormat<'p, P>> {
@@ -10,8 +10,8 @@ pub(crate) enum FanBuilderState<'p, P: PolygonList<'p> + ?Sized, FB: FanFormat<'
 }
 
 impl<'p, P: PolygonList<'p> + ?Sized, FB: FanFormat<'p, P>> FanBuilderState<'p, P, FB> {
-    pub(crate) fn new_fan(&mut self, polygon_list: &'p P, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<&mut FB::Builder, TriangulationError<<FB::Builder as FanBuilder<'p, P>>::Error>> {
-        fn set_initialized<'f, 'pp, P: PolygonList<'pp> + ?Sized, FB: FanFormat<'pp, P>>(s: &'f mut FanBuilderState<'pp, P, FB>, fb: FB::Builder) -> Result<&'f mut FB::Builder, TriangulationError<<FB::Builder as FanBuilder<'pp, P>>::Error>> {
+    pub(crate) fn new_fan(&mut self, polygon_list: &'p P, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<&mut FB::Builder, FanError<'p, P, FB>> {
+        fn set_initialized<'f, 'pp, P: PolygonList<'pp> + ?Sized, FB: FanFormat<'pp, P>>(s: &'f mut FanBuilderState<'pp, P, FB>, fb: FB::Builder) -> Result<&'f mut FB::Builder, FanError<'pp, P, FB>> {
             *s = FanBuilderState::Initialized(fb);
             if let FanBuilderState::Initialized(fb) = s {
                 Ok(fb)
@@ -38,7 +38,7 @@ impl<'p, P: PolygonList<'p> + ?Sized, FB: FanFormat<'p, P>> FanBuilderState<'p,
         }
     }
 
-    pub(crate) fn complete(self, result: Result<(), TriangulationError<<FB::Builder as FanBuilder<'p, P>>::Error>>) -> Result<<FB::Builder as FanBuilder<'p, P>>::Output, TriangulationError<<FB::Builder as FanBuilder<'p, P>>::Error>> {
+    pub(crate) fn complete(self, result: Result<(), FanError<'p, P, FB>>) -> FanResult<'p, P, FB> {
         match (self, result) {
             // Success
             (FanBuilderState::Initialized(fb), Ok(())) => fb.build().map_err(Into::into),
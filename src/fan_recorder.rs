@@ -0,0 +1,107 @@
+use std::{convert::Infallible, marker::PhantomData};
+
+use crate::{FanBuilder, FanError, FanFormat, PolygonList, TriangleWinding, TriangulationError, fan_builder_state::FanBuilderState};
+
+/// Triangle fans recorded by a [FanRecorder], which can be replayed into another [FanFormat] once they are known to be complete
+#[derive(Debug, Clone)]
+pub(crate) struct RecordedFans<Index> {
+    indices: Vec<Index>,
+    fan_starts: Vec<usize>,
+}
+
+impl<Index: Clone> RecordedFans<Index> {
+    pub fn new() -> Self {
+        Self { indices: Vec::new(), fan_starts: Vec::new() }
+    }
+
+    pub fn clear(&mut self) {
+        self.indices.clear();
+        self.fan_starts.clear();
+    }
+
+    /// Pass the recorded fans on to `fbs`, in the order they were recorded, converting each index with `map`
+    pub fn replay_mapped<'p, P, FB>(&self, fbs: &mut FanBuilderState<'p, P, FB>, polygon_list: &'p P, map: impl Fn(&Index) -> P::Index) -> Result<(), FanError<'p, P, FB>>
+    where P: PolygonList<'p> + ?Sized,
+          FB: FanFormat<'p, P> {
+        for (i, &start) in self.fan_starts.iter().enumerate() {
+            let end = self.fan_starts.get(i + 1).copied().unwrap_or(self.indices.len());
+            let fan = &self.indices[start..end];
+            let fb = fbs.new_fan(polygon_list, map(&fan[0]), map(&fan[1]), map(&fan[2]))?;
+            for vi in &fan[3..] {
+                fb.extend_fan(map(vi))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Records the fans triangulated from any [PolygonList] with indices of type `I` (by default, those of `P`),
+/// using the winding of the [FanBuilder] `W` they will be replayed into
+pub(crate) struct FanRecorder<'p, P: PolygonList<'p> + ?Sized, W, I = <P as PolygonList<'p>>::Index> {
+    fans: RecordedFans<I>,
+    _phantom: PhantomData<fn() -> (&'p P, W)>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, W: FanBuilder<'p, P>, I: Clone> FanRecorder<'p, P, W, I> {
+    /// Record into `fans`, which are cleared first so their allocations can be reused
+    pub fn new(mut fans: RecordedFans<I>) -> Self {
+        fans.clear();
+        Self { fans, _phantom: PhantomData }
+    }
+}
+
+impl<'g, 'p, G, P, W, I> FanFormat<'g, G> for FanRecorder<'p, P, W, I>
+where G: PolygonList<'g, Index = I> + ?Sized,
+      P: PolygonList<'p> + ?Sized,
+      W: FanBuilder<'p, P>,
+      I: Clone {
+    type Builder = Self;
+
+    fn initialize(mut self, _polygon_list: &'g G, vi0: G::Index, vi1: G::Index, vi2: G::Index) -> Result<Self::Builder, Infallible> {
+        FanBuilder::<'g, G>::new_fan(&mut self, vi0, vi1, vi2)?;
+        Ok(self)
+    }
+}
+
+impl<'g, 'p, G, P, W, I> FanBuilder<'g, G> for FanRecorder<'p, P, W, I>
+where G: PolygonList<'g, Index = I> + ?Sized,
+      P: PolygonList<'p> + ?Sized,
+      W: FanBuilder<'p, P>,
+      I: Clone {
+    type Output = RecordedFans<I>;
+    type Error = Infallible;
+
+    const WINDING: TriangleWinding = W::WINDING;
+
+    fn new_fan(&mut self, vi0: G::Index, vi1: G::Index, vi2: G::Index) -> Result<(), Self::Error> {
+        self.fans.fan_starts.push(self.fans.indices.len());
+        self.fans.indices.extend([vi0, vi1, vi2]);
+        Ok(())
+    }
+
+    fn extend_fan(&mut self, vi: G::Index) -> Result<(), Self::Error> {
+        self.fans.indices.push(vi);
+        Ok(())
+    }
+
+    fn build(self) -> Result<Self::Output, Self::Error> {
+        Ok(self.fans)
+    }
+
+    fn fail(self, _error: &TriangulationError<Self::Error, G::Index>) { }
+}
+
+/// Convert an error from triangulating into a [FanRecorder], which can't have come from the recorder itself,
+/// converting the indices of the vertices involved with `map`
+pub(crate) fn with_fan_builder_error<E: std::error::Error, I, Index>(error: TriangulationError<Infallible, I>, map: impl Fn(I) -> Index) -> TriangulationError<E, Index> {
+    match error {
+        TriangulationError::TrapezoidationError(error) => TriangulationError::TrapezoidationError(error.map_index(map)),
+        TriangulationError::NoVertices => TriangulationError::NoVertices,
+        TriangulationError::InternalError(error) => TriangulationError::InternalError(error),
+        TriangulationError::FanBuilder(error) => match error { },
+        TriangulationError::Cancelled => TriangulationError::Cancelled,
+        TriangulationError::InvalidDirection => TriangulationError::InvalidDirection,
+        #[cfg(feature = "_debugging")]
+        TriangulationError::SvgOutput(error) => TriangulationError::SvgOutput(error),
+    }
+}
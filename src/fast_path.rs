@@ -0,0 +1,219 @@
+//! Triangulation without a trapezoidation, for inputs of a single ring which is convex or already y-monotone.
+//!
+//! A convex ring is a single fan around any of its vertices. A y-monotone ring splits into unimonotone pieces
+//! along the diagonals between consecutive vertices on opposite chains, which [Monotone::build_fans] triangulates
+//! just as it does the pieces found by the trapezoidation.
+
+use std::cmp::Ordering;
+
+use crate::{Coords, FanBuilder, FanError, FanFormat, FanResult, PolygonElement, PolygonList, PolygonListExt, Real, TriangleWinding, TriangulationError, Vertex, diagnose::orientation, fan_builder_state::FanBuilderState, monotone::MonotoneBuilder};
+
+pub(crate) type Ring<Index, C> = Vec<(Index, Coords<C>)>;
+
+enum Shape {
+    Convex { counterclockwise: bool },
+    // The position of the topmost vertex in the ring
+    Monotone { top: usize },
+}
+
+/// Triangulate `polygon_list` directly if it is a single convex or y-monotone ring.
+/// Otherwise, `format` is returned untouched so it can be passed on to the trapezoidation.
+pub(crate) fn try_triangulate<'p, P, FB>(polygon_list: &'p P, format: FB) -> Result<FanResult<'p, P, FB>, FB>
+where P: PolygonList<'p> + ?Sized,
+      FB: FanFormat<'p, P> {
+    let ps = PolygonListExt::new(polygon_list);
+    let Some(shape) = classify(ps) else {
+        return Err(format);
+    };
+
+    let mut ring = Vec::with_capacity(ps.vertex_count());
+    for element in ps.iter_polygon_vertices() {
+        if let PolygonElement::ContinuePolygon(index) = element.into() {
+            let coords = ps[index.clone()].coords();
+            ring.push((index, coords));
+        }
+    }
+
+    match shape {
+        Shape::Convex { counterclockwise } => {
+            let mut fbs = FanBuilderState::<P, FB>::Uninitialized(format);
+            let result = build_convex_fan(&ring, counterclockwise, ps, &mut fbs);
+            Ok(fbs.complete(result))
+        },
+        Shape::Monotone { top } => {
+            let Some(merged) = merge_chains(&ring, top) else {
+                return Err(format);
+            };
+            let mut fbs = FanBuilderState::<P, FB>::Uninitialized(format);
+            let result = build_monotone_fans(&ring, &merged, ps, &mut fbs);
+            Ok(fbs.complete(result))
+        },
+    }
+}
+
+/// Check in one pass whether `ps` is a single ring, of at least 3 vertices, which is convex or y-monotone.
+/// Rings with consecutive repeated vertices are neither, and are left to the trapezoidation
+fn classify<'p, P: PolygonList<'p> + ?Sized>(ps: PolygonListExt<'p, P>) -> Option<Shape> {
+    if ps.vertex_count() < 3 {
+        return None;
+    }
+
+    let mut classifier = Classifier::default();
+    // The first two vertices, and the last two seen so far
+    let (mut first, mut last) = ([Coords::zero(); 2], [Coords::zero(); 2]);
+    let mut len = 0;
+    let mut ended = false;
+    for element in ps.iter_polygon_vertices() {
+        match element.into() {
+            PolygonElement::ContinuePolygon(index) => {
+                if ended {
+                    return None;
+                }
+                let c = ps[index].coords();
+                if len < 2 {
+                    first[len] = c;
+                } else {
+                    classifier.turn(last[0], last[1], c, len - 1)?;
+                }
+                last = [last[1], c];
+                len += 1;
+            },
+            PolygonElement::NewPolygon => ended = len > 0,
+        }
+    }
+    if len < 3 {
+        return None;
+    }
+    classifier.turn(last[0], last[1], first[0], len - 1)?;
+    classifier.turn(last[1], first[0], first[1], 0)?;
+
+    // With a single maximum, turning the same way at every vertex makes exactly one revolution
+    let top = classifier.top?;
+    Some(if classifier.left_turns != classifier.right_turns {
+        Shape::Convex { counterclockwise: classifier.left_turns }
+    } else {
+        Shape::Monotone { top }
+    })
+}
+
+#[derive(Default)]
+struct Classifier {
+    top: Option<usize>,
+    left_turns: bool,
+    right_turns: bool,
+}
+
+impl Classifier {
+    /// Account for the turn from `a` through `b` (at `position` in the ring) to `c`.
+    /// Returns `None` if the ring can't be convex or monotone
+    fn turn<C: Real>(&mut self, a: Coords<C>, b: Coords<C>, c: Coords<C>, position: usize) -> Option<()> {
+        let (incoming, outgoing) = (b.partial_cmp(&a)?, c.partial_cmp(&b)?);
+        if incoming == Ordering::Equal || outgoing == Ordering::Equal {
+            return None;
+        }
+        // Any more than one local maximum and the ring isn't monotone
+        if incoming == Ordering::Greater && outgoing == Ordering::Less && self.top.replace(position).is_some() {
+            return None;
+        }
+
+        let cross = (b.x() - a.x()) * (c.y() - b.y()) - (b.y() - a.y()) * (c.x() - b.x());
+        if cross > C::zero() {
+            self.left_turns = true;
+        } else if cross < C::zero() {
+            self.right_turns = true;
+        } else {
+            // Collinear vertices still form a monotone ring, but never a strictly convex one
+            self.left_turns = true;
+            self.right_turns = true;
+        }
+        Some(())
+    }
+}
+
+fn build_convex_fan<'p, P, FB>(ring: &Ring<P::Index, <P::Vertex as Vertex>::Coordinate>, counterclockwise: bool, ps: PolygonListExt<'p, P>, fbs: &mut FanBuilderState<'p, P, FB>) -> Result<(), FanError<'p, P, FB>>
+where P: PolygonList<'p> + ?Sized,
+      FB: FanFormat<'p, P> {
+    let n = ring.len();
+    let index = |i: usize| ring[i].0.clone();
+    if counterclockwise == (FB::Builder::WINDING == TriangleWinding::Counterclockwise) {
+        let fb = fbs.new_fan(ps.polygon_list(), index(0), index(1), index(2))?;
+        for i in 3..n {
+            fb.extend_fan(index(i))?;
+        }
+    } else {
+        let fb = fbs.new_fan(ps.polygon_list(), index(0), index(n - 1), index(n - 2))?;
+        for i in (1..n - 2).rev() {
+            fb.extend_fan(index(i))?;
+        }
+    }
+    Ok(())
+}
+
+/// Merge the two chains of a y-monotone ring from the top vertex down to the bottom vertex,
+/// returning the position of each vertex in the ring and whether it follows the top vertex in the ring.
+/// Returns `None` if the chains cross or touch anywhere but the top and bottom vertices
+pub(crate) fn merge_chains<Index, C: Real>(ring: &Ring<Index, C>, top: usize) -> Option<Vec<(usize, bool)>> {
+    let n = ring.len();
+    let mut merged = Vec::with_capacity(n);
+    merged.push((top, true));
+    let (mut forward, mut backward) = ((top + 1) % n, (top + n - 1) % n);
+    // Which side of the backward chain the forward chain is on. Each vertex is checked against the edge
+    // of the opposite chain spanning its height, and the chains cross if the side ever changes
+    let mut side = None;
+    while forward != backward {
+        let ordering = ring[forward].1.partial_cmp(&ring[backward].1)?;
+        let (c, upper, lower, forward_side) = match ordering {
+            Ordering::Greater => (ring[forward].1, ring[(backward + 1) % n].1, ring[backward].1, true),
+            Ordering::Less => (ring[backward].1, ring[(forward + n - 1) % n].1, ring[forward].1, false),
+            Ordering::Equal => return None,
+        };
+        let orientation = match orientation(upper, lower, c) {
+            Ordering::Equal => return None,
+            orientation if forward_side => orientation,
+            orientation => orientation.reverse(),
+        };
+        if side.replace(orientation).is_some_and(|side| side != orientation) {
+            return None;
+        }
+
+        if forward_side {
+            merged.push((forward, true));
+            forward = (forward + 1) % n;
+        } else {
+            merged.push((backward, false));
+            backward = (backward + n - 1) % n;
+        }
+    }
+    // Both chains end at the bottom vertex
+    merged.push((forward, true));
+    Some(merged)
+}
+
+pub(crate) fn build_monotone_fans<'p, P, FB>(ring: &Ring<P::Index, <P::Vertex as Vertex>::Coordinate>, merged: &[(usize, bool)], ps: PolygonListExt<'p, P>, fbs: &mut FanBuilderState<'p, P, FB>) -> Result<(), FanError<'p, P, FB>>
+where P: PolygonList<'p> + ?Sized,
+      FB: FanFormat<'p, P> {
+    let build_piece = |piece: &[(usize, bool)], fbs: &mut FanBuilderState<'p, P, FB>| {
+        let (index, coords) = ring[piece[0].0].clone();
+        let mut monotone = MonotoneBuilder::new(index, coords);
+        for &(i, _) in &piece[1..] {
+            let (index, coords) = ring[i].clone();
+            monotone.add_vertex(index, coords);
+        }
+        match monotone.build().map_err(TriangulationError::InternalError)? {
+            Some(monotone) => monotone.build_fans::<P, FB>(ps, fbs),
+            None => Ok(()),
+        }
+    };
+
+    // Consecutive vertices on opposite chains are joined by a diagonal. Between two diagonals,
+    // all vertices but the lowest are on one chain, and the highest and lowest are joined by an edge of the other chain
+    let last = merged.len() - 1;
+    let mut start = 0;
+    for i in 1..last - 1 {
+        if merged[i].1 != merged[i + 1].1 {
+            build_piece(&merged[start..=i + 1], fbs)?;
+            start = i;
+        }
+    }
+    build_piece(&merged[start..], fbs)
+}
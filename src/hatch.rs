@@ -0,0 +1,201 @@
+//! Hatch line generation for filling polygon interiors
+//!
+//! [hatch] intersects the polygons with evenly spaced parallel lines, as used to fill shapes with a pen plotter or laser cutter.
+//! The lines can be joined into back-and-forth paths with [Hatch::boustrophedon], so the pen is lifted less often.
+//!
+//! ```
+//! # use triangulate::hatch::hatch;
+//! let square = vec![vec![[0f32, 0.], [4., 0.], [4., 4.], [0., 4.]]];
+//! let hatch = hatch(&square, 1., 0.).expect("Trapezoidation failed");
+//! assert_eq!(hatch.lines().len(), 4);
+//! // Every line ends on the same edge as the next one starts, so a single path covers them all
+//! assert_eq!(hatch.boustrophedon().len(), 1);
+//! ```
+
+use std::{cmp::Ordering, error, fmt::{self, Debug}};
+
+use num_traits::NumCast;
+
+use crate::{PolygonList, PolygonListExt, Real, TrapezoidationError, Vertex};
+
+/// Describes an error which occurred while generating hatch lines with [hatch]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum HatchError<Index> {
+    /// The spacing is not a positive number
+    InvalidSpacing,
+    /// The spacing is so small that more than [HatchError::MAX_LINES] lines would be generated, or so small relative to the
+    /// coordinates that the lines can't be numbered
+    TooManyLines,
+    /// An error occurred while trapezoidizing the polygons
+    TrapezoidationError(TrapezoidationError<Index>),
+}
+
+impl<Index> HatchError<Index> {
+    /// The most hatch lines generated by a single call to [hatch]
+    pub const MAX_LINES: usize = 1 << 26;
+
+    fn map_index<NewIndex>(self, map: impl Fn(Index) -> NewIndex) -> HatchError<NewIndex> {
+        match self {
+            Self::InvalidSpacing => HatchError::InvalidSpacing,
+            Self::TooManyLines => HatchError::TooManyLines,
+            Self::TrapezoidationError(error) => HatchError::TrapezoidationError(error.map_index(map)),
+        }
+    }
+}
+
+impl<Index> From<TrapezoidationError<Index>> for HatchError<Index> {
+    fn from(error: TrapezoidationError<Index>) -> Self {
+        Self::TrapezoidationError(error)
+    }
+}
+
+impl<Index: Debug> error::Error for HatchError<Index> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::TrapezoidationError(TrapezoidationError::InternalError(error)) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<Index: Debug> fmt::Display for HatchError<Index> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSpacing => write!(f, "Hatch spacing must be positive"),
+            Self::TooManyLines => write!(f, "Hatch spacing would generate more than {} lines", Self::MAX_LINES),
+            Self::TrapezoidationError(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+/// The part of one hatch line within a single region of the rotated polygons.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct HatchSpan<C: Real> {
+    /// The line's index, such that `y = row * spacing`
+    pub row: i64,
+    pub y: C,
+    pub x: [C; 2],
+    /// The segments bounding the span on the left and right
+    pub edges: [usize; 2],
+}
+
+/// A move along the boundary from one end of a line to the same end of a line in the next row
+#[derive(Debug, Clone, PartialEq)]
+struct Link<C: Real> {
+    line: usize,
+    vertices: Vec<[C; 2]>,
+}
+
+/// Hatch lines generated by [hatch]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hatch<C: Real> {
+    lines: Vec<[[C; 2]; 2]>,
+    // The links from the start and end of each line
+    links: Vec<[Option<Link<C>>; 2]>,
+}
+
+/// Fill the interior of the polygons with parallel lines `spacing` apart.
+///
+/// The lines point in the direction `angle`, in radians counterclockwise from the x axis,
+/// and pass through the points `[-sin(angle), cos(angle)] * k * spacing` for integer `k`, so neighbouring shapes hatched with
+/// the same parameters line up. A line running exactly along an edge is only included where the interior is on its left.
+///
+/// The polygons are rotated so the lines become horizontal, then trapezoidized, so this takes expected O(*n* log *n* + *k*) time
+/// for *n* vertices and *k* lines.
+///
+/// Returns [HatchError::InvalidSpacing] if `spacing` is not positive, and [HatchError::TooManyLines] rather than generating
+/// more than [HatchError::MAX_LINES] lines. Where the coordinates are too large for `spacing` to be represented between them,
+/// neighbouring lines may coincide.
+pub fn hatch<'p, C: Real + Debug, P: PolygonList<'p> + ?Sized>(polygon_list: &'p P, spacing: C, angle: C) -> Result<Hatch<C>, HatchError<P::Index>>
+where P::Vertex: Vertex<Coordinate = C> {
+    if spacing.partial_cmp(&C::zero()) != Some(Ordering::Greater) {
+        return Err(HatchError::InvalidSpacing);
+    }
+    let (sin, cos) = angle.sin_cos();
+
+    // Rotate by -angle, so the hatch lines are horizontal
+    // The original index of each rotated vertex is kept for reporting errors
+    let (indices, ring_ranges) = PolygonListExt::new(polygon_list).ring_ranges();
+    let rings: Vec<Vec<[C; 2]>> = ring_ranges.iter()
+        .map(|ring| indices[ring.clone()].iter().map(|index| {
+            let v = polygon_list.get_vertex(index.clone());
+            [cos * v.x() + sin * v.y(), cos * v.y() - sin * v.x()]
+        }).collect())
+        .collect();
+    if rings.is_empty() {
+        return Ok(Hatch { lines: Vec::new(), links: Vec::new() });
+    }
+
+    let original = |error: HatchError<[usize; 2]>| error.map_index(|[r, i]| indices[ring_ranges[r].start + i].clone());
+    let trapezoidation = rings.trapezoidize().map_err(|error| original(error.into()))?;
+    let mut spans = trapezoidation.hatch_spans(spacing).map_err(original)?;
+    spans.sort_by(|a, b| (a.row, a.x[0]).partial_cmp(&(b.row, b.x[0])).unwrap_or(Ordering::Equal));
+    // Spans touching at a vertex (e.g. where a line grazes the bottom of a hole) are drawn as one line
+    spans.dedup_by(|span, prev| {
+        let touching = prev.row == span.row && prev.x[1] == span.x[0];
+        if touching {
+            prev.x[1] = span.x[1];
+            prev.edges[1] = span.edges[1];
+        }
+        touching
+    });
+
+    let rotate_back = |[x, y]: [C; 2]| [cos * x - sin * y, sin * x + cos * y];
+    let links = spans.iter().map(|span| [0, 1].map(|side| {
+        let next_row = span.row + 1;
+        let (si, vertices) = trapezoidation.boundary_above(span.edges[side], <C as NumCast>::from(next_row)? * spacing)?;
+        let row_start = spans.partition_point(|s| s.row < next_row);
+        // Only one span in a row can be bounded by a given segment on a given side
+        let line = spans[row_start..].iter()
+            .take_while(|s| s.row == next_row)
+            .position(|s| s.edges[side] == si)?;
+        Some(Link { line: row_start + line, vertices: vertices.into_iter().map(rotate_back).collect() })
+    })).collect();
+    let lines = spans.iter()
+        .map(|span| span.x.map(|x| rotate_back([x, span.y])))
+        .collect();
+    Ok(Hatch { lines, links })
+}
+
+impl<C: Real> Hatch<C> {
+    /// The hatch lines, each given as its start and end point.
+    ///
+    /// Lines are ordered by their offset perpendicular to the hatch direction, then along it, and all point in the hatch direction.
+    pub fn lines(&self) -> &[[[C; 2]; 2]] {
+        &self.lines
+    }
+
+    /// Join the lines into back-and-forth paths, alternating direction on each line.
+    ///
+    /// A line is joined to one in the next row when the boundary leads from its end to the next line without turning back,
+    /// so the connecting move follows the boundary and never leaves the polygons. The pen only needs to be lifted between paths.
+    /// Every line appears in exactly one path, as two consecutive points.
+    pub fn boustrophedon(&self) -> Vec<Vec<[C; 2]>> {
+        let mut used = vec![false; self.lines.len()];
+        let mut paths = Vec::new();
+        for first in 0..self.lines.len() {
+            if used[first] {
+                continue;
+            }
+            used[first] = true;
+            let mut path = self.lines[first].to_vec();
+
+            // The side of the current line which the path has reached
+            let (mut current, mut side) = (first, 1);
+            while let Some(link) = &self.links[current][side] {
+                if used[link.line] {
+                    break;
+                }
+                used[link.line] = true;
+                path.extend_from_slice(&link.vertices);
+                path.push(self.lines[link.line][side]);
+                path.push(self.lines[link.line][1 - side]);
+                current = link.line;
+                side = 1 - side;
+            }
+            paths.push(path);
+        }
+        paths
+    }
+}
@@ -1,7 +1,10 @@
-use std::{fmt, marker::PhantomData, num::NonZeroUsize, ops, cmp, hash};
+use std::{fmt, marker::PhantomData, num::NonZeroU32, ops, cmp, hash};
 
+/// An index into a `Vec<T>`, stored in 32 bits to keep the trapezoidation's links compact.
+///
+/// `Option<Idx<T>>` is also 32 bits, as the index is stored offset by one.
 #[repr(transparent)]
-pub struct Idx<T>(NonZeroUsize, PhantomData<T>);
+pub struct Idx<T>(NonZeroU32, PhantomData<T>);
 
 impl<T> fmt::Debug for Idx<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -20,9 +23,14 @@ impl<T: IdxDisplay> fmt::Display for Idx<T> {
 }
 
 impl<T> Idx<T> {
+    /// The largest index which can be stored
+    pub const MAX: usize = u32::MAX as usize - 1;
+
     pub fn new(index: usize) -> Self {
-        let index = index + 1;
-        Self(unsafe { NonZeroUsize::new_unchecked(index) }, Default::default())
+        assert!(index <= Self::MAX, "Index {} does not fit in 32 bits", index);
+        // unsafe: `index` is at most `u32::MAX - 1`, so adding one neither overflows nor gives 0
+        let index = index as u32 + 1;
+        Self(unsafe { NonZeroU32::new_unchecked(index) }, Default::default())
     }
 
     pub fn advance(&mut self) -> Self {
@@ -73,7 +81,7 @@ impl<T> Idx<T> {
     }
 
     pub fn usize(&self) -> usize {
-        self.0.get() - 1
+        self.0.get() as usize - 1
     }
 }
 
@@ -81,12 +89,30 @@ impl<T> Idx<T> {
 // https://github.com/rust-lang/rust/issues/26925
 impl<T> Clone for Idx<T> {
     fn clone(&self) -> Self {
-        Self(self.0, Default::default())
+        *self
     }
 }
 
 impl<T> Copy for Idx<T> { }
 
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for Idx<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.usize() as u32)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Idx<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let index = u32::deserialize(deserializer)? as usize;
+        if index > Self::MAX {
+            return Err(serde::de::Error::custom(format!("Index {} does not fit in 32 bits", index)));
+        }
+        Ok(Self::new(index))
+    }
+}
+
 impl<T> PartialEq for Idx<T> {
     fn eq(&self, other: &Self) -> bool {
         self.0 == other.0
@@ -136,7 +162,7 @@ impl<T> cmp::PartialEq<usize> for Idx<T> {
 
 impl<T> cmp::PartialOrd for Idx<T> {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-        self.0.partial_cmp(&other.0)
+        Some(self.cmp(other))
     }
 }
 
@@ -190,64 +216,113 @@ impl<T> ops::IndexMut<Idx<T>> for [T] {
     }
 }
 
-pub trait SliceExt<T> {
-    fn iter_index(&self) -> SliceIndexIter<T>;
-}
+/// One field of every `T` in a structure of arrays, indexed by [Idx<T>] without bounds checks, as for a [Vec<T>]
+pub(crate) struct Column<T, C>(Vec<C>, PhantomData<T>);
 
-pub trait VecExt<T> : SliceExt<T> {
-    fn push_get_index(&mut self, value: T) -> Idx<T>;
+impl<T, C> Column<T, C> {
+    pub fn new() -> Self {
+        Self(Vec::new(), PhantomData)
+    }
 
-    fn next_index(&self) -> Idx<T>;
-}
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn push(&mut self, value: C) {
+        self.0.push(value);
+    }
 
-impl<T> SliceExt<T> for [T] {
-    fn iter_index(&self) -> SliceIndexIter<T> {
-        SliceIndexIter::new(self)
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional);
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.0.shrink_to_fit();
+    }
+
+    /// The bytes allocated for the column
+    #[cfg(test)]
+    pub fn heap_bytes(&self) -> usize {
+        self.0.capacity() * std::mem::size_of::<C>()
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn iter(&self) -> std::slice::Iter<'_, C> {
+        self.0.iter()
     }
 }
 
-impl<T> SliceExt<T> for Vec<T> {
-    fn iter_index(&self) -> SliceIndexIter<T> {
-        SliceIndexIter::new(&self[..])
+impl<T, C> Default for Column<T, C> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-impl<T> VecExt<T> for Vec<T> {
-    fn push_get_index(&mut self, value: T) -> Idx<T> {
-        let index = Idx::new(self.len());
-        self.push(value);
-        index
+impl<T, C> FromIterator<C> for Column<T, C> {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        Self(iter.into_iter().collect(), PhantomData)
     }
+}
 
-    fn next_index(&self) -> Idx<T> {
-        Idx::new(self.len())
+impl<T, C: Clone> Clone for Column<T, C> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
     }
 }
 
-pub struct SliceIndexIter<'a, T> {
-    slice: &'a [T],
-    index: usize,
+impl<T, C> ops::Index<Idx<T>> for Column<T, C> {
+    type Output = C;
+
+    #[inline]
+    fn index(&self, index: Idx<T>) -> &Self::Output {
+        unsafe {
+            self.0.get_unchecked(index.usize())
+        }
+    }
 }
 
-impl<'a, T> SliceIndexIter<'a, T> {
-    fn new(slice: &'a [T]) -> Self {
-        Self {
-            slice,
-            index: 0,
+impl<T, C> ops::IndexMut<Idx<T>> for Column<T, C> {
+    #[inline]
+    fn index_mut(&mut self, index: Idx<T>) -> &mut Self::Output {
+        unsafe {
+            self.0.get_unchecked_mut(index.usize())
         }
     }
 }
 
-impl<'a, T> Iterator for SliceIndexIter<'a, T> {
-    type Item = Idx<T>;
+#[cfg(feature = "serde")]
+impl<T, C: serde::Serialize> serde::Serialize for Column<T, C> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.index < self.slice.len() {
-            let result = Some(Idx::new(self.index));
-            self.index += 1;
-            result
-        } else {
-            None
-        }
+#[cfg(feature = "serde")]
+impl<'de, T, C: serde::Deserialize<'de>> serde::Deserialize<'de> for Column<T, C> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(|values| Self(values, PhantomData))
     }
 }
+
+pub trait VecExt<T> {
+    fn push_get_index(&mut self, value: T) -> Idx<T>;
+
+    fn next_index(&self) -> Idx<T>;
+}
+
+impl<T> VecExt<T> for Vec<T> {
+    fn push_get_index(&mut self, value: T) -> Idx<T> {
+        let index = Idx::new(self.len());
+        self.push(value);
+        index
+    }
+
+    fn next_index(&self) -> Idx<T> {
+        Idx::new(self.len())
+    }
+}
+
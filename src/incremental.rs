@@ -0,0 +1,111 @@
+use std::mem;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{FanFormat, FanResult, Trapezoidation, TrapezoidationError, Vertex, trapezoidation::{TrapezoidationParts, TrapezoidationState}};
+
+/// Builds a [Trapezoidation] one ring at a time, for polygons which are not all known up front.
+///
+/// Each ring is inserted into the existing structure, in expected O(*m* log *n*) time for a ring of *m* vertices
+/// added to *n* existing vertices, rather than trapezoidizing every ring again.
+/// Segments within each ring are inserted in random order, so the query structure stays balanced in expectation.
+///
+/// The rings must satisfy the same preconditions as a [PolygonList](crate::PolygonList) once all of them are added,
+/// and also after each intermediate ring which is triangulated.
+///
+/// ```
+/// # use triangulate::{TrapezoidationBuilder, formats::IndexedListFormat, ListFormat};
+/// let mut builder = TrapezoidationBuilder::with_seed(0);
+/// builder.add_ring(vec![[0f32, 0.], [10., 0.], [10., 10.], [0., 10.]]).expect("Trapezoidation failed");
+/// let mut triangles = Vec::<[usize; 2]>::new();
+/// builder.triangulate(IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+/// assert_eq!(triangles.len(), 6);
+///
+/// // Add a hole, and triangulate again
+/// builder.add_ring(vec![[2f32, 2.], [2., 8.], [8., 8.], [8., 2.]]).expect("Trapezoidation failed");
+/// triangles.clear();
+/// builder.triangulate(IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+/// assert_eq!(triangles.len(), 24);
+/// ```
+pub struct TrapezoidationBuilder<V: Vertex> {
+    rings: Vec<Vec<V>>,
+    parts: TrapezoidationParts<V, [usize; 2]>,
+    seed: u64,
+    rng: ChaCha8Rng,
+}
+
+impl<V: Vertex> Default for TrapezoidationBuilder<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Vertex> TrapezoidationBuilder<V> {
+    /// Create an empty builder, with a random seed for ordering segment insertion
+    pub fn new() -> Self {
+        Self::with_seed(rand::thread_rng().gen())
+    }
+
+    /// Create an empty builder, ordering segment insertion by `seed`.
+    ///
+    /// Adding the same rings in the same order produces the same triangulation as
+    /// [PolygonList::trapezoidize_with_seed](crate::PolygonList::trapezoidize_with_seed) on a [Vec] of those rings,
+    /// although the query structure differs, as that inserts the segments of all the rings in a single random order.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            rings: Vec::new(),
+            parts: TrapezoidationParts::new(0),
+            seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// The seed used to order segment insertion
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The rings added so far, in the order they were added
+    pub fn rings(&self) -> &[Vec<V>] {
+        &self.rings
+    }
+
+    /// Consume the builder, returning the rings added so far
+    pub fn into_rings(self) -> Vec<Vec<V>> {
+        self.rings
+    }
+
+    /// Insert `ring` into the trapezoidation, returning its position in [TrapezoidationBuilder::rings].
+    ///
+    /// A ring with fewer than 3 vertices is rejected, leaving the builder unchanged.
+    /// Any other error means the rings violate the triangulation preconditions, and the builder should be discarded.
+    pub fn add_ring(&mut self, ring: Vec<V>) -> Result<usize, TrapezoidationError<[usize; 2]>> {
+        let index = self.rings.len();
+        if ring.len() < 3 {
+            return Err(TrapezoidationError::ShortRing { ring: index, vertices: (0..ring.len()).map(|i| [index, i]).collect() });
+        }
+
+        let len = ring.len();
+        self.rings.push(ring);
+        let parts = mem::replace(&mut self.parts, TrapezoidationParts::new(0));
+        let mut state = TrapezoidationState::from_parts(&self.rings, parts, Some(self.seed));
+        let result = state.add_ring((0..len).map(|i| [index, i]), index, &mut self.rng);
+        self.parts = state.into_parts();
+        result.map(|()| index)
+    }
+
+    /// The trapezoidation of the rings added so far.
+    ///
+    /// This copies the current structure in O(*n*) time, so later rings can still be added.
+    pub fn trapezoidation(&self) -> Trapezoidation<'_, Vec<Vec<V>>> {
+        TrapezoidationState::from_parts(&self.rings, self.parts.clone(), Some(self.seed)).finish()
+    }
+
+    /// Triangulate the rings added so far into the layout specified by `format`.
+    ///
+    /// Vertices are indexed by `[ring, vertex]`, as for a [Vec] of rings. See [PolygonList::triangulate](crate::PolygonList::triangulate).
+    pub fn triangulate<'b, FB: FanFormat<'b, Vec<Vec<V>>>>(&'b self, format: FB) -> FanResult<'b, Vec<Vec<V>>, FB> {
+        self.trapezoidation().triangulate(format)
+    }
+}
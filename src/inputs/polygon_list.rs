@@ -1,6 +1,9 @@
 use std::{convert::TryInto, marker::PhantomData, ops};
 
-use crate::{FanFormat, TriangulationError, VertexExt, VertexIndex, trapezoidation::{Trapezoidation, TrapezoidationState}, errors::TrapezoidationError, FanBuilder};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{FanFormat, FanResult, Strategy, SweepDirection, TriangulationError, VertexExt, VertexIndex, trapezoidation::{Trapezoidation, TrapezoidationState}, errors::{SdfError, TrapezoidationError}};
 
 use super::vertex::Vertex;
 
@@ -113,14 +116,49 @@ pub trait Polygon<'p>: 'p + Sized {
     /// Generate a [Trapezoidation], which can later be triangulated. 
     /// 
     /// Unless the [Trapezoidation] is needed for other reasons, this can be done in a single step with [Polygon::triangulate].
-    fn trapezoidize(&'p self) -> Result<Trapezoidation<'p, SinglePolygon<'p, Self>>, TrapezoidationError> {
+    fn trapezoidize(&'p self) -> Result<Trapezoidation<'p, SinglePolygon<'p, Self>>, TrapezoidationError<Self::Index>> {
         self.as_polygon_list().trapezoidize()
     }
 
+    /// Generate a [Trapezoidation], using `rng` to order segment insertion.
+    /// 
+    /// See [PolygonList::trapezoidize_with_rng].
+    fn trapezoidize_with_rng<R: Rng + ?Sized>(&'p self, rng: &mut R) -> Result<Trapezoidation<'p, SinglePolygon<'p, Self>>, TrapezoidationError<Self::Index>> {
+        self.as_polygon_list().trapezoidize_with_rng(rng)
+    }
+
+    /// Generate a [Trapezoidation], ordering segment insertion by `seed`.
+    /// 
+    /// See [PolygonList::trapezoidize_with_seed].
+    fn trapezoidize_with_seed(&'p self, seed: u64) -> Result<Trapezoidation<'p, SinglePolygon<'p, Self>>, TrapezoidationError<Self::Index>> {
+        self.as_polygon_list().trapezoidize_with_seed(seed)
+    }
+
     /// Triangulate the polygon into the layout specified by `format`
-    fn triangulate<FB: FanFormat<'p, SinglePolygon<'p, Self>>>(&'p self, format: FB) -> Result<<FB::Builder as FanBuilder<'p, SinglePolygon<'p, Self>>>::Output, TriangulationError<<FB::Builder as FanBuilder<'p, SinglePolygon<'p, Self>>>::Error>> {
+    fn triangulate<FB: FanFormat<'p, SinglePolygon<'p, Self>>>(&'p self, format: FB) -> FanResult<'p, SinglePolygon<'p, Self>, FB> {
         self.as_polygon_list().triangulate(format)
     }
+
+    /// Triangulate the polygon into the layout specified by `format`, ordering segment insertion by `seed`.
+    /// 
+    /// See [PolygonList::triangulate_with_seed].
+    fn triangulate_with_seed<FB: FanFormat<'p, SinglePolygon<'p, Self>>>(&'p self, format: FB, seed: u64) -> FanResult<'p, SinglePolygon<'p, Self>, FB> {
+        self.as_polygon_list().triangulate_with_seed(format, seed)
+    }
+
+    /// Triangulate the polygon into the layout specified by `format`, using the algorithm picked by `strategy`.
+    /// 
+    /// See [PolygonList::triangulate_with_strategy].
+    fn triangulate_with_strategy<FB: FanFormat<'p, SinglePolygon<'p, Self>>>(&'p self, format: FB, strategy: Strategy) -> FanResult<'p, SinglePolygon<'p, Self>, FB> {
+        self.as_polygon_list().triangulate_with_strategy(format, strategy)
+    }
+
+    /// Triangulate the polygon into the layout specified by `format`, sweeping it along `direction`.
+    /// 
+    /// See [PolygonList::triangulate_with_direction].
+    fn triangulate_with_direction<FB: FanFormat<'p, SinglePolygon<'p, Self>>>(&'p self, format: FB, direction: SweepDirection) -> FanResult<'p, SinglePolygon<'p, Self>, FB> {
+        self.as_polygon_list().triangulate_with_direction(format, direction)
+    }
 }
 
 /// An indexable list of polygons and their vertices
@@ -169,13 +207,96 @@ pub trait PolygonList<'p>: 'p {
     /// Generate a [Trapezoidation], which can later be triangulated. 
     /// 
     /// Unless the [Trapezoidation] is needed for other reasons, this can be done in a single step with [PolygonList::triangulate].
-    fn trapezoidize(&'p self) -> Result<Trapezoidation<'p, Self>, TrapezoidationError> {
-        TrapezoidationState::new(self).build()
+    /// 
+    /// The segment insertion order is seeded randomly. The seed is available from [Trapezoidation::seed],
+    /// and is included in any `InternalError`.
+    fn trapezoidize(&'p self) -> Result<Trapezoidation<'p, Self>, TrapezoidationError<Self::Index>> {
+        self.trapezoidize_with_seed(rand::thread_rng().gen())
+    }
+
+    /// Generate a [Trapezoidation], using `rng` to order segment insertion.
+    /// 
+    /// The same `rng` state and polygons always produce the same [Trapezoidation].
+    fn trapezoidize_with_rng<R: Rng + ?Sized>(&'p self, rng: &mut R) -> Result<Trapezoidation<'p, Self>, TrapezoidationError<Self::Index>> {
+        TrapezoidationState::new(self, None).build(rng)
+    }
+
+    /// Generate a [Trapezoidation], ordering segment insertion by `seed`.
+    /// 
+    /// The same `seed` and polygons always produce the same [Trapezoidation], on any platform.
+    fn trapezoidize_with_seed(&'p self, seed: u64) -> Result<Trapezoidation<'p, Self>, TrapezoidationError<Self::Index>> {
+        TrapezoidationState::new(self, Some(seed)).build(&mut ChaCha8Rng::seed_from_u64(seed))
     }
 
     /// Triangulate the polygons into the layout specified by `format`
-    fn triangulate<FB: FanFormat<'p, Self>>(&'p self, format: FB) -> Result<<FB::Builder as FanBuilder<'p, Self>>::Output, TriangulationError<<FB::Builder as FanBuilder<'p, Self>>::Error>> {
-        self.trapezoidize().map_err(TriangulationError::TrapezoidationError)?.triangulate(format)
+    /// 
+    /// A single ring which is convex or y-monotone is triangulated directly, without building a [Trapezoidation].
+    /// Convex rings are output as a single fan.
+    fn triangulate<FB: FanFormat<'p, Self>>(&'p self, format: FB) -> FanResult<'p, Self, FB> {
+        match crate::fast_path::try_triangulate(self, format) {
+            Ok(result) => result,
+            Err(format) => self.trapezoidize().map_err(TriangulationError::TrapezoidationError)?.triangulate(format),
+        }
+    }
+
+    /// Triangulate the polygons into the layout specified by `format`, ordering segment insertion by `seed`.
+    /// 
+    /// The same `seed` and polygons always produce the same triangulation. 
+    /// This can be used to replay a failure using the seed reported by an `InternalError`.
+    fn triangulate_with_seed<FB: FanFormat<'p, Self>>(&'p self, format: FB, seed: u64) -> FanResult<'p, Self, FB> {
+        match crate::fast_path::try_triangulate(self, format) {
+            Ok(result) => result,
+            Err(format) => self.trapezoidize_with_seed(seed).map_err(TriangulationError::TrapezoidationError)?.triangulate(format),
+        }
+    }
+
+    /// Triangulate the polygons into the layout specified by `format`, using the algorithm picked by `strategy`.
+    ///
+    /// [Strategy::Auto] ear clips small inputs, which is considerably faster than [PolygonList::triangulate] for them.
+    fn triangulate_with_strategy<FB: FanFormat<'p, Self>>(&'p self, format: FB, strategy: Strategy) -> FanResult<'p, Self, FB> {
+        crate::strategy::triangulate(self, format, strategy)
+    }
+
+    /// Triangulate the polygons into the layout specified by `format`, sweeping them along `direction` 
+    /// rather than along the y axis.
+    ///
+    /// The vertices are rotated into a copy so that `direction` points along y, which the trapezoidation, 
+    /// the monotone pieces and all orientation tests then use. The output refers to the original vertices.
+    /// Directions along an axis are exact, but other vectors round the rotated coordinates, so inputs which only
+    /// just meet the preconditions can fail. See [SweepDirection].
+    /// A [SweepDirection::Vector] which is zero or not finite is reported as [TriangulationError::InvalidDirection].
+    fn triangulate_with_direction<FB: FanFormat<'p, Self>>(&'p self, format: FB, direction: SweepDirection) -> FanResult<'p, Self, FB> {
+        crate::direction::triangulate(self, format, direction)
+    }
+
+    /// Triangulate the polygons into the layout specified by `format`, splitting the work between threads.
+    ///
+    /// Rings are partitioned into groups whose bounding boxes overlap, directly or through other rings in the group, 
+    /// so each hole stays with the ring around it. Each group is trapezoidized and triangulated as a separate [rayon] task,
+    /// and the fans are passed to `format` one group at a time, ordered by the first ring of each group.
+    /// This suits lists of many disjoint polygons, such as the countries and islands of a map.
+    #[cfg(feature = "rayon")]
+    fn par_triangulate<FB: FanFormat<'p, Self>>(&'p self, format: FB) -> FanResult<'p, Self, FB>
+    where Self: Sync, Self::Index: Send + Sync {
+        self.par_triangulate_with_seed(format, rand::thread_rng().gen())
+    }
+
+    /// Triangulate the polygons into the layout specified by `format`, splitting the work between threads 
+    /// and ordering segment insertion within each group by `seed`.
+    /// 
+    /// The same `seed` and polygons always produce the same triangulation, however many threads are used.
+    /// See [PolygonList::par_triangulate].
+    #[cfg(feature = "rayon")]
+    fn par_triangulate_with_seed<FB: FanFormat<'p, Self>>(&'p self, format: FB, seed: u64) -> FanResult<'p, Self, FB>
+    where Self: Sync, Self::Index: Send + Sync {
+        crate::parallel::par_triangulate(self, format, seed)
+    }
+
+    /// Compute a signed distance field of the polygons on a `width` × `height` grid, with one value per cell in row-major order.
+    ///
+    /// See [Trapezoidation::signed_distance_field].
+    fn signed_distance_field(&'p self, width: usize, height: usize, transform: [[<Self::Vertex as Vertex>::Coordinate; 3]; 2], max_distance: <Self::Vertex as Vertex>::Coordinate) -> Result<Vec<<Self::Vertex as Vertex>::Coordinate>, SdfError<Self::Index>> {
+        self.trapezoidize()?.signed_distance_field(width, height, transform, max_distance)
     }
 }
 
@@ -185,7 +306,7 @@ pub(crate) struct PolygonListExt<'p, P: PolygonList<'p> + ?Sized>(&'p P);
 
 impl<'p, P: PolygonList<'p> + ?Sized> Clone for PolygonListExt<'p, P> {
     fn clone(&self) -> Self {
-        Self(self.0)
+        *self
     }
 }
 
@@ -207,6 +328,36 @@ impl<'p, P: PolygonList<'p> + ?Sized> PolygonListExt<'p, P> {
     pub fn polygon_list(&self) -> &'p P {
         self.0
     }
+
+    /// The index of every vertex in order, and the range of each ring within them. Empty rings are skipped.
+    pub fn ring_ranges(&self) -> (Vec<P::Index>, Vec<ops::Range<usize>>) {
+        let mut indices = Vec::with_capacity(self.0.vertex_count());
+        let mut rings = Vec::new();
+        let mut start = 0;
+        for element in self.0.iter_indices() {
+            match element.into() {
+                PolygonElement::ContinuePolygon(index) => indices.push(index),
+                PolygonElement::NewPolygon => if indices.len() > start {
+                    rings.push(start..indices.len());
+                    start = indices.len();
+                },
+            }
+        }
+        if indices.len() > start {
+            rings.push(start..indices.len());
+        }
+        (indices, rings)
+    }
+
+    /// As [PolygonListExt::ring_ranges], but rejecting the first ring with fewer than 3 vertices
+    #[allow(clippy::type_complexity)]
+    pub fn checked_ring_ranges(&self) -> Result<(Vec<P::Index>, Vec<ops::Range<usize>>), TrapezoidationError<P::Index>> {
+        let (indices, rings) = self.ring_ranges();
+        if let Some((ring, vertices)) = rings.iter().enumerate().find(|(_, vertices)| vertices.len() < 3) {
+            return Err(TrapezoidationError::ShortRing { ring, vertices: indices[vertices.clone()].to_vec() });
+        }
+        Ok((indices, rings))
+    }
 }
 
 impl<'p, P: PolygonList<'p> + ?Sized> ops::Index<P::Index> for PolygonListExt<'p, P> {
@@ -374,6 +525,7 @@ where OldIndex::Output<New>: VertexIndex + crate::Mappable<New, Output<Old> = Ol
 }
 
 mod private {
+    #[allow(dead_code)]
     pub trait Sealed { }
 
     impl<'p, P: super::PolygonList<'p>> Sealed for P { }
@@ -19,9 +19,12 @@ pub trait Vertex {
 }
 
 #[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct Coords<C: Real>([C; 2]);
 
 impl<C: Real> Coords<C> {
+    pub fn new(x: C, y: C) -> Self { Self([x, y]) }
+
     pub fn x(&self) -> C { self.0[0] }
     pub fn y(&self) -> C { self.0[1] }
 
@@ -0,0 +1,173 @@
+use std::{mem, sync::{Arc, atomic::{AtomicBool, Ordering}}};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{FanBuilder, FanError, FanFormat, PolygonList, TrapezoidationError, TriangulationError, fan_builder_state::FanBuilderState, trapezoidation::{Buffers, SegmentCursor, Trapezoidation, TrapezoidationState, TrapezoidationStructure, Walk}};
+
+/// How many segments are inserted between checks of the cancellation flag
+const CANCEL_CHECK_INTERVAL: usize = 64;
+
+/// The result of a call to [TriangulationJob::step]
+#[derive(Debug)]
+pub enum JobStatus<Output> {
+    /// The budget was spent before the triangulation completed
+    Pending,
+    /// The triangulation completed, with the output of the format
+    Complete(Output),
+}
+
+/// A triangulation which runs a little at a time, for spreading a large input over several frames or checking for cancellation.
+///
+/// Each call to [TriangulationJob::step] does a bounded amount of work, counted in segments inserted into the trapezoidation,
+/// then in trapezoids traversed while triangulating the monotone pieces. The output is identical to triangulating the [Trapezoidation] from
+/// [PolygonList::trapezoidize_with_seed] with the same seed.
+///
+/// ```
+/// # use triangulate::{TriangulationJob, JobStatus, formats::IndexedListFormat, ListFormat};
+/// let square = vec![vec![[0f32, 0.], [10., 0.], [10., 10.], [0., 10.]], vec![[2f32, 2.], [2., 8.], [8., 8.], [8., 2.]]];
+/// let mut job = TriangulationJob::new(&square, IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format());
+/// let triangles = loop {
+///     match job.step(2).expect("Triangulation failed") {
+///         JobStatus::Pending => println!("{:.0}%", job.progress() * 100.),
+///         JobStatus::Complete(triangles) => break triangles,
+///     }
+/// };
+/// assert_eq!(triangles.len(), 24);
+/// ```
+pub struct TriangulationJob<'p, P: PolygonList<'p> + ?Sized, FB: FanFormat<'p, P>> {
+    polygon_list: &'p P,
+    phase: Phase<'p, P>,
+    fbs: FanBuilderState<'p, P, FB>,
+    buffers: Buffers<P::Vertex, P::Index>,
+    seed: u64,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+enum Phase<'p, P: PolygonList<'p> + ?Sized> {
+    // The cursor is created on the first step, so errors in the input are reported from there
+    Trapezoidizing { state: TrapezoidationState<'p, P>, cursor: Option<SegmentCursor> },
+    Triangulating { trapezoidation: Trapezoidation<'p, P>, walk: Walk<P::Vertex, P::Index>, segments: usize },
+    Finished,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, FB: FanFormat<'p, P>> TriangulationJob<'p, P, FB> {
+    /// Prepare to triangulate `polygon_list` into the layout specified by `format`, with a random seed for ordering segment insertion
+    pub fn new(polygon_list: &'p P, format: FB) -> Self {
+        Self::with_seed(polygon_list, format, rand::thread_rng().gen())
+    }
+
+    /// Prepare to triangulate `polygon_list` into the layout specified by `format`, ordering segment insertion by `seed`
+    pub fn with_seed(polygon_list: &'p P, format: FB, seed: u64) -> Self {
+        Self {
+            polygon_list,
+            phase: Phase::Trapezoidizing {
+                state: TrapezoidationState::new(polygon_list, Some(seed)),
+                cursor: None,
+            },
+            fbs: FanBuilderState::Uninitialized(format),
+            buffers: Buffers::new(),
+            seed,
+            cancel: None,
+        }
+    }
+
+    /// Stop the job with [TriangulationError::Cancelled] once `flag` is set, which may be done from another thread.
+    ///
+    /// The flag is checked at the start of every step, and periodically within steps with large budgets.
+    pub fn with_cancel_flag(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(flag);
+        self
+    }
+
+    /// The seed used to order segment insertion
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The fraction of the work done so far, from 0 to 1.
+    ///
+    /// Work is counted in segments inserted and trapezoids traversed, which both take roughly constant time on average.
+    pub fn progress(&self) -> f32 {
+        let (done, total) = match &self.phase {
+            Phase::Trapezoidizing { cursor: None, .. } => return 0.,
+            // Every vertex and every segment adds one trapezoid to the one covering the plane
+            Phase::Trapezoidizing { cursor: Some(cursor), .. } => (cursor.inserted(), cursor.len() * 3 + 1),
+            Phase::Triangulating { trapezoidation, walk, segments } => (segments + walk.visited(), segments + trapezoidation.ts().len()),
+            Phase::Finished => return 1.,
+        };
+        (done as f64 / total as f64).min(1.) as f32
+    }
+
+    /// Whether the job has completed, failed or been cancelled
+    pub fn is_finished(&self) -> bool {
+        matches!(self.phase, Phase::Finished)
+    }
+
+    /// Continue the triangulation, inserting up to `budget` segments or traversing up to `budget` trapezoids.
+    ///
+    /// Once this returns [JobStatus::Complete] or an error, the job is finished.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the job is already finished.
+    #[allow(clippy::type_complexity)]
+    pub fn step(&mut self, budget: usize) -> Result<JobStatus<<FB::Builder as FanBuilder<'p, P>>::Output>, FanError<'p, P, FB>> {
+        assert!(!self.is_finished(), "TriangulationJob stepped after it finished");
+
+        let result = self.advance(budget).map_err(|e| e.diagnose(self.polygon_list, Some(self.seed)));
+        match result {
+            Ok(false) => Ok(JobStatus::Pending),
+            result => {
+                self.phase = Phase::Finished;
+                let fbs = mem::replace(&mut self.fbs, FanBuilderState::Error(None));
+                fbs.complete(result.map(|_| ())).map(JobStatus::Complete)
+            }
+        }
+    }
+
+    /// Spend up to `budget`, returning whether the triangulation finished
+    fn advance(&mut self, mut budget: usize) -> Result<bool, FanError<'p, P, FB>> {
+        loop {
+            if self.cancel.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed)) {
+                return Err(TriangulationError::Cancelled);
+            }
+            let chunk = budget.min(CANCEL_CHECK_INTERVAL);
+            budget -= chunk;
+
+            match &mut self.phase {
+                Phase::Trapezoidizing { state, cursor } => {
+                    let cursor = match cursor {
+                        Some(cursor) => cursor,
+                        None => {
+                            state.load_polygon_list(&mut self.buffers).map_err(TriangulationError::TrapezoidationError)?;
+                            cursor.insert(SegmentCursor::new(&mut self.buffers, &mut ChaCha8Rng::seed_from_u64(self.seed)))
+                        }
+                    };
+                    let inserted = state.insert_segments(&mut self.buffers, 0, cursor, chunk)
+                        .map_err(|e| TriangulationError::TrapezoidationError(TrapezoidationError::InternalError(e)))?;
+                    // Carry any of the budget left after the last segment over to the triangulation
+                    budget += chunk - inserted;
+
+                    if cursor.is_finished() {
+                        let segments = cursor.len();
+                        let Phase::Trapezoidizing { state, .. } = mem::replace(&mut self.phase, Phase::Finished) else { unreachable!() };
+                        let trapezoidation = state.finish();
+                        let walk = trapezoidation.begin_walk(&mut self.buffers).map_err(TriangulationError::InternalError)?;
+                        self.phase = Phase::Triangulating { trapezoidation, walk, segments };
+                        continue;
+                    }
+                },
+                Phase::Triangulating { trapezoidation, walk, .. } => {
+                    // The walk checks the cancellation flag before each trapezoid, so it can spend the rest of the budget at once
+                    return trapezoidation.walk(walk, &mut self.fbs, &mut self.buffers, chunk + budget, self.cancel.as_deref());
+                },
+                Phase::Finished => unreachable!(),
+            }
+
+            if budget == 0 {
+                return Ok(false);
+            }
+        }
+    }
+}
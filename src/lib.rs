@@ -19,7 +19,7 @@
 //!     vec![[0.05, 0.05], [0.05, 0.95], [0.95, 0.95], [0.95, 0.05]]
 //! ];
 //! let mut triangulated_indices = Vec::<[usize; 2]>::new();
-//! polygons.triangulate(formats::IndexedListFormat::new(&mut triangulated_indices).into_fan_builder()).expect("Triangulation failed");
+//! polygons.triangulate(formats::IndexedListFormat::new(&mut triangulated_indices).into_fan_format()).expect("Triangulation failed");
 //! println!("First triangle: {:?}, {:?}, {:?}", 
 //!     polygons.get_vertex(triangulated_indices[0]), 
 //!     polygons.get_vertex(triangulated_indices[1]), 
@@ -47,16 +47,21 @@
 //! * [FanBuilder]
 //! * [ListFormat]
 //! * [ListBuilder]
+//! * [TrapezoidFormat]
+//! * [TrapezoidBuilder]
 //! 
 //! ## Preconditions  
 //! * No edge can cross any other edge, whether it is on the same polygon or not.
 //! * Each vertex must be part of exactly two edges. Polygons cannot 'share' vertices with each other.
 //! * Each vertex must be distinct - no vertex can have x and y coordinates that both compare equal to another vertex's.
 //! 
-//! These preconditions are not explicitly checked, but an invalid polygon set will likely yield `TriangulationError::InternalError`.
+//! These preconditions are not checked up front. If an invalid polygon set makes triangulation fail, the violation is found and reported as
+//! [TrapezoidationError::EdgeCrossing] or [TrapezoidationError::DuplicateVertex], with the indices of the vertices involved.
 //! 
 //! ## Results
 //! Because the algorithm involves random ordering, the exact triangulation is not guaranteed to be same between invocations.
+//! To reproduce a triangulation exactly, use [PolygonList::triangulate_with_seed] or [PolygonList::trapezoidize_with_rng].
+//! If an `InternalError` occurs, it includes the seed that was used, so the failure can be replayed.
 //! 
 //! ## Algorithm
 //! This library is based on [Raimund Seidel's randomized algorithm for triangulating polygons](https://www.cs.princeton.edu/courses/archive/fall05/cos528/handouts/A%20Simple%20and%20fast.pdf). 
@@ -72,10 +77,31 @@ mod monotone;
 mod mappable;
 mod math;
 mod fan_builder_state;
+mod fan_recorder;
 mod inputs;
 mod outputs;
 #[macro_use]
 mod errors;
+mod diagnose;
+mod location;
+mod view;
+mod raster;
+mod incremental;
+mod dynamic;
+mod triangulator;
+mod batch;
+mod earclip;
+mod fast_path;
+mod strategy;
+mod sweep;
+mod direction;
+mod job;
+mod owned;
+#[cfg(feature = "rayon")]
+mod parallel;
+
+pub mod sprite;
+pub mod hatch;
 
 #[cfg(feature = "_debugging")]
 pub mod debug;
@@ -85,7 +111,17 @@ pub mod debug;
 pub mod tests;
 
 pub use trapezoidation::Trapezoidation;
-pub use errors::{TrapezoidationError, TriangulationError};
+pub use incremental::TrapezoidationBuilder;
+pub use triangulator::Triangulator;
+pub use batch::{Batch, BatchItem, BatchIter, BatchResult};
+pub use strategy::Strategy;
+pub use direction::SweepDirection;
+pub use job::{JobStatus, TriangulationJob};
+pub use owned::{OwnedPolygonList, OwnedPolygonListIter, OwnedTrapezoidation};
+pub use dynamic::{DynamicTriangulation, TriangleId, TriangulationChange};
+pub use errors::{SdfError, TrapezoidationError, TriangulationError};
+pub use location::{EdgeHit, HorizontalDirection, Location};
+pub use view::{TrapezoidId, TrapezoidView};
 
 pub(crate) use fan_builder_state::FanBuilderState;
 
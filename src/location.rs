@@ -0,0 +1,126 @@
+use crate::{Real, Vertex, VertexIndex, errors::InternalError, idx::Idx, nexus::{FinalNexusType, Nexus, Nexuses}, trapezoid::{Trapezoid, Trapezoids}};
+
+/// The location of a point relative to a [PolygonList](crate::PolygonList), found with [Trapezoidation::locate](crate::Trapezoidation::locate).
+///
+/// Rings are identified by their position among the non-empty polygons of the [PolygonList](crate::PolygonList).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Location<Index: VertexIndex> {
+    /// The point is inside the polygons
+    Inside {
+        /// The ring forming the outer boundary of the region containing the point
+        ring: usize,
+    },
+    /// The point is outside all polygons, or inside a hole
+    Outside,
+    /// The point lies exactly on an edge
+    OnEdge {
+        /// The ring the edge belongs to
+        ring: usize,
+        /// The vertices of the edge, in the order they appear in the ring
+        edge: (Index, Index),
+    },
+    /// The point lies exactly on a vertex
+    OnVertex {
+        /// The ring the vertex belongs to
+        ring: usize,
+        /// The vertex
+        vertex: Index,
+    },
+}
+
+/// The direction of a ray shot with [Trapezoidation::shoot_horizontal](crate::Trapezoidation::shoot_horizontal)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HorizontalDirection {
+    /// Towards negative x
+    Left,
+    /// Towards positive x
+    Right,
+}
+
+/// An edge found by [Trapezoidation::shoot_horizontal](crate::Trapezoidation::shoot_horizontal) or [Trapezoidation::nearest_edge](crate::Trapezoidation::nearest_edge)
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeHit<Index: VertexIndex, C: Real> {
+    /// The ring the edge belongs to
+    pub ring: usize,
+    /// The vertices of the edge, in the order they appear in the ring
+    pub edge: (Index, Index),
+    /// The point on the edge which was hit
+    pub point: [C; 2],
+    /// The distance from the query point to [EdgeHit::point]
+    pub distance: C,
+}
+
+/// A connected region of trapezoids which are not separated by any segment
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Face {
+    pub inside: bool,
+    /// The ring forming the outer boundary of the face, or [None] for the unbounded face
+    pub ring: Option<usize>,
+}
+
+/// Assign each trapezoid to its [Face].
+///
+/// The unbounded face is flood-filled from the top trapezoid. Crossing a segment (i.e. moving to the other side of a nexus' segments)
+/// starts a new face with the opposite inside state. Every bounded face's highest point is an 'A' nexus on its outer ring.
+pub(crate) fn trapezoid_faces<V: Vertex, Index: VertexIndex>(ns: &Nexuses<V, Index>, ts: &Trapezoids<V, Index>, ti_top: Idx<Trapezoid<V, Index>>) -> Result<Box<[Face]>, InternalError> {
+    let mut face_ids = vec![usize::MAX; ts.len()];
+    let mut faces = Vec::<Face>::new();
+
+    let mut seeds = vec![(ti_top, false)];
+    let mut stack = Vec::new();
+    while let Some((ti_seed, inside)) = seeds.pop() {
+        if face_ids[ti_seed.usize()] != usize::MAX {
+            continue;
+        }
+
+        let face_id = faces.len();
+        faces.push(Face { inside, ring: None });
+        face_ids[ti_seed.usize()] = face_id;
+        stack.push(ti_seed);
+
+        while let Some(ti) = stack.pop() {
+            let t = ts.get(ti);
+            for ni in t.up().into_iter().chain(t.down()) {
+                let [face0, face1] = ns.get(ni).final_type()?.faces();
+                let (same, other) = if face0.contains(&ti) {
+                    (face0, face1)
+                } else if face1.contains(&ti) {
+                    (face1, face0)
+                } else {
+                    return Err(InternalError::new(format!("{} is not adjacent to {}", ti, ni)));
+                };
+
+                for ti_same in same {
+                    if face_ids[ti_same.usize()] == usize::MAX {
+                        face_ids[ti_same.usize()] = face_id;
+                        stack.push(ti_same);
+                    }
+                }
+                for ti_other in other {
+                    if face_ids[ti_other.usize()] == usize::MAX {
+                        seeds.push((ti_other, !inside));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut tops = vec![Option::<Idx<Nexus<V, Index>>>::None; faces.len()];
+    for (i, n) in ns.iter().enumerate() {
+        if let FinalNexusType::A { ti_downcenter, .. } = n.final_type()? {
+            let top = &mut tops[face_ids[ti_downcenter.usize()]];
+            if top.is_none_or(|ni_top| ns.coords(ni_top) < n.coords()) {
+                *top = Some(Idx::new(i));
+            }
+        }
+    }
+    // The unbounded face is the first face, it has no boundary
+    for (face, top) in faces.iter_mut().zip(tops).skip(1) {
+        let ni_top = top.ok_or_else(|| InternalError::new("Bounded face has no top nexus"))?;
+        face.ring = Some(ns.get(ni_top).ring());
+    }
+
+    face_ids.into_iter()
+        .map(|face_id| faces.get(face_id).copied().ok_or_else(|| InternalError::new("Trapezoid is not connected to the trapezoidation")))
+        .collect()
+}
@@ -19,3 +19,35 @@ pub(crate) fn math_n(n: usize, h: usize) -> usize {
     }
     ((n as f64) / nf).ceil() as usize
 }
+
+
+/// Interleaves the bits of `x` and `y`, so that sorting by the result visits nearby points consecutively
+pub(crate) fn morton_code(x: u16, y: u16) -> u32 {
+    fn spread(v: u16) -> u32 {
+        let mut v = v as u32;
+        v = (v | (v << 8)) & 0x00FF00FF;
+        v = (v | (v << 4)) & 0x0F0F0F0F;
+        v = (v | (v << 2)) & 0x33333333;
+        v = (v | (v << 1)) & 0x55555555;
+        v
+    }
+    spread(x) | (spread(y) << 1)
+}
+
+/// Maps coordinates within the bounds of `coords` to a [morton_code] on a 65536 × 65536 grid
+pub(crate) fn morton_mapper<C: Real>(coords: &[Coords<C>]) -> impl Fn(Coords<C>) -> u32 {
+    let mut min = [f64::MAX; 2];
+    let mut max = [f64::MIN; 2];
+    for c in coords {
+        if let (Some(x), Some(y)) = (c.x().to_f64(), c.y().to_f64()) {
+            min = [min[0].min(x), min[1].min(y)];
+            max = [max[0].max(x), max[1].max(y)];
+        }
+    }
+    let scale = [u16::MAX as f64 / (max[0] - min[0]).max(f64::MIN_POSITIVE), u16::MAX as f64 / (max[1] - min[1]).max(f64::MIN_POSITIVE)];
+
+    move |c| {
+        let quantize = |v: Option<f64>, i: usize| v.map_or(0, |v| ((v - min[i]) * scale[i]) as u16);
+        morton_code(quantize(c.x().to_f64(), 0), quantize(c.y().to_f64(), 1))
+    }
+}
@@ -3,7 +3,7 @@ use std::marker::PhantomData;
 use num_traits::real::Real;
 use smallvec::{SmallVec, smallvec};
 
-use crate::{FanFormat, FanBuilderState, PolygonList, PolygonListExt, TriangleWinding, VertexIndex, errors::{TriangulationError, InternalError}, math::is_left_of_line, FanBuilder, Coords};
+use crate::{FanError, FanFormat, FanBuilderState, PolygonList, PolygonListExt, TriangleWinding, VertexIndex, errors::{TriangulationError, InternalError}, math::is_left_of_line, FanBuilder, Coords};
 
 pub(crate) struct MonotoneBuilder<Index: VertexIndex, C: Real> {
     vec: SmallVec<[(Index, Coords<C>); 16]>,
@@ -84,7 +84,7 @@ impl<Index: VertexIndex, C: Real> Monotone<Index, C> {
         }
     }
 
-    pub(crate) fn build_fans<'z, 'p, P: PolygonList<'p, Index=Index> + ?Sized, FB: FanFormat<'p, P>>(mut self, ps: PolygonListExt<'p, P>, fbs: &'z mut FanBuilderState<'p, P, FB>) -> Result<(), TriangulationError<<FB::Builder as FanBuilder<'p, P>>::Error>> {
+    pub(crate) fn build_fans<'z, 'p, P: PolygonList<'p, Index=Index> + ?Sized, FB: FanFormat<'p, P>>(mut self, ps: PolygonListExt<'p, P>, fbs: &'z mut FanBuilderState<'p, P, FB>) -> Result<(), FanError<'p, P, FB>> {
         enum BuilderOrDeferredTris<'z, 'p, P: PolygonList<'p> + ?Sized, FB: FanFormat<'p, P>> {
             Builder(&'z mut FB::Builder),
             DeferredTris(&'z mut FanBuilderState<'p, P, FB>, usize, PhantomData<&'p ()>),
@@ -99,8 +99,13 @@ impl<Index: VertexIndex, C: Real> Monotone<Index, C> {
             }
         }
 
+        // Whether any triangles were added since the last reset. Otherwise, resetting again would repeat the same steps forever
+        let mut progressed = false;
+
         while self.remaining_vertices() >= 3 {
             if self.can_triangulate() {
+                progressed = true;
+
                 // The base triangle, with all 3 points specified
                 let vi1 = self.skipped_pop().0;
                 let mut vi0 = self.skipped_peek().0;
@@ -111,7 +116,8 @@ impl<Index: VertexIndex, C: Real> Monotone<Index, C> {
                 // and can make the calls in a reversed order
                 let is_backtracking = self.can_triangulate();
                 let mut bodt: BuilderOrDeferredTris<'_, '_, P, FB> = if is_backtracking ^ self.is_left_chain ^ (FB::Builder::WINDING == TriangleWinding::Clockwise) {
-                    if !self.is_left_chain {
+                    // A backtracking fan is rooted at the pending vertex, an advancing fan at the top of the stack
+                    if is_backtracking {
                         std::mem::swap(&mut vi0, &mut vi2);
                     }
                     BuilderOrDeferredTris::Builder(fbs.new_fan(ps.polygon_list(), vi0, vi1, vi2)?)
@@ -163,6 +169,10 @@ impl<Index: VertexIndex, C: Real> Monotone<Index, C> {
             } else {
                 // In rare cases, pushing vertices and popping whenever backtracking is possible will not be able to triangulate everything.
                 // If we have pushed all vertices and we can't backtrack, reset our position to the initial setup (minus all removed vertices)
+                if !progressed {
+                    return Err(TriangulationError::internal("Monotone polygon cannot be triangulated"));
+                }
+                progressed = false;
                 self.reset_position();
             }
         }
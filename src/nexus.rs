@@ -1,21 +1,32 @@
 use std::{marker::PhantomData, mem, fmt};
 
+use smallvec::{SmallVec, smallvec};
 use zot::{Ot, Zot};
 
-use crate::{Vertex, VertexIndex, errors::InternalError, idx::{Idx, IdxDisplay}, segment::Segment, trapezoid::Trapezoid, Coords, math::is_left_of_line};
+use crate::{Vertex, VertexIndex, errors::InternalError, idx::{Column, Idx, IdxDisplay}, segment::{Segment, Segments}, trapezoid::Trapezoid, Coords, math::is_left_of_line};
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DividerDirection {
     Ascending,
     Descending,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound = ""))]
 struct Divider<V: Vertex, Index: VertexIndex> {
     si: Idx<Segment<V, Index>>,
     ti_right: Idx<Trapezoid<V, Index>>,
     direction: DividerDirection,
 }
 
+impl<V: Vertex, Index: VertexIndex> Clone for Divider<V, Index> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> Copy for Divider<V, Index> { }
+
 impl<V: Vertex, Index: VertexIndex> fmt::Debug for Divider<V, Index> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Divider").field("si", &self.si).field("ti_right", &self.ti_right).field("direction", &self.direction).finish()
@@ -41,11 +52,63 @@ impl<V: Vertex, Index: VertexIndex> std::fmt::Display for Divider<V, Index> {
 pub(crate) enum FinalNexusType<V: Vertex, Index: VertexIndex> {
     V { ti_upleft: Idx<Trapezoid<V, Index>>, ti_upcenter: Idx<Trapezoid<V, Index>>, ti_upright: Idx<Trapezoid<V, Index>>, ti_down: Idx<Trapezoid<V, Index>> },
     I { ti_upleft: Idx<Trapezoid<V, Index>>, ti_upright: Idx<Trapezoid<V, Index>>, ti_downleft: Idx<Trapezoid<V, Index>>, ti_downright: Idx<Trapezoid<V, Index>> },
-    A { _ti_up: Idx<Trapezoid<V, Index>>, ti_downleft: Idx<Trapezoid<V, Index>>, ti_downcenter: Idx<Trapezoid<V, Index>>, ti_downright: Idx<Trapezoid<V, Index>> },
+    A { ti_up: Idx<Trapezoid<V, Index>>, ti_downleft: Idx<Trapezoid<V, Index>>, ti_downcenter: Idx<Trapezoid<V, Index>>, ti_downright: Idx<Trapezoid<V, Index>> },
+}
+
+impl<V: Vertex, Index: VertexIndex> FinalNexusType<V, Index> {
+    /// The trapezoids adjacent to the nexus, grouped by the side of the nexus' segments they are on.
+    /// Trapezoids in the same group are connected without crossing a segment, so they are either all inside or all outside the polygon.
+    #[allow(clippy::type_complexity)]
+    pub fn faces(&self) -> [SmallVec<[Idx<Trapezoid<V, Index>>; 3]>; 2] {
+        match *self {
+            FinalNexusType::V { ti_upleft, ti_upcenter, ti_upright, ti_down } => [smallvec![ti_upleft, ti_upright, ti_down], smallvec![ti_upcenter]],
+            FinalNexusType::I { ti_upleft, ti_upright, ti_downleft, ti_downright } => [smallvec![ti_upleft, ti_downleft], smallvec![ti_upright, ti_downright]],
+            FinalNexusType::A { ti_up, ti_downleft, ti_downcenter, ti_downright } => [smallvec![ti_up, ti_downleft, ti_downright], smallvec![ti_downcenter]],
+        }
+    }
+
+    /// The trapezoids sharing part of `ti`'s horizontal edge through the nexus, on the opposite side of it.
+    /// The center trapezoid of a 'V' or 'A' only touches the nexus at a point, so it has no neighbours here.
+    pub fn neighbours(&self, ti: Idx<Trapezoid<V, Index>>) -> SmallVec<[Idx<Trapezoid<V, Index>>; 2]> {
+        match *self {
+            FinalNexusType::V { ti_upleft, ti_upright, ti_down, .. } => {
+                if ti == ti_down {
+                    smallvec![ti_upleft, ti_upright]
+                } else if ti == ti_upleft || ti == ti_upright {
+                    smallvec![ti_down]
+                } else {
+                    smallvec![]
+                }
+            }
+            FinalNexusType::I { ti_upleft, ti_upright, ti_downleft, ti_downright } => {
+                if ti == ti_upleft {
+                    smallvec![ti_downleft]
+                } else if ti == ti_downleft {
+                    smallvec![ti_upleft]
+                } else if ti == ti_upright {
+                    smallvec![ti_downright]
+                } else if ti == ti_downright {
+                    smallvec![ti_upright]
+                } else {
+                    smallvec![]
+                }
+            }
+            FinalNexusType::A { ti_up, ti_downleft, ti_downright, .. } => {
+                if ti == ti_up {
+                    smallvec![ti_downleft, ti_downright]
+                } else if ti == ti_downleft || ti == ti_downright {
+                    smallvec![ti_up]
+                } else {
+                    smallvec![]
+                }
+            }
+        }
+    }
 }
 
 pub(crate) struct Nexus<V: Vertex, Index: VertexIndex> {
     vi: Index,
+    ring: usize,
     c: Coords<V::Coordinate>,
     ti_upleft: Idx<Trapezoid<V, Index>>,
     ti_downleft: Idx<Trapezoid<V, Index>>,
@@ -53,6 +116,33 @@ pub(crate) struct Nexus<V: Vertex, Index: VertexIndex> {
     _v: PhantomData<V>,
 }
 
+/// (De)serialize a column of [Zot]s as pairs of [Option]s, as zot's own serde support does not build
+#[cfg(feature = "serde")]
+mod serde_zot {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use zot::Zot;
+
+    use crate::idx::Column;
+
+    pub fn serialize<T, C: Serialize, S: Serializer>(column: &Column<T, Zot<C>>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(column.iter().map(|zot| (zot.first(), zot.second())))
+    }
+
+    pub fn deserialize<'de, T, C: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Column<T, Zot<C>>, D::Error> {
+        <Vec<(Option<C>, Option<C>)>>::deserialize(deserializer).map(|pairs| pairs.into_iter().map(Zot::from).collect())
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> Clone for Nexus<V, Index> {
+    fn clone(&self) -> Self {
+        Self {
+            vi: self.vi.clone(),
+            dividers: self.dividers,
+            ..*self
+        }
+    }
+}
+
 impl<V: Vertex, Index:VertexIndex> std::fmt::Debug for Nexus<V, Index> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut s = f.debug_struct("Nexus");
@@ -60,7 +150,7 @@ impl<V: Vertex, Index:VertexIndex> std::fmt::Debug for Nexus<V, Index> {
         s.field("vi", &self.vi);
         #[cfg(not(feature = "_debugging"))]
         s.field("vi", &"?");
-        s.field("c", &self.c).field("ti_upleft", &self.ti_upleft).field("ti_downleft", &self.ti_downleft).field("dividers", &self.dividers).field("_v", &self._v).finish()
+        s.field("ring", &self.ring).field("c", &self.c).field("ti_upleft", &self.ti_upleft).field("ti_downleft", &self.ti_downleft).field("dividers", &self.dividers).field("_v", &self._v).finish()
     }
 }
 
@@ -87,9 +177,10 @@ impl<V: Vertex, Index: VertexIndex> IdxDisplay for Nexus<V, Index> {
 }
 
 impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
-    pub fn new(vi: Index, c: Coords<V::Coordinate>, ti_up: Idx<Trapezoid<V, Index>>, ti_down: Idx<Trapezoid<V, Index>>) -> Self {
+    pub fn new(vi: Index, ring: usize, c: Coords<V::Coordinate>, ti_up: Idx<Trapezoid<V, Index>>, ti_down: Idx<Trapezoid<V, Index>>) -> Self {
         Self {
             vi,
+            ring,
             c,
             ti_upleft: ti_up,
             ti_downleft: ti_down,
@@ -103,7 +194,7 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
              Ok(
                  if div0.direction == DividerDirection::Descending {
                     FinalNexusType::A {
-                        _ti_up: self.ti_upleft,
+                        ti_up: self.ti_upleft,
                         ti_downleft: self.ti_downleft,
                         ti_downcenter: div0.ti_right,
                         ti_downright: div1.ti_right,
@@ -131,8 +222,16 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
 
     pub fn vertex(&self) -> Index { self.vi.clone() }
 
+    /// The position of the vertex's polygon among the non-empty polygons of the [PolygonList](crate::PolygonList)
+    pub fn ring(&self) -> usize { self.ring }
+
     pub fn coords(&self) -> Coords<V::Coordinate> { self.c }
 
+    /// The segments joined at this nexus
+    pub fn segments(&self) -> impl Iterator<Item=Idx<Segment<V, Index>>> + '_ {
+        self.dividers.iter().map(|d| d.si)
+    }
+
     pub fn replace_trapezoid(&mut self, ti_old: Idx<Trapezoid<V, Index>>, ti_new: Idx<Trapezoid<V, Index>>) -> Result<(), InternalError> {
         *self.find_trapezoid(ti_old).ok_or_else(|| InternalError::new(format!("Trapezoid {} is not connected to replace with {}", ti_old, ti_new)))? = ti_new;
         Ok(())
@@ -153,22 +252,20 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
         }
     }
 
-    pub fn add_segment(ns: &mut [Nexus<V, Index>], ss: &[Segment<V, Index>], ni: Idx<Nexus<V, Index>>, si: Idx<Segment<V, Index>>, ti_right: Idx<Trapezoid<V, Index>>) -> Result<(), InternalError> {
-        let dir = Self::get_segment_direction(ss, ni, si)?;
+    pub fn add_segment(ns: &mut Nexuses<V, Index>, ss: &Segments<V, Index>, ni: Idx<Nexus<V, Index>>, si: Idx<Segment<V, Index>>, ti_right: Idx<Trapezoid<V, Index>>) -> Result<(), InternalError> {
+        let dir = Self::get_segment_direction(ns, ss, ni, si)?;
         let div = Divider::new(si, ti_right, dir);
-        let mut divs = Zot::Zero;
-        std::mem::swap(&mut divs, &mut ns[ni].dividers);
-        ns[ni].dividers = match divs {
+        let dividers = match ns.get(ni).dividers {
             Zot::Zero => Zot::One(div),
             Zot::One(div0) => {
                 let swap = if div0.direction == div.direction {
-                    let s0 = &ss[div0.si];
-                    let s1 = &ss[div.si];
+                    let s0 = ss.get(ns, div0.si);
+                    let s1 = ss.get(ns, div.si);
                     let ni1 = match div.direction {
                         DividerDirection::Ascending => s1.ni_max(),
                         DividerDirection::Descending => s1.ni_min(),
                     };
-                    s0.is_on_left(ns[ni1].coords())
+                    s0.is_on_left(ns.coords(ni1))
                 } else {
                     div.direction == DividerDirection::Ascending
                 };
@@ -180,11 +277,12 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
             },
             Zot::Two(div0, div1) => return Err(InternalError::new(format!("Nexus already has two segments ({}, {})", div0, div1))),
         };
+        ns.modify(ni, |n| n.dividers = dividers);
         Ok(())
     }
 
-    fn get_segment_direction(ss: &[Segment<V, Index>], ni: Idx<Nexus<V, Index>>, si: Idx<Segment<V, Index>>) -> Result<DividerDirection, InternalError> {
-        let s = &ss[si];
+    fn get_segment_direction(ns: &Nexuses<V, Index>, ss: &Segments<V, Index>, ni: Idx<Nexus<V, Index>>, si: Idx<Segment<V, Index>>) -> Result<DividerDirection, InternalError> {
+        let s = ss.get(ns, si);
         if ni == s.ni_max() {
             Ok(DividerDirection::Descending)
         } else if ni == s.ni_min() {
@@ -212,6 +310,12 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
         (self.ti_downleft, self.filter_trapezoids(DividerDirection::Descending).last().copied()).into()
     }
 
+    /// Every trapezoid above and below the nexus
+    #[cfg(feature = "serde")]
+    pub fn trapezoids(&self) -> impl Iterator<Item=Idx<Trapezoid<V, Index>>> + '_ {
+        [self.ti_upleft, self.ti_downleft].into_iter().chain(self.dividers.iter().map(|d| d.ti_right))
+    }
+
     pub fn iter_up_trapezoids(&self) -> impl Iterator<Item=Idx<Trapezoid<V, Index>>> + '_ {
         NexusTrapezoidIter::new(self, DividerDirection::Ascending)
     }
@@ -220,15 +324,15 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
         NexusTrapezoidIter::new(self, DividerDirection::Descending)
     }
 
-    pub fn get_down_trapezoid_in_direction(&self, ns: &[Nexus<V, Index>], ss: &[Segment<V, Index>], s: &Segment<V, Index>) -> Result<Idx<Trapezoid<V, Index>>, InternalError> {
+    pub fn get_down_trapezoid_in_direction(&self, ns: &Nexuses<V, Index>, ss: &Segments<V, Index>, s: &Segment<V, Index>) -> Result<Idx<Trapezoid<V, Index>>, InternalError> {
         match self.filter_dividers(DividerDirection::Descending) {
             Zot::Zero => Ok(self.ti_downleft),
             Zot::One(div_r) |
             Zot::Two(_, div_r) => {
-                let c = if ns[s.ni_min()].vertex() == self.vertex() {
+                let c = if ns.vertex(s.ni_min()) == self.vi {
                     return Err(InternalError::new("Invalid segment/nexus connection"))
-                } else if ns[s.ni_max()].vertex() == self.vertex() {
-                    ns[ss[div_r.si].ni_min()].coords()
+                } else if ns.vertex(s.ni_max()) == self.vi {
+                    ns.coords(ss.get(ns, div_r.si).ni_min())
                 } else {
                     self.coords()
                 };
@@ -267,13 +371,13 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
         }
     }
 
-    pub fn get_trapezoid_toward_coords(&self, ss: &[Segment<V, Index>], ns: &[Nexus<V, Index>], direction: DividerDirection, c_to: Coords<V::Coordinate>) -> Result<Idx<Trapezoid<V, Index>>, InternalError> {
+    pub fn get_trapezoid_toward_coords(&self, ss: &Segments<V, Index>, ns: &Nexuses<V, Index>, direction: DividerDirection, c_to: Coords<V::Coordinate>) -> Result<Idx<Trapezoid<V, Index>>, InternalError> {
         match self.filter_dividers(direction) {
             Zot::Zero => Ok(if direction == DividerDirection::Ascending { self.ti_upleft } else { self.ti_downleft }),
             Zot::One(div) => {
-                let s = &ss[div.si];
+                let s = ss.get(ns, div.si);
                 
-                let ti = if is_left_of_line(ns[s.ni_min()].coords(), ns[s.ni_max()].coords(), c_to) {
+                let ti = if is_left_of_line(ns.coords(s.ni_min()), ns.coords(s.ni_max()), c_to) {
                     match direction {
                         DividerDirection::Ascending => self.ti_upleft,
                         DividerDirection::Descending => self.ti_downleft,
@@ -289,6 +393,148 @@ impl<V: Vertex, Index: VertexIndex> Nexus<V, Index> {
     }
 }
 
+/// The nexuses of a trapezoidation, stored as a structure of arrays.
+///
+/// Each [Nexus] is read whole by value and changed with [Nexuses::modify]. Coordinates, which point location compares against,
+/// can be read on their own.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound(
+    serialize = "Index: serde::Serialize, V::Coordinate: serde::Serialize",
+    deserialize = "Index: serde::Deserialize<'de>, V::Coordinate: serde::Deserialize<'de>",
+)))]
+pub(crate) struct Nexuses<V: Vertex, Index: VertexIndex> {
+    vertex: Column<Nexus<V, Index>, Index>,
+    ring: Column<Nexus<V, Index>, u32>,
+    coords: Column<Nexus<V, Index>, Coords<V::Coordinate>>,
+    ti_upleft: Column<Nexus<V, Index>, Idx<Trapezoid<V, Index>>>,
+    ti_downleft: Column<Nexus<V, Index>, Idx<Trapezoid<V, Index>>>,
+    #[cfg_attr(feature = "serde", serde(with = "serde_zot"))]
+    dividers: Column<Nexus<V, Index>, Zot<Divider<V, Index>>>,
+}
+
+impl<V: Vertex, Index: VertexIndex> Nexuses<V, Index> {
+    pub fn len(&self) -> usize {
+        self.coords.len()
+    }
+
+    #[inline]
+    pub fn get(&self, ni: Idx<Nexus<V, Index>>) -> Nexus<V, Index> {
+        Nexus {
+            vi: self.vertex[ni].clone(),
+            ring: self.ring[ni] as usize,
+            c: self.coords[ni],
+            ti_upleft: self.ti_upleft[ni],
+            ti_downleft: self.ti_downleft[ni],
+            dividers: self.dividers[ni],
+            _v: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn coords(&self, ni: Idx<Nexus<V, Index>>) -> Coords<V::Coordinate> {
+        self.coords[ni]
+    }
+
+    pub fn vertex(&self, ni: Idx<Nexus<V, Index>>) -> Index {
+        self.vertex[ni].clone()
+    }
+
+    /// Change the trapezoids and segments linked to the nexus
+    pub fn modify<R>(&mut self, ni: Idx<Nexus<V, Index>>, f: impl FnOnce(&mut Nexus<V, Index>) -> R) -> R {
+        let mut n = self.get(ni);
+        let result = f(&mut n);
+        self.ti_upleft[ni] = n.ti_upleft;
+        self.ti_downleft[ni] = n.ti_downleft;
+        self.dividers[ni] = n.dividers;
+        result
+    }
+
+    pub fn push_get_index(&mut self, n: Nexus<V, Index>) -> Idx<Nexus<V, Index>> {
+        let ni = Idx::new(self.len());
+        self.vertex.push(n.vi);
+        // There are fewer rings than vertices, which are limited to MAX_VERTICES, so this doesn't truncate
+        self.ring.push(n.ring as u32);
+        self.coords.push(n.c);
+        self.ti_upleft.push(n.ti_upleft);
+        self.ti_downleft.push(n.ti_downleft);
+        self.dividers.push(n.dividers);
+        ni
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Nexus<V, Index>> + '_ {
+        self.iter_index().map(|ni| self.get(ni))
+    }
+
+    pub fn iter_index(&self) -> impl ExactSizeIterator<Item = Idx<Nexus<V, Index>>> {
+        (0..self.len()).map(Idx::new)
+    }
+
+    /// Whether every column has a value for each nexus, which deserialized nexuses need not
+    #[cfg(feature = "serde")]
+    pub fn columns_match(&self) -> bool {
+        [self.vertex.len(), self.ring.len(), self.ti_upleft.len(), self.ti_downleft.len(), self.dividers.len()].iter().all(|&len| len == self.len())
+    }
+
+    pub fn clear(&mut self) {
+        self.vertex.clear();
+        self.ring.clear();
+        self.coords.clear();
+        self.ti_upleft.clear();
+        self.ti_downleft.clear();
+        self.dividers.clear();
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.vertex.reserve(additional);
+        self.ring.reserve(additional);
+        self.coords.reserve(additional);
+        self.ti_upleft.reserve(additional);
+        self.ti_downleft.reserve(additional);
+        self.dividers.reserve(additional);
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.vertex.shrink_to_fit();
+        self.ring.shrink_to_fit();
+        self.coords.shrink_to_fit();
+        self.ti_upleft.shrink_to_fit();
+        self.ti_downleft.shrink_to_fit();
+        self.dividers.shrink_to_fit();
+    }
+
+    /// The bytes allocated for the nexuses
+    #[cfg(test)]
+    pub fn heap_bytes(&self) -> usize {
+        self.vertex.heap_bytes() + self.ring.heap_bytes() + self.coords.heap_bytes() +
+            self.ti_upleft.heap_bytes() + self.ti_downleft.heap_bytes() + self.dividers.heap_bytes()
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> Default for Nexuses<V, Index> {
+    fn default() -> Self {
+        Self {
+            vertex: Column::new(),
+            ring: Column::new(),
+            coords: Column::new(),
+            ti_upleft: Column::new(),
+            ti_downleft: Column::new(),
+            dividers: Column::new(),
+        }
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> Clone for Nexuses<V, Index> {
+    fn clone(&self) -> Self {
+        Self {
+            vertex: self.vertex.clone(),
+            ring: self.ring.clone(),
+            coords: self.coords.clone(),
+            ti_upleft: self.ti_upleft.clone(),
+            ti_downleft: self.ti_downleft.clone(),
+            dividers: self.dividers.clone(),
+        }
+    }
+}
+
 struct NexusTrapezoidIter<'a, V: Vertex, Index: VertexIndex> {
     parent: &'a Nexus<V, Index>,
     dir: DividerDirection,
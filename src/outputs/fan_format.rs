@@ -40,5 +40,11 @@ pub trait FanBuilder<'p, P: PolygonList<'p> + ?Sized>: Sized {
     /// Called when triangulation encounters an error.
     /// 
     /// Any required cleanup (e.g. removing the partial triangulation added to an existing [Vec]) should be done here
-    fn fail(self, error: &TriangulationError<Self::Error>);
+    fn fail(self, error: &TriangulationError<Self::Error, P::Index>);
 }
+
+/// The error returned when triangulating `P` into the format `FB`
+pub type FanError<'p, P, FB> = TriangulationError<<<FB as FanFormat<'p, P>>::Builder as FanBuilder<'p, P>>::Error, <P as PolygonList<'p>>::Index>;
+
+/// The result of triangulating `P` into the format `FB`
+pub type FanResult<'p, P, FB> = Result<<<FB as FanFormat<'p, P>>::Builder as FanBuilder<'p, P>>::Output, FanError<'p, P, FB>>;
@@ -0,0 +1,88 @@
+use std::marker::PhantomData;
+
+use crate::{PolygonList, ListFormat, ListBuilder, TriangleWinding, TriangulationError};
+
+/// A wrapper which outputs the triangles of a [ListFormat] in a canonical order
+///
+/// Each triangle is rotated so its smallest index comes first, preserving its winding,
+/// and the triangles are sorted lexicographically before being passed to the wrapped [ListFormat].
+/// The same triangulation always produces the same output, regardless of the order its triangles were generated in.
+///
+/// Constructed with [ListFormat::canonicalize].
+pub struct CanonicalListFormat<'p, P: PolygonList<'p> + ?Sized, LF: ListFormat<'p, P>>(LF, PhantomData<&'p P>)
+where P::Index: Ord;
+
+impl<'p, P: PolygonList<'p> + ?Sized, LF: ListFormat<'p, P>> CanonicalListFormat<'p, P, LF>
+where P::Index: Ord {
+    pub(crate) fn new(list_format: LF) -> Self {
+        Self(list_format, PhantomData)
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, LF: ListFormat<'p, P>> ListFormat<'p, P> for CanonicalListFormat<'p, P, LF>
+where P::Index: Ord {
+    type Builder = CanonicalListBuilder<'p, P, LF::Builder>;
+
+    fn initialize(self, polygon_list: &'p P) -> Result<Self::Builder, <Self::Builder as ListBuilder<'p, P>>::Error> {
+        let list_builder = self.0.initialize(polygon_list)?;
+        Ok(CanonicalListBuilder::new(list_builder, polygon_list.vertex_count()))
+    }
+}
+
+/// A wrapper which outputs the triangles of a [ListBuilder] in a canonical order
+pub struct CanonicalListBuilder<'p, P: PolygonList<'p> + ?Sized, LB: ListBuilder<'p, P>>
+where P::Index: Ord {
+    list_builder: LB,
+    triangles: Vec<[P::Index; 3]>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, LB: ListBuilder<'p, P>> CanonicalListBuilder<'p, P, LB>
+where P::Index: Ord {
+    fn new(list_builder: LB, vertex_count: usize) -> Self {
+        Self {
+            list_builder,
+            // A single polygon with n vertices has n - 2 triangles, each hole adds 2 more
+            triangles: Vec::with_capacity(vertex_count),
+        }
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, LB: ListBuilder<'p, P>> ListBuilder<'p, P> for CanonicalListBuilder<'p, P, LB>
+where P::Index: Ord {
+    type Output = LB::Output;
+    type Error = LB::Error;
+
+    const WINDING: TriangleWinding = LB::WINDING;
+
+    fn add_triangle(&mut self, vi0: P::Index, vi1: P::Index, vi2: P::Index) -> Result<(), Self::Error> {
+        let mut triangle = [vi0, vi1, vi2];
+        // Rotating (rather than sorting) the indices keeps the winding intact
+        let min = (0..3).min_by(|&i, &j| triangle[i].cmp(&triangle[j])).unwrap_or(0);
+        triangle.rotate_left(min);
+        self.triangles.push(triangle);
+        Ok(())
+    }
+
+    fn build(self) -> Result<Self::Output, Self::Error> {
+        let Self { mut list_builder, mut triangles } = self;
+        triangles.sort_unstable();
+
+        for [vi0, vi1, vi2] in triangles {
+            if let Err(error) = list_builder.add_triangle(vi0, vi1, vi2) {
+                // Give the wrapped builder the chance to clean up its partial output, then return its own error
+                let error = TriangulationError::FanBuilder(error);
+                list_builder.fail(&error);
+                match error {
+                    TriangulationError::FanBuilder(error) => return Err(error),
+                    _ => unreachable!(),
+                }
+            }
+        }
+
+        list_builder.build()
+    }
+
+    fn fail(self, error: &TriangulationError<Self::Error, P::Index>) {
+        self.list_builder.fail(error);
+    }
+}
@@ -63,7 +63,7 @@ where P::Vertex: Clone {
         Ok(self.list.build())
     }
 
-    fn fail(self, _error: &TriangulationError<Self::Error>) {
+    fn fail(self, _error: &TriangulationError<Self::Error, P::Index>) {
         self.list.fail();
     }
 }
@@ -105,7 +105,7 @@ impl<'p, P: PolygonList<'p> + ?Sized, FS: Fans<Fan=P::Index>> FanBuilder<'p, P>
         Ok(self.fans)
     }
 
-    fn fail(mut self, _error: &TriangulationError<Self::Error>) {
+    fn fail(mut self, _error: &TriangulationError<Self::Error, P::Index>) {
         self.fans.truncate(self.initial_vert_count);
     }
 }
@@ -64,7 +64,7 @@ impl<'p, P: PolygonList<'p> + ?Sized, LB: ListBuilder<'p, P>> FanBuilder<'p, P>
         self.list_builder.build()
     }
 
-    fn fail(self, error: &crate::TriangulationError<Self::Error>) {
+    fn fail(self, error: &crate::TriangulationError<Self::Error, P::Index>) {
         self.list_builder.fail(error);
     }
 }
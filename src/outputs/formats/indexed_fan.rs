@@ -65,7 +65,7 @@ where FS::Fan: Fan<P::Index> {
         Ok(self.fans.build())
     }
 
-    fn fail(self, _error: &TriangulationError<Self::Error>) {
+    fn fail(self, _error: &TriangulationError<Self::Error, P::Index>) {
         self.fans.fail();
     }
 }
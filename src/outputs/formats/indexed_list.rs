@@ -54,7 +54,7 @@ impl<'p, P: PolygonList<'p> + ?Sized, L: List<P::Index>> ListBuilder<'p, P> for
         Ok(self.list.build())
     }
 
-    fn fail(self, _error: &TriangulationError<Self::Error>) {
+    fn fail(self, _error: &TriangulationError<Self::Error, P::Index>) {
         self.list.fail();
     }
 }
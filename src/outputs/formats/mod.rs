@@ -1,5 +1,5 @@
 //! Predefined implementations of [FanFormat](crate::FanFormat), [FanBuilder](crate::FanBuilder), 
-//! [ListFormat](crate::ListFormat), and [ListBuilder](crate::ListBuilder)
+//! [ListFormat](crate::ListFormat), [ListBuilder](crate::ListBuilder), [TrapezoidFormat](crate::TrapezoidFormat), and [TrapezoidBuilder](crate::TrapezoidBuilder)
 
 mod generic_fans;
 pub(crate) use generic_fans::GenericFans;
@@ -18,4 +18,8 @@ pub use indexed_list::IndexedListFormat;
 mod deindexed_list;
 pub use deindexed_list::DeindexedListFormat;
 mod reverse_fan;
-pub use reverse_fan::ReverseFanFormat;
\ No newline at end of file
+pub use reverse_fan::ReverseFanFormat;
+mod canonical_list;
+pub use canonical_list::CanonicalListFormat;
+mod trapezoid_list;
+pub use trapezoid_list::TrapezoidListFormat;
\ No newline at end of file
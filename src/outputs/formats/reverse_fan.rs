@@ -41,7 +41,7 @@ impl<'p, P: PolygonList<'p> + ?Sized, FB: FanBuilder<'p, P>> FanBuilder<'p, P> f
         self.0.build()
     }
 
-    fn fail(self, error: &crate::TriangulationError<Self::Error>) {
+    fn fail(self, error: &crate::TriangulationError<Self::Error, P::Index>) {
         self.0.fail(error);
     }
 }
\ No newline at end of file
@@ -0,0 +1,61 @@
+use std::marker::PhantomData;
+
+use crate::{PolygonList, TrapezoidBuilder, TrapezoidFormat, TriangulationError, Vertex};
+
+/// A [TrapezoidFormat] which outputs each trapezoid as its four corners, constructed from `[x, y]` coordinates.
+///
+/// Accepts either a [Vec] or a mutable reference to one, which will be appended to.
+#[derive(Debug)]
+pub struct TrapezoidListFormat<'p, P: PolygonList<'p> + ?Sized, V: From<[<P::Vertex as Vertex>::Coordinate; 2]>, L: AsMut<Vec<[V; 4]>>> {
+    list: L,
+    _phantom: PhantomData<(&'p P, V)>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, V: From<[<P::Vertex as Vertex>::Coordinate; 2]>, L: AsMut<Vec<[V; 4]>>> TrapezoidListFormat<'p, P, V, L> {
+    /// Create a trapezoid format which stores its output in the given [Vec]
+    pub fn new(list: L) -> Self {
+        Self { list, _phantom: PhantomData, }
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, V: From<[<P::Vertex as Vertex>::Coordinate; 2]>, L: AsMut<Vec<[V; 4]>>> TrapezoidFormat<'p, P> for TrapezoidListFormat<'p, P, V, L> {
+    type Builder = TrapezoidListBuilder<'p, P, V, L>;
+
+    fn initialize(self, _polygon_list: &'p P) -> Result<Self::Builder, <Self::Builder as TrapezoidBuilder<'p, P>>::Error> {
+        Ok(TrapezoidListBuilder::new(self.list))
+    }
+}
+
+/// The [TrapezoidBuilder] of a [TrapezoidListFormat], which appends each trapezoid's corners to the list.
+///
+/// If trapezoid generation fails, the list is truncated back to its original length.
+pub struct TrapezoidListBuilder<'p, P: PolygonList<'p> + ?Sized, V: From<[<P::Vertex as Vertex>::Coordinate; 2]>, L: AsMut<Vec<[V; 4]>>> {
+    list: L,
+    initial_len: usize,
+    _phantom: PhantomData<(&'p P, V)>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, V: From<[<P::Vertex as Vertex>::Coordinate; 2]>, L: AsMut<Vec<[V; 4]>>> TrapezoidListBuilder<'p, P, V, L> {
+    fn new(mut list: L) -> Self {
+        let initial_len = list.as_mut().len();
+        Self { list, initial_len, _phantom: PhantomData, }
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized, V: From<[<P::Vertex as Vertex>::Coordinate; 2]>, L: AsMut<Vec<[V; 4]>>> TrapezoidBuilder<'p, P> for TrapezoidListBuilder<'p, P, V, L> {
+    type Output = L;
+    type Error = std::convert::Infallible;
+
+    fn add_trapezoid(&mut self, corners: [[<P::Vertex as Vertex>::Coordinate; 2]; 4]) -> Result<(), Self::Error> {
+        self.list.as_mut().push(corners.map(V::from));
+        Ok(())
+    }
+
+    fn build(self) -> Result<Self::Output, Self::Error> {
+        Ok(self.list)
+    }
+
+    fn fail(mut self, _error: &TriangulationError<Self::Error, P::Index>) {
+        self.list.as_mut().truncate(self.initial_len);
+    }
+}
@@ -17,6 +17,14 @@ pub trait ListFormat<'p, P: PolygonList<'p> + ?Sized> {
     where Self: Sized {
         formats::FanToListFormat::new(self)
     }
+
+    /// Constructs a [ListFormat] which outputs triangles in a canonical order, suitable for comparing triangulations.
+    /// 
+    /// See [CanonicalListFormat](formats::CanonicalListFormat).
+    fn canonicalize(self) -> formats::CanonicalListFormat<'p, P, Self>
+    where Self: Sized, P::Index: Ord {
+        formats::CanonicalListFormat::new(self)
+    }
 }
 
 /// Performs the construction of a triangle list
@@ -38,5 +46,5 @@ pub trait ListBuilder<'p, P: PolygonList<'p> + ?Sized> {
     /// Called when triangulation encounters an error.
     /// 
     /// Any required cleanup (e.g. removing the partial triangulation added to an existing [Vec]) should be done here
-    fn fail(self, error: &TriangulationError<Self::Error>);
+    fn fail(self, error: &TriangulationError<Self::Error, P::Index>);
 }
\ No newline at end of file
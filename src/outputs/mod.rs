@@ -1,9 +1,11 @@
 pub mod formats;
 
 mod fan_format;
-pub use fan_format::{FanFormat, FanBuilder};
+pub use fan_format::{FanFormat, FanBuilder, FanError, FanResult};
 mod list_format;
 pub use list_format::{ListFormat, ListBuilder};
+mod trapezoid_format;
+pub use trapezoid_format::{TrapezoidFormat, TrapezoidBuilder};
 mod triangle_winding;
 pub use triangle_winding::TriangleWinding;
 mod fan;
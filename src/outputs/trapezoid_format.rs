@@ -0,0 +1,36 @@
+use std::error;
+
+use crate::{PolygonList, TriangulationError, Vertex};
+
+/// Describes the construction and layout of a list of trapezoids
+pub trait TrapezoidFormat<'p, P: PolygonList<'p> + ?Sized> {
+    /// The type responsible for constructing the trapezoid list.
+    ///
+    /// This type can be `Self`, if you choose to implement both [TrapezoidFormat] and [TrapezoidBuilder] on the same type.
+    type Builder: TrapezoidBuilder<'p, P> + Sized;
+
+    /// Constructs a [TrapezoidFormat::Builder], optionally using a reference to the [PolygonList] being decomposed.
+    fn initialize(self, polygon_list: &'p P) -> Result<Self::Builder, <Self::Builder as TrapezoidBuilder<'p, P>>::Error>;
+}
+
+/// Performs the construction of a list of trapezoids
+pub trait TrapezoidBuilder<'p, P: PolygonList<'p> + ?Sized> {
+    /// The trapezoid list output type
+    type Output;
+    /// The error type when the builder fails
+    type Error: error::Error;
+
+    /// Adds a trapezoid with the given corners.
+    ///
+    /// The corners are in counterclockwise order (with the y axis pointing up), starting from the bottom left:
+    /// bottom left, bottom right, top right, top left. The bottom and top edges are exactly horizontal.
+    fn add_trapezoid(&mut self, corners: [[<P::Vertex as Vertex>::Coordinate; 2]; 4]) -> Result<(), Self::Error>;
+
+    /// Called when all trapezoids have been added to get the resulting output
+    fn build(self) -> Result<Self::Output, Self::Error>;
+
+    /// Called when an error is encountered.
+    ///
+    /// Any required cleanup (e.g. removing the partial output added to an existing [Vec]) should be done here
+    fn fail(self, error: &TriangulationError<Self::Error, P::Index>);
+}
@@ -0,0 +1,204 @@
+use std::{collections::HashMap, hash::Hash, slice};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{FanFormat, FanResult, PolygonElement, PolygonList, PolygonListExt, Trapezoidation, TrapezoidationError, Vertex, VertexIndex, trapezoidation::{TrapezoidationParts, TrapezoidationState}};
+
+/// The polygons copied by an [OwnedTrapezoidation].
+///
+/// The vertices are copied ring by ring, leaving out empty rings, and keep the indices they had in the original [PolygonList],
+/// so triangulating or locating points gives the same indices as the original.
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(bound(serialize = "V: serde::Serialize, Index: serde::Serialize")))]
+pub struct OwnedPolygonList<V: Vertex, Index: VertexIndex + Hash = [usize; 2]> {
+    rings: Vec<Vec<V>>,
+    indices: Vec<Vec<Index>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lookup: HashMap<Index, [usize; 2]>,
+}
+
+impl<V: Vertex, Index: VertexIndex + Hash> OwnedPolygonList<V, Index> {
+    fn new(rings: Vec<Vec<V>>, indices: Vec<Vec<Index>>) -> Self {
+        let mut lookup = HashMap::with_capacity(indices.iter().map(Vec::len).sum());
+        for (r, ring) in indices.iter().enumerate() {
+            for (i, index) in ring.iter().enumerate() {
+                lookup.entry(index.clone()).or_insert([r, i]);
+            }
+        }
+        Self { rings, indices, lookup }
+    }
+
+    /// The copied rings, each of which has at least 3 vertices
+    pub fn rings(&self) -> &[Vec<V>] {
+        &self.rings
+    }
+
+    /// The original index of each vertex of [OwnedPolygonList::rings]
+    pub fn indices(&self) -> &[Vec<Index>] {
+        &self.indices
+    }
+}
+
+impl<'p, V: Vertex + 'p, Index: VertexIndex + Hash + 'p> PolygonList<'p> for OwnedPolygonList<V, Index> {
+    type Vertex = V;
+    type Index = Index;
+    type IntoItem = PolygonElement<Index>;
+    type Iter<'i> = OwnedPolygonListIter<'i, Index>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.indices.iter().map(Vec::len).sum()
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'p: 'i {
+        OwnedPolygonListIter { rings: self.indices.iter(), current: [].iter() }
+    }
+
+    fn get_vertex<'a>(&'a self, index: Self::Index) -> &'a Self::Vertex
+    where 'p: 'a {
+        let [r, i] = self.lookup[&index];
+        &self.rings[r][i]
+    }
+}
+
+/// [Iterator] for an [OwnedPolygonList]
+pub struct OwnedPolygonListIter<'i, Index> {
+    rings: slice::Iter<'i, Vec<Index>>,
+    current: slice::Iter<'i, Index>,
+}
+
+impl<Index: VertexIndex> Iterator for OwnedPolygonListIter<'_, Index> {
+    type Item = PolygonElement<Index>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(index) = self.current.next() {
+            return Some(PolygonElement::ContinuePolygon(index.clone()));
+        }
+        self.current = self.rings.next()?.iter();
+        Some(PolygonElement::NewPolygon)
+    }
+}
+
+/// A [Trapezoidation] which owns a copy of its polygons, so it can be cached, sent to another thread,
+/// or, with the `serde` feature, serialized.
+///
+/// Vertices keep the indices they had in the original [PolygonList], which must implement [Hash] to look them up.
+/// [OwnedTrapezoidation::trapezoidation] views the structure without copying it, to triangulate into any format or to locate points.
+///
+/// Deserializing checks that the structure is safe to use, but not that it is a valid trapezoidation of the rings,
+/// so it should only be given the output of serializing an [OwnedTrapezoidation].
+///
+/// ```
+/// # use triangulate::{Location, OwnedTrapezoidation, formats::IndexedListFormat, ListFormat};
+/// let square = vec![vec![[0f32, 0.], [10., 0.], [10., 10.], [0., 10.]]];
+/// let owned = OwnedTrapezoidation::new(&square).expect("Trapezoidation failed");
+/// drop(square);
+///
+/// let triangles = owned.triangulate(IndexedListFormat::new(Vec::<[[usize; 2]; 3]>::new()).into_fan_format()).expect("Triangulation failed");
+/// assert_eq!(triangles.len(), 2);
+/// assert_eq!(owned.trapezoidation().locate(&[5f32, 5.]).unwrap(), Location::Inside { ring: 0 });
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(bound(serialize = "V: serde::Serialize, V::Coordinate: serde::Serialize, Index: serde::Serialize")))]
+pub struct OwnedTrapezoidation<V: Vertex, Index: VertexIndex + Hash = [usize; 2]> {
+    polygons: OwnedPolygonList<V, Index>,
+    parts: TrapezoidationParts<V, Index>,
+    seed: Option<u64>,
+}
+
+impl<V: Vertex + Clone, Index: VertexIndex + Hash> OwnedTrapezoidation<V, Index> {
+    /// Copy the polygons and trapezoidize them, with a random seed for ordering segment insertion
+    pub fn new<'p, P>(polygon_list: &'p P) -> Result<Self, TrapezoidationError<Index>>
+    where P: PolygonList<'p, Vertex = V, Index = Index> + ?Sized {
+        Self::with_seed(polygon_list, rand::thread_rng().gen())
+    }
+
+    /// Copy the polygons and trapezoidize them, ordering segment insertion by `seed`.
+    ///
+    /// This produces the same triangulation as [PolygonList::trapezoidize_with_seed] with the same seed.
+    pub fn with_seed<'p, P>(polygon_list: &'p P, seed: u64) -> Result<Self, TrapezoidationError<Index>>
+    where P: PolygonList<'p, Vertex = V, Index = Index> + ?Sized {
+        let (all_indices, ring_ranges) = PolygonListExt::new(polygon_list).ring_ranges();
+        let rings = ring_ranges.iter()
+            .map(|ring| all_indices[ring.clone()].iter().map(|index| polygon_list.get_vertex(index.clone()).clone()).collect())
+            .collect();
+        let indices = ring_ranges.into_iter().map(|ring| all_indices[ring].to_vec()).collect();
+
+        let polygons = OwnedPolygonList::new(rings, indices);
+        let parts = TrapezoidationState::new(&polygons, Some(seed)).build(&mut ChaCha8Rng::seed_from_u64(seed))?.into_parts();
+        Ok(Self { polygons, parts, seed: Some(seed) })
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex + Hash> OwnedTrapezoidation<V, Index> {
+    /// The copied polygons
+    pub fn polygons(&self) -> &OwnedPolygonList<V, Index> {
+        &self.polygons
+    }
+
+    /// The copied rings, each of which has at least 3 vertices
+    pub fn rings(&self) -> &[Vec<V>] {
+        self.polygons.rings()
+    }
+
+    /// The seed used to order segment insertion, if the trapezoidation was generated from one
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// View the trapezoidation of [OwnedTrapezoidation::polygons], without copying it
+    pub fn trapezoidation(&self) -> Trapezoidation<'_, OwnedPolygonList<V, Index>> {
+        Trapezoidation::borrowed(&self.polygons, &self.parts, self.seed)
+    }
+
+    /// Triangulate the rings into the layout specified by `format`.
+    ///
+    /// Vertices are identified by their indices in the original [PolygonList]. See [PolygonList::triangulate].
+    pub fn triangulate<'b, FB: FanFormat<'b, OwnedPolygonList<V, Index>>>(&'b self, format: FB) -> FanResult<'b, OwnedPolygonList<V, Index>, FB> {
+        self.trapezoidation().triangulate(format)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, V, Index> serde::Deserialize<'de> for OwnedPolygonList<V, Index>
+where V: Vertex + serde::Deserialize<'de>,
+      Index: VertexIndex + Hash + serde::Deserialize<'de> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "OwnedPolygonList")]
+        struct Unchecked<V, Index> {
+            rings: Vec<Vec<V>>,
+            indices: Vec<Vec<Index>>,
+        }
+
+        let Unchecked { rings, indices } = Unchecked::deserialize(deserializer)?;
+        if rings.len() != indices.len() || rings.iter().zip(&indices).any(|(ring, ring_indices)| ring.len() != ring_indices.len()) {
+            return Err(serde::de::Error::custom("Rings and indices differ in length"));
+        }
+        Ok(Self::new(rings, indices))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, V, Index> serde::Deserialize<'de> for OwnedTrapezoidation<V, Index>
+where V: Vertex + serde::Deserialize<'de>,
+      V::Coordinate: serde::Deserialize<'de>,
+      Index: VertexIndex + Hash + serde::Deserialize<'de> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "OwnedTrapezoidation", bound(deserialize = "V: serde::Deserialize<'de>, V::Coordinate: serde::Deserialize<'de>, Index: serde::Deserialize<'de>"))]
+        struct Unchecked<V: Vertex, Index: VertexIndex + Hash> {
+            polygons: OwnedPolygonList<V, Index>,
+            parts: TrapezoidationParts<V, Index>,
+            seed: Option<u64>,
+        }
+
+        let Unchecked { polygons, parts, seed } = Unchecked::deserialize(deserializer)?;
+        parts.check(|index, ring| {
+            let &[r, i] = polygons.lookup.get(index).filter(|&&[r, _]| r == ring)?;
+            let v: &V = &polygons.rings[r][i];
+            Some(crate::Coords::new(v.x(), v.y()))
+        }).map_err(serde::de::Error::custom)?;
+        Ok(Self { polygons, parts, seed })
+    }
+}
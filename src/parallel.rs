@@ -0,0 +1,135 @@
+use std::{cmp::Ordering, ops::Range, slice};
+
+use rayon::prelude::*;
+
+use crate::{FanFormat, FanResult, PolygonElement, PolygonList, PolygonListExt, Real, TriangulationError, Vertex, VertexIndex, fan_builder_state::FanBuilderState, fan_recorder::{FanRecorder, RecordedFans, with_fan_builder_error}};
+
+/// Some of the rings of a [PolygonList], which can be triangulated independently of the others.
+///
+/// Vertices keep their original indices, so the fans can be passed on to a format for the whole [PolygonList].
+pub(crate) struct RingGroup<'g, 'p, P: PolygonList<'p> + ?Sized> {
+    polygon_list: &'p P,
+    indices: &'g [P::Index],
+    rings: Vec<Range<usize>>,
+}
+
+impl<'g, 'p: 'g, P: PolygonList<'p> + ?Sized> PolygonList<'g> for RingGroup<'g, 'p, P> {
+    type Vertex = P::Vertex;
+    type Index = P::Index;
+    type IntoItem = PolygonElement<P::Index>;
+    type Iter<'i> = RingGroupIter<'i, P::Index>
+    where Self: 'i, Self::Vertex: 'i, 'g: 'i;
+
+    fn vertex_count(&self) -> usize {
+        self.rings.iter().map(|ring| ring.len()).sum()
+    }
+
+    fn iter_indices<'i>(&'i self) -> Self::Iter<'i>
+    where Self: 'i, Self::Vertex: 'i, 'g: 'i {
+        RingGroupIter { indices: self.indices, rings: self.rings.iter(), current: 0..0 }
+    }
+
+    fn get_vertex<'a>(&'a self, index: Self::Index) -> &'a Self::Vertex
+    where 'g: 'a {
+        self.polygon_list.get_vertex(index)
+    }
+}
+
+pub(crate) struct RingGroupIter<'i, Index> {
+    indices: &'i [Index],
+    rings: slice::Iter<'i, Range<usize>>,
+    current: Range<usize>,
+}
+
+impl<Index: VertexIndex> Iterator for RingGroupIter<'_, Index> {
+    type Item = PolygonElement<Index>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(i) = self.current.next() {
+            return Some(PolygonElement::ContinuePolygon(self.indices[i].clone()));
+        }
+        let ring = self.rings.next()?;
+        self.current = ring.clone();
+        Some(PolygonElement::NewPolygon)
+    }
+}
+
+/// Partition `rings` into groups whose bounding boxes overlap, directly or through other rings in the group.
+///
+/// Holes always lie within the bounding box of the ring around them, so each group can be triangulated on its own.
+/// Groups are ordered by their first ring, and rings keep their original order within each group.
+pub(crate) fn ring_groups<'p, P: PolygonList<'p> + ?Sized>(polygon_list: &'p P, indices: &[P::Index], rings: &[Range<usize>]) -> Vec<Vec<Range<usize>>> {
+    let bounds: Vec<_> = rings.iter().map(|ring| {
+        let v = polygon_list.get_vertex(indices[ring.start].clone());
+        let (mut min, mut max) = ([v.x(), v.y()], [v.x(), v.y()]);
+        for index in &indices[ring.clone()] {
+            let v = polygon_list.get_vertex(index.clone());
+            min = [min[0].min(v.x()), min[1].min(v.y())];
+            max = [max[0].max(v.x()), max[1].max(v.y())];
+        }
+        (min, max)
+    }).collect();
+
+    let mut parent: Vec<usize> = (0..rings.len()).collect();
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    // Sweep from left to right, keeping the rings whose bounding box reaches the current one
+    let mut order: Vec<usize> = (0..rings.len()).collect();
+    order.sort_by(|&a, &b| bounds[a].0[0].partial_cmp(&bounds[b].0[0]).unwrap_or(Ordering::Equal));
+    let mut active: Vec<usize> = Vec::new();
+    for r in order {
+        let (min, max) = bounds[r];
+        active.retain(|&a| bounds[a].1[0] >= min[0]);
+        for &a in &active {
+            if bounds[a].0[1] <= max[1] && min[1] <= bounds[a].1[1] {
+                let (ra, rr) = (find(&mut parent, a), find(&mut parent, r));
+                parent[ra.max(rr)] = ra.min(rr);
+            }
+        }
+        active.push(r);
+    }
+
+    let mut group_of = vec![usize::MAX; rings.len()];
+    let mut groups: Vec<Vec<Range<usize>>> = Vec::new();
+    for (r, ring) in rings.iter().enumerate() {
+        let root = find(&mut parent, r);
+        if group_of[root] == usize::MAX {
+            group_of[root] = groups.len();
+            groups.push(Vec::new());
+        }
+        groups[group_of[root]].push(ring.clone());
+    }
+    groups
+}
+
+pub(crate) fn par_triangulate<'p, P, FB>(polygon_list: &'p P, format: FB, seed: u64) -> FanResult<'p, P, FB>
+where P: PolygonList<'p> + Sync + ?Sized,
+      P::Index: Send + Sync,
+      FB: FanFormat<'p, P> {
+    // Report short rings by their position in the whole list, rather than within their group
+    let (indices, rings) = match PolygonListExt::new(polygon_list).checked_ring_ranges() {
+        Ok(ring_ranges) => ring_ranges,
+        Err(error) => return FanBuilderState::<P, FB>::Uninitialized(format).complete(Err(TriangulationError::TrapezoidationError(error))),
+    };
+
+    let groups = ring_groups(polygon_list, &indices, &rings);
+    let results: Vec<_> = groups.into_par_iter()
+        .map(|rings| {
+            let group = RingGroup { polygon_list, indices: &indices, rings };
+            group.triangulate_with_seed(FanRecorder::<P, FB::Builder>::new(RecordedFans::new()), seed)
+        })
+        .collect();
+
+    // Replay the fans in group order, so the output doesn't depend on scheduling
+    let mut fbs = FanBuilderState::<P, FB>::Uninitialized(format);
+    let result = results.into_iter().try_for_each(|fans| {
+        fans.map_err(|error| with_fan_builder_error(error, |i| i))?.replay_mapped(&mut fbs, polygon_list, P::Index::clone)
+    });
+    fbs.complete(result)
+}
@@ -1,13 +1,12 @@
 use core::fmt;
 use std::clone;
 
-use num_traits::real::Real;
-
-use crate::{Vertex, VertexIndex, idx::{Idx, IdxDisplay}, trapezoid::Trapezoid, Coords};
+use crate::{Vertex, VertexIndex, idx::{Idx, IdxDisplay}, nexus::Nexus, segment::Segment, trapezoid::Trapezoid};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound = ""))]
 pub(crate) enum QueryNode<V: Vertex, Index: VertexIndex> {
-    Branch(Idx<QueryNode<V, Index>>, Idx<QueryNode<V, Index>>, QueryNodeBranch<V::Coordinate>),
+    Branch(Idx<QueryNode<V, Index>>, Idx<QueryNode<V, Index>>, QueryNodeBranch<V, Index>),
     Sink(Idx<Trapezoid<V, Index>>),
 }
 
@@ -29,38 +28,43 @@ impl<V: Vertex, Index: VertexIndex> fmt::Display for QueryNode<V, Index> {
     }
 }
 
-pub(crate) enum QueryNodeBranch<C: Real> {
-    X(Coords<C>, Coords<C>),
-    Y(Coords<C>),
+/// The test made at a branch, referring to the segment or vertex rather than copying its coordinates
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound = ""))]
+pub(crate) enum QueryNodeBranch<V: Vertex, Index: VertexIndex> {
+    /// Left of the segment, or right of it
+    X(Idx<Segment<V, Index>>),
+    /// Below or at the vertex, or above it
+    Y(Idx<Nexus<V, Index>>),
 }
 
-impl<C: Real> fmt::Debug for QueryNodeBranch<C> {
+impl<V: Vertex, Index: VertexIndex> fmt::Debug for QueryNodeBranch<V, Index> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::X(c_min_x, c_max_x) => f.debug_tuple("X").field(c_min_x).field(c_max_x).finish(),
-            Self::Y(c_y) => f.debug_tuple("Y").field(c_y).finish(),
+            Self::X(si) => f.debug_tuple("X").field(si).finish(),
+            Self::Y(ni) => f.debug_tuple("Y").field(ni).finish(),
         }
     }
 }
 
-impl<C: Real> clone::Clone for QueryNodeBranch<C> {
+impl<V: Vertex, Index: VertexIndex> clone::Clone for QueryNodeBranch<V, Index> {
     fn clone(&self) -> Self {
         match self {
-            Self::X(c_min, c_max) => Self::X(*c_min, *c_max),
-            Self::Y(c) => Self::Y(*c),
+            Self::X(si) => Self::X(*si),
+            Self::Y(ni) => Self::Y(*ni),
         }
     }
 }
 
-impl<C: Real> fmt::Display for QueryNodeBranch<C> {
+impl<V: Vertex, Index: VertexIndex> fmt::Display for QueryNodeBranch<V, Index> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::X(c_min_x, c_max_x) => write!(f, "X({}, {})", c_min_x, c_max_x),
-            Self::Y(c_y) => write!(f, "Y({})", c_y),
+            Self::X(si) => write!(f, "X({})", si),
+            Self::Y(ni) => write!(f, "Y({})", ni),
         }
     }
 }
 
+#[cfg(feature = "_debugging")]
 pub struct IndexedQueryNode<'a, V: Vertex, Index: VertexIndex>(Idx<QueryNode<V, Index>>, &'a QueryNode<V, Index>);
 
 impl<V: Vertex, Index: VertexIndex> QueryNode<V, Index> {
@@ -74,6 +78,7 @@ impl<V: Vertex, Index: VertexIndex> QueryNode<V, Index> {
     }
 }
 
+#[cfg(feature = "_debugging")]
 impl<'a, V: Vertex, Index: VertexIndex> std::fmt::Display for IndexedQueryNode<'a, V, Index> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "[{}] {}", self.0, self.1)
@@ -92,22 +97,22 @@ impl<V: Vertex, Index: VertexIndex> QueryNode<V, Index> {
     }
 
     #[must_use]
-    pub fn branch_x(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, c_min_x: Coords<V::Coordinate>, c_max_x: Coords<V::Coordinate>, ti_right: Idx<Trapezoid<V, Index>>) -> (Self, Self) {
-        (self.branch(qi_left, qi_right, QueryNodeBranch::X(c_min_x, c_max_x)), QueryNode::Sink(ti_right))
+    pub fn branch_x(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, si: Idx<Segment<V, Index>>, ti_right: Idx<Trapezoid<V, Index>>) -> (Self, Self) {
+        (self.branch(qi_left, qi_right, QueryNodeBranch::X(si)), QueryNode::Sink(ti_right))
     }
 
     #[must_use]
-    pub fn merge_x(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, c_min_x: Coords<V::Coordinate>, c_max_x: Coords<V::Coordinate>) -> Self {
-        self.branch(qi_left, qi_right, QueryNodeBranch::X(c_min_x, c_max_x))
+    pub fn merge_x(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, si: Idx<Segment<V, Index>>) -> Self {
+        self.branch(qi_left, qi_right, QueryNodeBranch::X(si))
     }
 
     #[must_use]
-    pub fn branch_y(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, c_y: Coords<V::Coordinate>, ti_up: Idx<Trapezoid<V, Index>>) -> (Self, Self) {
-        (self.branch(qi_left, qi_right, QueryNodeBranch::Y(c_y)), QueryNode::Sink(ti_up))
+    pub fn branch_y(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, ni: Idx<Nexus<V, Index>>, ti_up: Idx<Trapezoid<V, Index>>) -> (Self, Self) {
+        (self.branch(qi_left, qi_right, QueryNodeBranch::Y(ni)), QueryNode::Sink(ti_up))
     }
 
     #[must_use]
-    fn branch(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, branch: QueryNodeBranch<V::Coordinate>) -> Self {
+    fn branch(&mut self, qi_left: Idx<Self>, qi_right: Idx<Self>, branch: QueryNodeBranch<V, Index>) -> Self {
         let mut new = QueryNode::Branch(qi_left, qi_right, branch);
         std::mem::swap(self, &mut new);
         new
@@ -0,0 +1,155 @@
+use std::{convert::Infallible, marker::PhantomData};
+
+use num_traits::ToPrimitive;
+
+use crate::{PolygonList, TrapezoidBuilder, TrapezoidFormat, TriangulationError, Vertex};
+
+/// Accumulates the exact area of polygons covering each pixel.
+///
+/// Each edge adds its signed area contribution to the pixels it passes through, and to the pixel to its right.
+/// Summing a row from left to right then gives the coverage of each pixel.
+pub(crate) struct CoverageBuilder<'p, P: PolygonList<'p> + ?Sized> {
+    width: usize,
+    height: usize,
+    transform: [[f32; 3]; 2],
+    // Two extra columns per row, so edges clamped to the right border never spill into the next row
+    accumulator: Vec<f32>,
+    _phantom: PhantomData<&'p P>,
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> CoverageBuilder<'p, P> {
+    pub fn new(width: usize, height: usize, transform: [[<P::Vertex as Vertex>::Coordinate; 3]; 2]) -> Self {
+        Self {
+            width,
+            height,
+            transform: transform.map(|row| row.map(|c| c.to_f32().unwrap_or(f32::NAN))),
+            accumulator: vec![0.; (width + 2) * height],
+            _phantom: PhantomData,
+        }
+    }
+
+    fn stride(&self) -> usize {
+        self.width + 2
+    }
+
+    fn apply(&self, [x, y]: [f32; 2]) -> [f32; 2] {
+        let [r0, r1] = self.transform;
+        [r0[0] * x + r0[1] * y + r0[2], r1[0] * x + r1[1] * y + r1[2]]
+    }
+
+    // Split the line where it crosses the left and right borders, so each piece can be clamped to the image without bending it.
+    // Anything left of the image covers the whole row, the same as a vertical line on the left border
+    fn add_line(&mut self, p0: [f32; 2], p1: [f32; 2]) {
+        let width = self.width as f32;
+        let mut ts = [0., 1., 1., 1.];
+        for (i, x_border) in [0., width].into_iter().enumerate() {
+            let t = (x_border - p0[0]) / (p1[0] - p0[0]);
+            if t > 0. && t < 1. {
+                ts[i + 1] = t;
+            }
+        }
+        ts[1..3].sort_by(f32::total_cmp);
+
+        let lerp = |t: f32| [p0[0] + (p1[0] - p0[0]) * t, p0[1] + (p1[1] - p0[1]) * t];
+        let clamp = |p: [f32; 2]| [p[0].clamp(0., width), p[1]];
+        for piece in ts.windows(2) {
+            if piece[0] < piece[1] {
+                self.add_clamped_line(clamp(lerp(piece[0])), clamp(lerp(piece[1])));
+            }
+        }
+    }
+
+    fn add_clamped_line(&mut self, p0: [f32; 2], p1: [f32; 2]) {
+        if p0[1] == p1[1] || !(p0[1].is_finite() && p1[1].is_finite()) {
+            return;
+        }
+        let (direction, p0, p1) = if p0[1] < p1[1] { (1., p0, p1) } else { (-1., p1, p0) };
+        let dxdy = (p1[0] - p0[0]) / (p1[1] - p0[1]);
+        let (width, stride) = (self.width as f32, self.stride());
+
+        let y_start = p0[1].max(0.);
+        let y_end = p1[1].min(self.height as f32);
+        let mut x = p0[0] + (y_start - p0[1]) * dxdy;
+        let mut y = y_start;
+        while y < y_end {
+            let row = y.floor();
+            let y_next = (row + 1.).min(y_end);
+            let dy = y_next - y;
+            let x_next = (x + dxdy * dy).clamp(0., width);
+            let d = dy * direction;
+
+            let start = row as usize * stride;
+            let acc = &mut self.accumulator[start..start + stride];
+            let (x0, x1) = if x < x_next { (x, x_next) } else { (x_next, x) };
+            let x0_floor = x0.floor();
+            let x0i = x0_floor as usize;
+            let x1_ceil = x1.ceil();
+            let x1i = x1_ceil as usize;
+            if x1i <= x0i + 1 {
+                // The line stays within one pixel, which is covered up to the line's average x
+                let x_mid = 0.5 * (x + x_next) - x0_floor;
+                acc[x0i] += d - d * x_mid;
+                acc[x0i + 1] += d * x_mid;
+            } else {
+                // The covered area grows quadratically in the first and last pixels, and linearly in between
+                let s = (x1 - x0).recip();
+                let x0_fract = x0 - x0_floor;
+                let a0 = 0.5 * s * (1. - x0_fract) * (1. - x0_fract);
+                let x1_fract = x1 - x1_ceil + 1.;
+                let a_end = 0.5 * s * x1_fract * x1_fract;
+                acc[x0i] += d * a0;
+                if x1i == x0i + 2 {
+                    acc[x0i + 1] += d * (1. - a0 - a_end);
+                } else {
+                    let a1 = s * (1.5 - x0_fract);
+                    acc[x0i + 1] += d * (a1 - a0);
+                    for a in &mut acc[x0i + 2..x1i - 1] {
+                        *a += d * s;
+                    }
+                    let a2 = a1 + (x1i - x0i - 3) as f32 * s;
+                    acc[x1i - 1] += d * (1. - a2 - a_end);
+                }
+                acc[x1i] += d * a_end;
+            }
+
+            x = x_next;
+            y = y_next;
+        }
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidFormat<'p, P> for CoverageBuilder<'p, P> {
+    type Builder = Self;
+
+    fn initialize(self, _polygon_list: &'p P) -> Result<Self::Builder, <Self::Builder as TrapezoidBuilder<'p, P>>::Error> {
+        Ok(self)
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidBuilder<'p, P> for CoverageBuilder<'p, P> {
+    type Output = Vec<u8>;
+    type Error = Infallible;
+
+    fn add_trapezoid(&mut self, corners: [[<P::Vertex as Vertex>::Coordinate; 2]; 4]) -> Result<(), Self::Error> {
+        let corners = corners.map(|c| self.apply(c.map(|c| c.to_f32().unwrap_or(f32::NAN))));
+        for i in 0..4 {
+            self.add_line(corners[i], corners[(i + 1) % 4]);
+        }
+        Ok(())
+    }
+
+    fn build(self) -> Result<Self::Output, Self::Error> {
+        let mut coverage = Vec::with_capacity(self.width * self.height);
+        for row in self.accumulator.chunks_exact(self.stride()) {
+            let mut sum = 0.;
+            for a in &row[..self.width] {
+                sum += a;
+                // The winding of the trapezoids depends on whether the transform mirrors them
+                coverage.push((sum.abs().min(1.) * 255. + 0.5) as u8);
+            }
+        }
+        Ok(coverage)
+    }
+
+    fn fail(self, _error: &TriangulationError<Self::Error, P::Index>) { }
+}
@@ -1,18 +1,29 @@
 use std::fmt;
 
-use crate::{Vertex, VertexIndex, idx::{Idx, IdxDisplay}, math::is_left_of_line, nexus::Nexus, Coords};
+use num_traits::{One, Zero};
+
+use crate::{Real, Vertex, VertexIndex, idx::{Column, Idx, IdxDisplay}, math::is_left_of_line, nexus::{Nexus, Nexuses}, Coords};
 
-#[derive(Clone)]
 pub(crate) struct Segment<V: Vertex, Index: VertexIndex> {
     ni_min: Idx<Nexus<V, Index>>,
     ni_max: Idx<Nexus<V, Index>>,
     c_min: Coords<V::Coordinate>,
     c_max: Coords<V::Coordinate>,
+    // Whether the polygon's vertex order goes from the min to the max nexus
+    forward: bool,
+}
+
+impl<V: Vertex, Index: VertexIndex> Clone for Segment<V, Index> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
+impl<V: Vertex, Index: VertexIndex> Copy for Segment<V, Index> { }
+
 impl<V: Vertex, Index: VertexIndex> fmt::Debug for Segment<V, Index> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Segment").field("ni_min", &self.ni_min).field("ni_max", &self.ni_max).field("c_min", &self.c_min).field("c_max", &self.c_max).finish()
+        f.debug_struct("Segment").field("ni_min", &self.ni_min).field("ni_max", &self.ni_max).field("c_min", &self.c_min).field("c_max", &self.c_max).field("forward", &self.forward).finish()
     }
 }
 
@@ -30,19 +41,154 @@ where V::Coordinate: std::fmt::Display {
 }
 
 impl<V: Vertex, Index: VertexIndex> Segment<V, Index> {
-    pub fn new(ni_min: Idx<Nexus<V, Index>>, ni_max: Idx<Nexus<V, Index>>, c_min: Coords<V::Coordinate>, c_max: Coords<V::Coordinate>) -> Self {
+    pub fn new(ni_min: Idx<Nexus<V, Index>>, ni_max: Idx<Nexus<V, Index>>, c_min: Coords<V::Coordinate>, c_max: Coords<V::Coordinate>, forward: bool) -> Self {
         Self {
             ni_min,
             ni_max,
             c_min,
             c_max,
+            forward,
         }
     }
 
     pub fn ni_min(&self) -> Idx<Nexus<V, Index>> { self.ni_min }
     pub fn ni_max(&self) -> Idx<Nexus<V, Index>> { self.ni_max }
 
+    /// The segment's nexuses, in the order of the polygon's vertices
+    #[allow(clippy::type_complexity)]
+    pub fn ni_ordered(&self) -> (Idx<Nexus<V, Index>>, Idx<Nexus<V, Index>>) {
+        if self.forward {
+            (self.ni_min, self.ni_max)
+        } else {
+            (self.ni_max, self.ni_min)
+        }
+    }
+
+    /// Whether `c` lies exactly on the segment
+    pub fn contains(&self, c: Coords<V::Coordinate>) -> bool {
+        let (c_min, c_max) = (self.c_min, self.c_max);
+        c_min <= c && c <= c_max && 
+            (c_max.x() - c_min.x()) * (c.y() - c_min.y()) == (c_max.y() - c_min.y()) * (c.x() - c_min.x())
+    }
+
+    /// The x coordinate where the segment crosses the horizontal line at `y`, clamped to the segment's endpoints
+    pub fn x_at(&self, y: V::Coordinate, x_near: V::Coordinate) -> V::Coordinate {
+        let (c_min, c_max) = (self.c_min, self.c_max);
+        if c_min.y() == c_max.y() {
+            // A horizontal segment crosses the line everywhere, so take the point nearest to `x_near`
+            x_near.max(c_min.x()).min(c_max.x())
+        } else if y <= c_min.y() {
+            // Interpolating would not always give the endpoints exactly
+            c_min.x()
+        } else if y >= c_max.y() {
+            c_max.x()
+        } else {
+            c_min.x() + (c_max.x() - c_min.x()) * (y - c_min.y()) / (c_max.y() - c_min.y())
+        }
+    }
+
+    /// The point on the segment nearest to `c`
+    pub fn closest_point(&self, c: Coords<V::Coordinate>) -> Coords<V::Coordinate> {
+        let (c_min, c_max) = (self.c_min, self.c_max);
+        let (dx, dy) = (c_max.x() - c_min.x(), c_max.y() - c_min.y());
+        let t = (((c.x() - c_min.x()) * dx + (c.y() - c_min.y()) * dy) / (dx * dx + dy * dy))
+            .max(V::Coordinate::zero())
+            .min(V::Coordinate::one());
+        Coords::new(c_min.x() + dx * t, c_min.y() + dy * t)
+    }
+
     pub fn is_on_left(&self, c: Coords<V::Coordinate>) -> bool {
         is_left_of_line(self.c_min, self.c_max, c)
     }
 }
+
+/// The segments of a trapezoidation, stored as a structure of arrays.
+///
+/// The endpoint coordinates aren't stored, they are read from the endpoint nexuses.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound = ""))]
+#[allow(clippy::type_complexity)]
+pub(crate) struct Segments<V: Vertex, Index: VertexIndex> {
+    nexuses: Column<Segment<V, Index>, [Idx<Nexus<V, Index>>; 2]>,
+    forward: Column<Segment<V, Index>, bool>,
+}
+
+impl<V: Vertex, Index: VertexIndex> Segments<V, Index> {
+    pub fn len(&self) -> usize {
+        self.forward.len()
+    }
+
+    #[inline]
+    pub fn get(&self, ns: &Nexuses<V, Index>, si: Idx<Segment<V, Index>>) -> Segment<V, Index> {
+        let [ni_min, ni_max] = self.nexuses[si];
+        Segment { ni_min, ni_max, c_min: ns.coords(ni_min), c_max: ns.coords(ni_max), forward: self.forward[si] }
+    }
+
+    /// The segment's nexuses, in the order of the polygon's vertices
+    #[allow(clippy::type_complexity)]
+    pub fn ni_ordered(&self, si: Idx<Segment<V, Index>>) -> (Idx<Nexus<V, Index>>, Idx<Nexus<V, Index>>) {
+        let [ni_min, ni_max] = self.nexuses[si];
+        if self.forward[si] {
+            (ni_min, ni_max)
+        } else {
+            (ni_max, ni_min)
+        }
+    }
+
+    pub fn push_get_index(&mut self, s: Segment<V, Index>) -> Idx<Segment<V, Index>> {
+        let si = Idx::new(self.len());
+        self.nexuses.push([s.ni_min, s.ni_max]);
+        self.forward.push(s.forward);
+        si
+    }
+
+    /// The nexuses at the ends of every segment, lowest first
+    #[cfg(feature = "serde")]
+    #[allow(clippy::type_complexity)]
+    pub fn iter_nexuses(&self) -> impl ExactSizeIterator<Item = [Idx<Nexus<V, Index>>; 2]> + '_ {
+        self.nexuses.iter().copied()
+    }
+
+    #[cfg(feature = "_debugging")]
+    pub fn iter_index(&self) -> impl ExactSizeIterator<Item = Idx<Segment<V, Index>>> {
+        (0..self.len()).map(Idx::new)
+    }
+
+    /// Whether every column has a value for each segment, which deserialized segments need not
+    #[cfg(feature = "serde")]
+    pub fn columns_match(&self) -> bool {
+        self.nexuses.len() == self.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.nexuses.clear();
+        self.forward.clear();
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.nexuses.reserve(additional);
+        self.forward.reserve(additional);
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.nexuses.shrink_to_fit();
+        self.forward.shrink_to_fit();
+    }
+
+    /// The bytes allocated for the segments
+    #[cfg(test)]
+    pub fn heap_bytes(&self) -> usize {
+        self.nexuses.heap_bytes() + self.forward.heap_bytes()
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> Default for Segments<V, Index> {
+    fn default() -> Self {
+        Self { nexuses: Column::new(), forward: Column::new() }
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> Clone for Segments<V, Index> {
+    fn clone(&self) -> Self {
+        Self { nexuses: self.nexuses.clone(), forward: self.forward.clone() }
+    }
+}
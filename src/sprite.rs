@@ -0,0 +1,337 @@
+//! Sprite mesh generation from texture alpha masks
+//!
+//! An [AlphaMask] is traced with marching squares to find the outlines of its opaque regions.
+//! The outlines are simplified to a pixel tolerance and triangulated into a [SpriteMesh],
+//! which covers only the visible pixels of the sprite instead of its full quad.
+//!
+//! ```
+//! # use triangulate::sprite::AlphaMask;
+//! // A 4x4 sprite with an opaque 2x2 center
+//! let alpha = [
+//!     0,   0,   0, 0,
+//!     0, 255, 255, 0,
+//!     0, 255, 255, 0,
+//!     0,   0,   0, 0,
+//! ];
+//! let mask = AlphaMask::new(&alpha, 4, 4);
+//! let mesh = mask.mesh(127, 0.5).expect("Triangulation failed");
+//! assert!(!mesh.indices.is_empty());
+//! ```
+
+use std::{collections::HashMap, convert::Infallible, error, fmt};
+
+use crate::{formats, ListFormat, PolygonList, TriangulationError};
+
+/// Describes an error which occurred while meshing an [AlphaMask]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SpriteError {
+    /// The outlines have more vertices than a [SpriteMesh]'s `u32` indices can address
+    TooManyVertices,
+    /// An error occurred while triangulating the outlines
+    TriangulationError(TriangulationError<Infallible, [usize; 2]>),
+}
+
+impl From<TriangulationError<Infallible, [usize; 2]>> for SpriteError {
+    fn from(error: TriangulationError<Infallible, [usize; 2]>) -> Self {
+        Self::TriangulationError(error)
+    }
+}
+
+impl error::Error for SpriteError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::TriangulationError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SpriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyVertices => write!(f, "Sprite outlines have more vertices than 32 bit indices can address"),
+            Self::TriangulationError(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+/// A single channel, row-major mask of pixel alpha values
+#[derive(Debug, Clone, Copy)]
+pub struct AlphaMask<'a> {
+    data: &'a [u8],
+    width: usize,
+    height: usize,
+}
+
+/// A closed outline traced from an [AlphaMask], in pixel coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contour {
+    /// The outline's vertices. The opaque region is always on the left side of the outline.
+    pub points: Vec<[f32; 2]>,
+    /// Whether this outline bounds a transparent hole within an opaque region
+    pub is_hole: bool,
+}
+
+/// An indexed triangle mesh generated by [AlphaMask::mesh]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpriteMesh {
+    /// Vertex positions, in pixel coordinates
+    pub positions: Vec<[f32; 2]>,
+    /// Texture coordinates of each vertex, in the range `[0, 1]`
+    pub uvs: Vec<[f32; 2]>,
+    /// Three indices into `positions` and `uvs` per triangle
+    pub indices: Vec<u32>,
+}
+
+impl<'a> AlphaMask<'a> {
+    /// Wrap `width * height` alpha values, stored row by row.
+    ///
+    /// # Panics
+    /// Panics if `data` does not contain exactly `width * height` values.
+    pub fn new(data: &'a [u8], width: usize, height: usize) -> Self {
+        assert_eq!(data.len(), width * height, "Alpha mask must contain width * height values");
+        Self { data, width, height }
+    }
+
+    /// The width of the mask in pixels
+    pub fn width(&self) -> usize { self.width }
+
+    /// The height of the mask in pixels
+    pub fn height(&self) -> usize { self.height }
+
+    // Pixels outside the mask are treated as fully transparent
+    fn alpha(&self, x: isize, y: isize) -> u8 {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            0
+        } else {
+            self.data[y as usize * self.width + x as usize]
+        }
+    }
+
+    /// Trace the outlines of all regions with alpha greater than `threshold`.
+    ///
+    /// Pixel `(x, y)` covers `[x, x + 1] × [y, y + 1]`, and outline vertices are interpolated
+    /// between pixel centers. Where only diagonally opposite pixels are opaque, they are joined
+    /// if the average alpha of the four pixels is above the threshold.
+    pub fn contours(&self, threshold: u8) -> Vec<Contour> {
+        let tracer = Tracer::new(self, threshold);
+        tracer.trace()
+    }
+
+    /// Trace, simplify and triangulate the regions with alpha greater than `threshold`.
+    ///
+    /// Outlines are simplified so that no removed vertex is further than `tolerance` pixels from the result.
+    /// Outlines which collapse during simplification are dropped.
+    /// A large `tolerance` relative to the size of features in the mask may cause outlines to cross,
+    /// which will fail triangulation.
+    /// Returns [SpriteError::TooManyVertices] if the simplified outlines can't be indexed by `u32`.
+    pub fn mesh(&self, threshold: u8, tolerance: f32) -> Result<SpriteMesh, SpriteError> {
+        let polygons: Vec<Vec<[f32; 2]>> = self.contours(threshold)
+            .into_iter()
+            .map(|contour| simplify(&contour.points, tolerance))
+            .filter(|points| points.len() >= 3)
+            .collect();
+
+        if polygons.is_empty() {
+            return Ok(SpriteMesh::default());
+        }
+
+        let mut offsets = Vec::with_capacity(polygons.len());
+        let mut positions = Vec::with_capacity(polygons.vertex_count());
+        for polygon in polygons.iter() {
+            offsets.push(positions.len());
+            positions.extend_from_slice(polygon);
+        }
+
+        let uvs = positions.iter()
+            .map(|[x, y]| [x / self.width as f32, y / self.height as f32])
+            .collect();
+
+        let mut triangles = Vec::<[usize; 2]>::new();
+        polygons.triangulate(formats::IndexedListFormat::new(&mut triangles).into_fan_format())?;
+        let indices = triangles.into_iter()
+            .map(|[polygon, vertex]| u32::try_from(offsets[polygon] + vertex).map_err(|_| SpriteError::TooManyVertices))
+            .collect::<Result<_, _>>()?;
+
+        Ok(SpriteMesh { positions, uvs, indices })
+    }
+}
+
+// Crossings are identified by the grid edge between two pixel centers they lie on.
+// The grid is padded by one pixel on each side so that every outline is closed.
+// Only edges the outlines cross are stored, so memory grows with the outlines' length rather than the mask's area.
+struct Tracer<'m, 'a> {
+    mask: &'m AlphaMask<'a>,
+    threshold: u8,
+    columns: usize,
+    rows: usize,
+    // For each crossing, the crossing that follows it along its outline
+    next: HashMap<usize, usize>,
+    points: HashMap<usize, [f32; 2]>,
+}
+
+impl<'m, 'a> Tracer<'m, 'a> {
+    fn new(mask: &'m AlphaMask<'a>, threshold: u8) -> Self {
+        let columns = mask.width + 2;
+        let rows = mask.height + 2;
+        let mut tracer = Self {
+            mask,
+            threshold,
+            columns,
+            rows,
+            next: HashMap::new(),
+            points: HashMap::new(),
+        };
+        tracer.march();
+        tracer
+    }
+
+    fn is_inside(&self, x: isize, y: isize) -> bool {
+        self.mask.alpha(x, y) > self.threshold
+    }
+
+    // Horizontal edges occupy even ids, vertical edges odd ids
+    fn edge_id(&self, x: isize, y: isize, vertical: bool) -> usize {
+        let column = (x + 1) as usize;
+        let row = (y + 1) as usize;
+        (row * self.columns + column) * 2 + vertical as usize
+    }
+
+    fn crossing(&self, (x0, y0): (isize, isize), (x1, y1): (isize, isize)) -> [f32; 2] {
+        let a0 = self.mask.alpha(x0, y0) as f32;
+        let a1 = self.mask.alpha(x1, y1) as f32;
+        let level = self.threshold as f32 + 0.5;
+        // Keep crossings away from pixel centers, so crossings on different edges never coincide
+        let t = ((level - a0) / (a1 - a0)).clamp(1. / 16., 15. / 16.);
+        [
+            x0 as f32 + 0.5 + t * (x1 - x0) as f32,
+            y0 as f32 + 0.5 + t * (y1 - y0) as f32,
+        ]
+    }
+
+    fn march(&mut self) {
+        for y in -1..(self.rows as isize - 2) {
+            for x in -1..(self.columns as isize - 2) {
+                // Cell corners and edges in counter-clockwise order, each edge starting at the corner of the same index
+                let corners = [(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)];
+                let edges = [
+                    self.edge_id(x, y, false),
+                    self.edge_id(x + 1, y, true),
+                    self.edge_id(x, y + 1, false),
+                    self.edge_id(x, y, true),
+                ];
+                let inside = corners.map(|(cx, cy)| self.is_inside(cx, cy));
+
+                // A crossing where the boundary leaves the opaque region (counter-clockwise) starts an outline segment,
+                // which ends at a crossing where the boundary re-enters it. That keeps the opaque region on the segment's left.
+                let exits: Vec<usize> = (0..4).filter(|&i| inside[i] && !inside[(i + 1) % 4]).collect();
+                let entries: Vec<usize> = (0..4).filter(|&i| !inside[i] && inside[(i + 1) % 4]).collect();
+
+                for &i in exits.iter().chain(entries.iter()) {
+                    let point = self.crossing(corners[i], corners[(i + 1) % 4]);
+                    self.points.insert(edges[i], point);
+                }
+
+                match (exits.as_slice(), entries.as_slice()) {
+                    ([exit], [entry]) => { self.next.insert(edges[*exit], edges[*entry]); }
+                    ([exit0, exit1], [_, _]) => {
+                        // Saddle: decide whether the opaque corners are joined through the cell's center
+                        let joined = corners.iter().map(|&(cx, cy)| self.mask.alpha(cx, cy) as u32).sum::<u32>() > (self.threshold as u32) * 4 + 2;
+                        for &exit in [exit0, exit1] {
+                            let entry = if joined { (exit + 1) % 4 } else { (exit + 3) % 4 };
+                            self.next.insert(edges[exit], edges[entry]);
+                        }
+                    }
+                    _ => { }
+                }
+            }
+        }
+    }
+
+    fn trace(mut self) -> Vec<Contour> {
+        // Start outlines in edge order, so the contours don't depend on the maps' iteration order
+        let mut starts: Vec<usize> = self.next.keys().copied().collect();
+        starts.sort_unstable();
+
+        let mut contours = Vec::new();
+        for start in starts {
+            if !self.next.contains_key(&start) {
+                continue;
+            }
+
+            let mut points = Vec::new();
+            let mut edge = start;
+            while let Some(next) = self.next.remove(&edge) {
+                points.push(self.points[&edge]);
+                edge = next;
+            }
+
+            let is_hole = signed_area(&points) < 0.;
+            contours.push(Contour { points, is_hole });
+        }
+        contours
+    }
+}
+
+fn signed_area(points: &[[f32; 2]]) -> f32 {
+    let mut area = 0.;
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        area += a[0] * b[1] - b[0] * a[1];
+    }
+    area / 2.
+}
+
+fn distance_to_segment(p: [f32; 2], a: [f32; 2], b: [f32; 2]) -> f32 {
+    let ab = [b[0] - a[0], b[1] - a[1]];
+    let ap = [p[0] - a[0], p[1] - a[1]];
+    let len2 = ab[0] * ab[0] + ab[1] * ab[1];
+    let t = if len2 > 0. { ((ap[0] * ab[0] + ap[1] * ab[1]) / len2).clamp(0., 1.) } else { 0. };
+    let d = [ap[0] - ab[0] * t, ap[1] - ab[1] * t];
+    (d[0] * d[0] + d[1] * d[1]).sqrt()
+}
+
+/// Simplify a closed outline with the Ramer–Douglas–Peucker algorithm,
+/// so that no removed point is further than `tolerance` from the result.
+pub fn simplify(points: &[[f32; 2]], tolerance: f32) -> Vec<[f32; 2]> {
+    fn simplify_open(points: &[[f32; 2]], tolerance: f32, keep: &mut [bool]) {
+        let last = points.len() - 1;
+        let mut farthest = (0, 0.);
+        for (i, p) in points.iter().enumerate().take(last).skip(1) {
+            let d = distance_to_segment(*p, points[0], points[last]);
+            if d > farthest.1 {
+                farthest = (i, d);
+            }
+        }
+        if farthest.1 > tolerance {
+            keep[farthest.0] = true;
+            simplify_open(&points[..=farthest.0], tolerance, &mut keep[..=farthest.0]);
+            simplify_open(&points[farthest.0..], tolerance, &mut keep[farthest.0..]);
+        }
+    }
+
+    if points.len() <= 3 {
+        return points.to_vec();
+    }
+
+    // Split the closed outline at the point furthest from the first point, and simplify both halves
+    let split = (1..points.len())
+        .max_by(|&i, &j| {
+            let di = distance_to_segment(points[i], points[0], points[0]);
+            let dj = distance_to_segment(points[j], points[0], points[0]);
+            di.total_cmp(&dj)
+        })
+        .unwrap_or(1);
+
+    let mut closed = points.to_vec();
+    closed.push(points[0]);
+    let mut keep = vec![false; closed.len()];
+    keep[0] = true;
+    keep[split] = true;
+    simplify_open(&closed[..=split], tolerance, &mut keep[..=split]);
+    simplify_open(&closed[split..], tolerance, &mut keep[split..]);
+
+    closed.pop();
+    closed.into_iter().zip(keep).filter_map(|(p, keep)| keep.then_some(p)).collect()
+}
@@ -0,0 +1,85 @@
+use crate::{FanFormat, FanResult, PolygonElement, PolygonList, earclip, fast_path, sweep};
+
+/// Inputs with up to this many vertices are ear clipped by [Strategy::Auto], as measured by the `strategy` benchmark
+const EAR_CLIP_MAX_VERTICES: usize = 8192;
+/// Inputs with more rings than this are trapezoidized by [Strategy::Auto], as holes are joined one at a time when ear clipping
+const EAR_CLIP_MAX_RINGS: usize = 32;
+
+/// The algorithm used by [PolygonList::triangulate_with_strategy]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Strategy {
+    /// Pick the fastest algorithm for the size of the input: [Strategy::EarClip] for inputs with few vertices and holes,
+    /// otherwise [Strategy::Seidel]. Either way, a single convex or y-monotone ring is triangulated directly
+    ///
+    /// Invalid input is handled by whichever algorithm is picked, so for inputs small enough to be ear clipped,
+    /// some of the input may be left out rather than reporting an error. Use [Strategy::Seidel] when invalid input must be reported.
+    #[default]
+    Auto,
+    /// Seidel's randomized algorithm, as used by [PolygonList::triangulate].
+    /// Expected O(*n* log\* *n*) time, with larger constant factors
+    Seidel,
+    /// Ear clipping, with vertices indexed by z-order for larger rings.
+    /// O(*n*²) time in the worst case, and O(*nh*) to join *h* holes to the rings around them, but very fast for small inputs.
+    ///
+    /// The triangulation doesn't depend on any seed. If the preconditions are violated, some of the input may be left out
+    /// rather than reporting an error.
+    EarClip,
+    /// A deterministic plane sweep, which splits the polygons into y-monotone pieces at their split and merge vertices.
+    /// O(*n* log *n*) time in the worst case.
+    ///
+    /// The triangulation doesn't depend on any seed, so the same polygons are always triangulated the same way.
+    Sweep,
+}
+
+impl Strategy {
+    /// The algorithm [Strategy::Auto] picks for an input with `vertex_count` vertices in `ring_count` rings
+    pub fn select(vertex_count: usize, ring_count: usize) -> Self {
+        if vertex_count <= EAR_CLIP_MAX_VERTICES && ring_count <= EAR_CLIP_MAX_RINGS {
+            Self::EarClip
+        } else {
+            Self::Seidel
+        }
+    }
+}
+
+pub(crate) fn triangulate<'p, P, FB>(polygon_list: &'p P, format: FB, strategy: Strategy) -> FanResult<'p, P, FB>
+where P: PolygonList<'p> + ?Sized,
+      FB: FanFormat<'p, P> {
+    let format = match strategy {
+        Strategy::Auto => match fast_path::try_triangulate(polygon_list, format) {
+            Ok(result) => return result,
+            Err(format) => format,
+        },
+        _ => format,
+    };
+
+    let strategy = match strategy {
+        Strategy::Auto => {
+            let vertex_count = polygon_list.vertex_count();
+            if vertex_count > EAR_CLIP_MAX_VERTICES {
+                Strategy::Seidel
+            } else {
+                let mut ring_count = 0;
+                let mut in_ring = false;
+                for element in polygon_list.iter_indices() {
+                    match element.into() {
+                        PolygonElement::ContinuePolygon(_) => if !in_ring {
+                            ring_count += 1;
+                            in_ring = true;
+                        },
+                        PolygonElement::NewPolygon => in_ring = false,
+                    }
+                }
+                Strategy::select(vertex_count, ring_count)
+            }
+        },
+        strategy => strategy,
+    };
+
+    match strategy {
+        Strategy::EarClip => earclip::triangulate(polygon_list, format),
+        Strategy::Sweep => sweep::triangulate(polygon_list, format),
+        _ => polygon_list.triangulate(format),
+    }
+}
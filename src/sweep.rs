@@ -0,0 +1,332 @@
+//! Deterministic triangulation by a plane sweep, which partitions the polygons into y-monotone pieces
+//! with the classic split and merge vertex diagonals, then triangulates each piece as the fast path does.
+//!
+//! Vertices are visited from top to bottom, in the same order as [Coords] compare. The edges crossing the sweep line
+//! are kept in a [BTreeMap] ordered from left to right, each recording whether the polygons' interior lies to its right,
+//! so rings may be wound either way.
+
+use std::{cmp::Ordering, collections::BTreeMap, mem};
+
+use crate::{Coords, FanError, FanFormat, FanResult, PolygonElement, PolygonList, PolygonListExt, Real, TrapezoidationError, TriangulationError, Vertex, VertexIndex, fan_builder_state::FanBuilderState, fast_path, math::is_left_of_line};
+
+pub(crate) fn triangulate<'p, P, FB>(polygon_list: &'p P, format: FB) -> FanResult<'p, P, FB>
+where P: PolygonList<'p> + ?Sized,
+      FB: FanFormat<'p, P> {
+    let ps = PolygonListExt::new(polygon_list);
+    let mut fbs = FanBuilderState::<P, FB>::Uninitialized(format);
+    let result = Sweep::new(ps).and_then(|mut sweep| {
+        sweep.partition()?;
+        sweep.build_fans(ps, &mut fbs)
+    });
+    fbs.complete(result.map_err(|error| error.diagnose(polygon_list, None)))
+}
+
+struct SweepVertex<Index, C: Real> {
+    index: Index,
+    coords: Coords<C>,
+    // Positions of the neighbouring vertices in the ring
+    prev: usize,
+    next: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct EdgeState {
+    interior_right: bool,
+    // The lowest vertex seen so far which can see this edge, if the interior is to its right
+    helper: usize,
+    helper_is_merge: bool,
+}
+
+/// An edge crossing the sweep line, or a point on it when `edge` is `usize::MAX`.
+/// Edges in the status never cross, so comparing them anywhere along their common span orders them from left to right
+#[derive(Debug, Clone, Copy)]
+struct EdgeKey<C: Real> {
+    upper: Coords<C>,
+    lower: Coords<C>,
+    edge: usize,
+}
+
+impl<C: Real> EdgeKey<C> {
+    /// Whether `c` lies left of this edge
+    fn side_of(&self, c: Coords<C>) -> Ordering {
+        if is_left_of_line(self.lower, self.upper, c) {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }
+    }
+}
+
+impl<C: Real> PartialEq for EdgeKey<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: Real> Eq for EdgeKey<C> { }
+
+impl<C: Real> PartialOrd for EdgeKey<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Real> Ord for EdgeKey<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.edge == other.edge {
+            return Ordering::Equal;
+        }
+        // The lower of the two upper endpoints lies within the span of both edges
+        match self.upper.partial_cmp(&other.upper) {
+            Some(Ordering::Less) => other.side_of(self.upper),
+            Some(Ordering::Greater) => self.side_of(other.upper).reverse(),
+            // Edges leaving the same vertex are ordered by their higher lower endpoint
+            _ => if self.lower > other.lower {
+                other.side_of(self.lower)
+            } else {
+                self.side_of(other.lower).reverse()
+            },
+        }
+    }
+}
+
+struct Sweep<Index, C: Real> {
+    vertices: Vec<SweepVertex<Index, C>>,
+    status: BTreeMap<EdgeKey<C>, EdgeState>,
+    // Whether the interior lies to the right of each edge, indexed by the position of the edge's first vertex
+    interior_right: Vec<bool>,
+    diagonals: Vec<[usize; 2]>,
+}
+
+impl<Index: VertexIndex, C: Real> Sweep<Index, C> {
+    fn new<'p, P, E>(ps: PolygonListExt<'p, P>) -> Result<Self, TriangulationError<E, Index>>
+    where P: PolygonList<'p, Index = Index, Vertex: Vertex<Coordinate = C>> + ?Sized,
+          E: std::error::Error {
+        let mut vertices: Vec<SweepVertex<Index, C>> = Vec::with_capacity(ps.vertex_count());
+        let mut start = 0;
+        let mut ring = 0;
+        for element in ps.iter_polygon_vertices() {
+            match element.into() {
+                PolygonElement::ContinuePolygon(index) => {
+                    let coords = ps[index.clone()].coords();
+                    if coords.x() != coords.x() || coords.y() != coords.y() {
+                        return Err(TriangulationError::internal("Vertex coordinates must not be NaN"));
+                    }
+                    // Repeated vertices add no area, so they are dropped rather than compared with themselves
+                    if vertices.len() > start && vertices[vertices.len() - 1].coords == coords {
+                        continue;
+                    }
+                    let position = vertices.len();
+                    vertices.push(SweepVertex { index, coords, prev: position.wrapping_sub(1), next: position + 1 });
+                },
+                PolygonElement::NewPolygon => {
+                    let next = close_ring(&mut vertices, start, ring)?;
+                    if next > start {
+                        ring += 1;
+                    }
+                    start = next;
+                },
+            }
+        }
+        close_ring(&mut vertices, start, ring)?;
+
+        let len = vertices.len();
+        Ok(Self { vertices, status: BTreeMap::new(), interior_right: vec![false; len], diagonals: Vec::new() })
+    }
+
+    fn key(&self, edge: usize) -> EdgeKey<C> {
+        let (a, b) = (self.vertices[edge].coords, self.vertices[self.vertices[edge].next].coords);
+        let (upper, lower) = if a > b { (a, b) } else { (b, a) };
+        EdgeKey { upper, lower, edge }
+    }
+
+    fn insert(&mut self, edge: usize, interior_right: bool, helper: usize) {
+        self.interior_right[edge] = interior_right;
+        self.status.insert(self.key(edge), EdgeState { interior_right, helper, helper_is_merge: false });
+    }
+
+    fn remove<E: std::error::Error>(&mut self, edge: usize) -> Result<EdgeState, TriangulationError<E, Index>> {
+        self.status.remove(&self.key(edge)).ok_or_else(|| TriangulationError::internal("An edge ending at the sweep line must cross it"))
+    }
+
+    /// The nearest edge left of `v`, if it has the interior to its right
+    fn left_of(&mut self, v: usize) -> Option<&mut EdgeState> {
+        let c = self.vertices[v].coords;
+        self.status.range_mut(..EdgeKey { upper: c, lower: c, edge: usize::MAX }).next_back()
+            .map(|(_, state)| state)
+            .filter(|state| state.interior_right)
+    }
+
+    /// The nearest edge left of `v`, which lies inside the polygons
+    fn left_of_inside<E: std::error::Error>(&mut self, v: usize) -> Result<&mut EdgeState, TriangulationError<E, Index>> {
+        self.left_of(v).ok_or_else(|| TriangulationError::internal("A vertex inside the polygons must have an edge to its left"))
+    }
+
+    fn connect_merge_helper(&mut self, v: usize, state: EdgeState) {
+        if state.helper_is_merge {
+            self.diagonals.push([v, state.helper]);
+        }
+    }
+
+    /// Sweep from top to bottom, adding diagonals which split the polygons into y-monotone pieces
+    fn partition<E: std::error::Error>(&mut self) -> Result<(), TriangulationError<E, Index>> {
+        let mut order: Vec<usize> = (0..self.vertices.len()).collect();
+        order.sort_by(|&a, &b| self.vertices[b].coords.partial_cmp(&self.vertices[a].coords).unwrap_or(Ordering::Equal));
+
+        for v in order {
+            let SweepVertex { coords, prev, next, .. } = self.vertices[v];
+            let (prev_below, next_below) = (self.vertices[prev].coords < coords, self.vertices[next].coords < coords);
+            // The edge arriving at v is identified by the previous vertex, and the edge leaving it by v itself
+            let (edge_in, edge_out) = (prev, v);
+
+            match (prev_below, next_below) {
+                // Start or split vertex
+                (true, true) => {
+                    let (left, right) = if self.key(edge_in) < self.key(edge_out) { (edge_in, edge_out) } else { (edge_out, edge_in) };
+                    let helper = self.left_of(v).map(|state| {
+                        state.helper_is_merge = false;
+                        mem::replace(&mut state.helper, v)
+                    });
+                    let inside = helper.is_some();
+                    if let Some(helper) = helper {
+                        self.diagonals.push([v, helper]);
+                    }
+                    self.insert(left, !inside, v);
+                    self.insert(right, inside, v);
+                },
+                // End or merge vertex
+                (false, false) => {
+                    let (left, right) = if self.key(edge_in) < self.key(edge_out) { (edge_in, edge_out) } else { (edge_out, edge_in) };
+                    let (left, right) = (self.remove(left)?, self.remove(right)?);
+                    if left.interior_right {
+                        self.connect_merge_helper(v, left);
+                    } else {
+                        self.connect_merge_helper(v, right);
+                        let state = self.left_of_inside(v)?;
+                        let previous = *state;
+                        *state = EdgeState { helper: v, helper_is_merge: true, ..previous };
+                        self.connect_merge_helper(v, previous);
+                    }
+                },
+                // Regular vertex
+                (below_in, _) => {
+                    let (up, down) = if below_in { (edge_out, edge_in) } else { (edge_in, edge_out) };
+                    let state = self.remove(up)?;
+                    if state.interior_right {
+                        self.connect_merge_helper(v, state);
+                    } else {
+                        let state = self.left_of_inside(v)?;
+                        let previous = *state;
+                        *state = EdgeState { helper: v, helper_is_merge: false, ..previous };
+                        self.connect_merge_helper(v, previous);
+                    }
+                    self.insert(down, state.interior_right, v);
+                },
+            }
+        }
+
+        if self.status.is_empty() {
+            Ok(())
+        } else {
+            Err(TriangulationError::internal("Every edge must end above the bottom of the polygons"))
+        }
+    }
+
+    /// Walk the boundary of each y-monotone piece, and triangulate it
+    fn build_fans<'p, P, FB>(self, ps: PolygonListExt<'p, P>, fbs: &mut FanBuilderState<'p, P, FB>) -> Result<(), FanError<'p, P, FB>>
+    where P: PolygonList<'p, Index = Index, Vertex: Vertex<Coordinate = C>> + ?Sized,
+          FB: FanFormat<'p, P> {
+        let len = self.vertices.len();
+
+        // Half edges with the interior on their left: each ring edge in one direction, and each diagonal in both.
+        // They are grouped by their first vertex and sorted counterclockwise around it
+        let mut starts = vec![0; len + 1];
+        let half_edges = (0..len).map(|v| {
+            let next = self.vertices[v].next;
+            // Facing down an edge, its right side in x is on the left
+            let downward = self.vertices[next].coords < self.vertices[v].coords;
+            if downward == self.interior_right[v] { [v, next] } else { [next, v] }
+        }).chain(self.diagonals.iter().flat_map(|&[a, b]| [[a, b], [b, a]]));
+        let half_edges: Vec<[usize; 2]> = half_edges.collect();
+        for &[from, _] in &half_edges {
+            starts[from + 1] += 1;
+        }
+        for v in 0..len {
+            starts[v + 1] += starts[v];
+        }
+        let mut targets = vec![0; half_edges.len()];
+        let mut filled = starts.clone();
+        for &[from, to] in &half_edges {
+            targets[filled[from]] = to;
+            filled[from] += 1;
+        }
+        let direction = |from: usize, to: usize| {
+            let (a, b) = (self.vertices[from].coords, self.vertices[to].coords);
+            [b.x() - a.x(), b.y() - a.y()]
+        };
+        for v in 0..len {
+            targets[starts[v]..starts[v + 1]].sort_by(|&a, &b| compare_angles(direction(v, a), direction(v, b)));
+        }
+
+        let mut visited = vec![false; targets.len()];
+        let mut ring = fast_path::Ring::new();
+        for first in 0..targets.len() {
+            if visited[first] {
+                continue;
+            }
+            ring.clear();
+            let mut from = starts.partition_point(|&start| start <= first) - 1;
+            let mut half_edge = first;
+            while !visited[half_edge] {
+                visited[half_edge] = true;
+                ring.push((self.vertices[from].index.clone(), self.vertices[from].coords));
+
+                // Continue along the next half edge clockwise from the way we came in
+                let to = targets[half_edge];
+                let back = direction(to, from);
+                let around = &targets[starts[to]..starts[to + 1]];
+                let after = around.partition_point(|&t| compare_angles(direction(to, t), back) == Ordering::Less);
+                half_edge = starts[to] + if after == 0 { around.len() - 1 } else { after - 1 };
+                from = to;
+            }
+            if ring.len() < 3 {
+                return Err(TriangulationError::internal("A monotone piece must have at least 3 vertices"));
+            }
+
+            let top = (0..ring.len()).fold(0, |top, i| if ring[i].1 > ring[top].1 { i } else { top });
+            let merged = fast_path::merge_chains(&ring, top).ok_or_else(|| TriangulationError::internal("Monotone pieces must not repeat vertices"))?;
+            fast_path::build_monotone_fans(&ring, &merged, ps, fbs)?;
+        }
+        Ok(())
+    }
+}
+
+/// Order directions counterclockwise, starting from the positive x axis
+fn compare_angles<C: Real>(a: [C; 2], b: [C; 2]) -> Ordering {
+    let half = |d: [C; 2]| d[1] < C::zero() || (d[1] == C::zero() && d[0] < C::zero());
+    match (half(a), half(b)) {
+        (false, true) => Ordering::Less,
+        (true, false) => Ordering::Greater,
+        _ => (b[0] * a[1]).partial_cmp(&(a[0] * b[1])).unwrap_or(Ordering::Equal),
+    }
+}
+
+/// Link the `ring`th ring, of vertices from `start` to the end of `vertices`, returning the start of the next ring
+fn close_ring<Index: Clone, C: Real, E: std::error::Error>(vertices: &mut Vec<SweepVertex<Index, C>>, start: usize, ring: usize) -> Result<usize, TriangulationError<E, Index>> {
+    if vertices.len() > start + 1 && vertices[start].coords == vertices[vertices.len() - 1].coords {
+        vertices.pop();
+    }
+    match vertices.len() - start {
+        0 => Ok(start),
+        1 | 2 => {
+            let vertices = vertices[start..].iter().map(|v| v.index.clone()).collect();
+            Err(TriangulationError::TrapezoidationError(TrapezoidationError::ShortRing { ring, vertices }))
+        },
+        _ => {
+            let last = vertices.len() - 1;
+            vertices[start].prev = last;
+            vertices[last].next = start;
+            Ok(vertices.len())
+        },
+    }
+}
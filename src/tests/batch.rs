@@ -0,0 +1,68 @@
+use crate::{Batch, ListFormat, PolygonList, TrapezoidationError, TriangulationError, formats::{DeindexedListFormat, IndexedListFormat}};
+
+use super::util;
+
+#[test]
+fn batch_matches_items() {
+    let seed = 5;
+    let items = [util::load_country("malta.txt"), util::load_country("belgium.txt"), util::load_country("croatia.txt"), util::load_country("malta.txt")];
+    let batch = Batch::new(&items);
+    assert_eq!(batch.len(), items.len());
+
+    let (triangles, batch_items) = batch.triangulate_items_with_seed(IndexedListFormat::new(Vec::<[usize; 3]>::new()), seed).expect("Triangulation failed");
+    let mut vertex_start = 0;
+    for (i, (item, batch_item)) in items.iter().zip(&batch_items).enumerate() {
+        assert!(batch_item.error.is_none());
+        let vertex_count = item.iter().map(Vec::len).sum::<usize>();
+        assert_eq!(batch_item.vertices, vertex_start..vertex_start + vertex_count);
+        vertex_start += vertex_count;
+
+        // Batch indices follow the concatenated rings
+        let expected = item.triangulate_with_seed(IndexedListFormat::new(Vec::<[[usize; 2]; 3]>::new()).into_fan_format(), seed).expect("Triangulation failed");
+        let actual: Vec<_> = triangles[batch_item.triangles.clone()].iter()
+            .map(|t| t.map(|index| {
+                let (item, index) = batch.item_index(index);
+                assert_eq!(item, i);
+                index
+            }))
+            .collect();
+        assert_eq!(actual, expected, "item {} triangulated differently", i);
+    }
+    assert_eq!(batch_items.last().unwrap().triangles.end, triangles.len());
+}
+
+#[test]
+fn batch_skips_failed_items() {
+    let square = vec![vec![[0f32, 0.], [1., 0.], [1., 1.], [0., 1.]]];
+    let line = vec![vec![[0f32, 0.], [1., 1.]]];
+    let empty = vec![];
+    let batch = Batch::new([&square, &line, &empty, &square]);
+
+    let mut triangles = vec![[9usize; 3]];
+    let (_, items) = batch.triangulate_items(IndexedListFormat::new(&mut triangles)).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 5);
+    assert_eq!(triangles[0], [9; 3]);
+    let ranges: Vec<_> = items.iter().map(|item| (item.vertices.clone(), item.triangles.clone())).collect();
+    assert_eq!(ranges, vec![(0..4, 0..2), (4..6, 2..2), (6..6, 2..2), (6..10, 2..4)]);
+    assert!(matches!(items[1].error, Some(TriangulationError::TrapezoidationError(TrapezoidationError::ShortRing { ring: 0, ref vertices })) if *vertices == [4, 5]));
+    assert!(matches!(items[2].error, Some(TriangulationError::NoVertices)));
+
+    // Without any triangles, nothing is output
+    let batch = Batch::new([&line, &empty]);
+    let result = batch.triangulate_items(IndexedListFormat::new(&mut triangles));
+    assert!(matches!(result, Err(TriangulationError::NoVertices)));
+    assert_eq!(triangles.len(), 5);
+}
+
+#[test]
+fn batch_deindexed() {
+    let square = vec![vec![[0f32, 0.], [1., 0.], [1., 1.], [0., 1.]]];
+    let shifted = vec![vec![[5f32, 0.], [6., 0.], [6., 1.], [5., 1.]]];
+    let batch = Batch::new([&square, &shifted]);
+
+    let (indexed, _) = batch.triangulate_items_with_seed(IndexedListFormat::new(Vec::<[usize; 3]>::new()), 0).expect("Triangulation failed");
+    let (deindexed, items) = batch.triangulate_items_with_seed(DeindexedListFormat::new(Vec::<[[f32; 2]; 3]>::new()), 0).expect("Triangulation failed");
+    let expected: Vec<_> = indexed.iter().map(|t| t.map(|index| *batch.get_vertex(index))).collect();
+    assert_eq!(deindexed, expected);
+    assert!(deindexed[items[1].triangles.clone()].iter().flatten().all(|[x, _]| *x >= 5.));
+}
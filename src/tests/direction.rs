@@ -0,0 +1,97 @@
+use crate::{FanFormat, ListFormat, Polygon, PolygonList, SweepDirection, TriangulationError, formats::{DeindexedListFormat, IndexedFanFormat, IndexedListFormat}};
+
+use super::util;
+
+fn signed_area([a, b, c]: [[f32; 2]; 3]) -> f64 {
+    let [a, b, c] = [a, b, c].map(|v| v.map(f64::from));
+    0.5 * ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]))
+}
+
+const DIRECTIONS: [SweepDirection; 4] = [SweepDirection::Y, SweepDirection::X, SweepDirection::Vector([1., 1.]), SweepDirection::Vector([-0.3, 2.])];
+
+// Every direction covers the same area with the same number of triangles, wound as the format requests
+fn check(polygon_list: &Vec<Vec<[f32; 2]>>) {
+    let expected = polygon_list.triangulate(DeindexedListFormat::new(Vec::<[[f32; 2]; 3]>::new()).into_fan_format()).expect("Triangulation failed");
+    let expected_area = expected.iter().copied().map(signed_area).sum::<f64>();
+    for direction in DIRECTIONS {
+        let triangles = polygon_list.triangulate_with_direction(DeindexedListFormat::new(Vec::<[[f32; 2]; 3]>::new()).into_fan_format(), direction).expect("Triangulation failed");
+        assert_eq!(triangles.len(), expected.len(), "{:?}", direction);
+        let areas: Vec<_> = triangles.iter().copied().map(signed_area).collect();
+        assert!(areas.iter().all(|&area| area >= 0.), "{:?} triangles must be counterclockwise", direction);
+        let area = areas.iter().sum::<f64>();
+        assert!((area - expected_area).abs() < 1e-6 * expected_area, "{:?}: {} != {}", direction, area, expected_area);
+
+        let reversed = polygon_list.triangulate_with_direction(DeindexedListFormat::new(Vec::<[[f32; 2]; 3]>::new()).into_fan_format().reverse_winding(), direction).expect("Triangulation failed");
+        assert!(reversed.iter().copied().all(|t| signed_area(t) <= 0.), "{:?} triangles must be clockwise", direction);
+    }
+}
+
+#[test]
+fn direction_shapes() {
+    for polygon in util::polygon::all() {
+        check(&vec![polygon]);
+    }
+    // Long horizontal runs, like text on a baseline
+    let mut comb = vec![[0f32, 0.], [40., 0.], [40., 1.]];
+    for i in (0..10).rev() {
+        let x = i as f32 * 4.;
+        comb.extend([[x + 3., 1.], [x + 3., 3.], [x + 1., 3.], [x + 1., 1.]]);
+    }
+    comb.push([0., 1.]);
+    check(&vec![comb]);
+}
+
+#[test]
+fn direction_countries() {
+    for name in ["malta.txt", "croatia.txt"] {
+        check(&util::load_polygon_list(util::countries_path().join(name).to_str().unwrap()).unwrap());
+    }
+}
+
+#[test]
+fn direction_fast_path() {
+    // Convex rings are recognized along any direction, and output as a single fan
+    let diamond = vec![[0f32, 1.], [-1., 0.], [0., -1.], [1., 0.]];
+    for direction in DIRECTIONS {
+        let fans = diamond.triangulate_with_direction(IndexedFanFormat::new(Vec::<Vec<usize>>::new()), direction).expect("Triangulation failed");
+        assert_eq!(fans, vec![vec![0, 1, 2, 3]]);
+    }
+
+    // Both chains zigzag along y, but are monotone along x
+    let strip = vec![[0f32, 0.], [1., 2.], [2., 0.], [3., 2.], [4., 0.], [4., 3.], [3., 5.], [2., 3.], [1., 5.], [0., 3.]];
+    let triangles = strip.triangulate_with_direction(IndexedListFormat::new(Vec::<[usize; 3]>::new()).into_fan_format(), SweepDirection::X).expect("Triangulation failed");
+    assert_eq!(triangles.len(), strip.len() - 2);
+}
+
+#[test]
+fn direction_invalid() {
+    let square = vec![[0f32, 0.], [1., 0.], [1., 1.], [0., 1.]];
+    for vector in [[0., 0.], [f64::NAN, 1.], [1., f64::INFINITY]] {
+        let mut triangles = vec![[9; 3]];
+        let result = square.triangulate_with_direction(IndexedListFormat::new(&mut triangles).into_fan_format(), SweepDirection::Vector(vector));
+        assert!(matches!(result, Err(TriangulationError::InvalidDirection)), "{:?} was accepted", vector);
+        assert_eq!(triangles, vec![[9; 3]]);
+    }
+
+    // Only the direction matters, not the length
+    for vector in [[1e300, 1e300], [1e-300, 1e-300]] {
+        let triangles = square.triangulate_with_direction(IndexedListFormat::new(Vec::<[usize; 3]>::new()).into_fan_format(), SweepDirection::Vector(vector)).expect("Triangulation failed");
+        assert_eq!(triangles.len(), 2);
+    }
+}
+
+#[test]
+fn direction_axes_exact() {
+    // The hole's lowest vertex is barely above the outer ring's bottom edge, which rounding could move it onto
+    let polygon_list = vec![
+        vec![[0f32, 0.], [4., 0.], [4., 4.], [0., 4.]],
+        vec![[1f32, 2.], [3., 2.], [2., f32::MIN_POSITIVE]],
+    ];
+    let expected = polygon_list.triangulate(DeindexedListFormat::new(Vec::<[[f32; 2]; 3]>::new()).into_fan_format()).expect("Triangulation failed");
+    let expected_area = expected.iter().copied().map(signed_area).sum::<f64>();
+    for vector in [[1., 0.], [-2., 0.], [0., 3.], [0., -1e-3]] {
+        let triangles = polygon_list.triangulate_with_direction(DeindexedListFormat::new(Vec::<[[f32; 2]; 3]>::new()).into_fan_format(), SweepDirection::Vector(vector)).expect("Triangulation failed");
+        assert_eq!(triangles.len(), expected.len(), "{:?}", vector);
+        assert_eq!(triangles.iter().copied().map(signed_area).sum::<f64>(), expected_area, "{:?}", vector);
+    }
+}
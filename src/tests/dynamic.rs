@@ -0,0 +1,150 @@
+use std::collections::{HashMap, HashSet};
+
+use rand::{Rng, SeedableRng};
+
+use crate::{DynamicTriangulation, TrapezoidationError, TriangulationError};
+
+fn area(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])) / 2.
+}
+
+// The triangles must exactly cover the rings, with consistent adjacency
+fn check(triangulation: &DynamicTriangulation<f64>) {
+    let rings = triangulation.rings();
+    let position = |[ring, i]: [usize; 2]| rings[ring][i];
+    let is_ring_edge = |a: [usize; 2], b: [usize; 2]| a[0] == b[0] && (b[1] == (a[1] + 1) % rings[a[0]].len() || a[1] == (b[1] + 1) % rings[a[0]].len());
+
+    let mut triangle_area = 0.;
+    let mut edges = HashSet::new();
+    for (id, t) in triangulation.triangles() {
+        let a = area(position(t[0]), position(t[1]), position(t[2]));
+        assert!(a > 0., "Triangle {:?} is not counterclockwise", t);
+        triangle_area += a;
+
+        let neighbours = triangulation.neighbours(id).unwrap();
+        for corner in 0..3 {
+            let (a, b) = (t[corner], t[(corner + 1) % 3]);
+            assert!(edges.insert((a, b)), "Edge {:?} is used twice", (a, b));
+            match neighbours[corner] {
+                Some(neighbour) => {
+                    let n = triangulation.triangle(neighbour).unwrap();
+                    let back = (0..3).find(|&c| n[c] == b && n[(c + 1) % 3] == a).expect("Neighbour does not share the edge");
+                    assert_eq!(triangulation.neighbours(neighbour).unwrap()[back], Some(id));
+                }
+                None => assert!(is_ring_edge(a, b), "Edge {:?} has no neighbour", (a, b)),
+            }
+        }
+    }
+
+    let ring_area: f64 = rings.iter()
+        .map(|ring| (0..ring.len()).map(|i| area([0., 0.], ring[i], ring[(i + 1) % ring.len()])).sum::<f64>())
+        .sum();
+    assert!((triangle_area - ring_area).abs() < 1e-6 * ring_area, "Triangles cover {}, rings cover {}", triangle_area, ring_area);
+}
+
+// Whether moving the vertex would make its ring edges touch any other ring edge
+fn crosses(rings: &[Vec<[f64; 2]>], [ring, i]: [usize; 2], position: [f64; 2]) -> bool {
+    let len = rings[ring].len();
+    let neighbours = [rings[ring][(i + len - 1) % len], rings[ring][(i + 1) % len]];
+    let on_segment = |p: [f64; 2], a: [f64; 2], b: [f64; 2]| area(a, b, p) == 0.
+        && a[0].min(b[0]) <= p[0] && p[0] <= a[0].max(b[0]) && a[1].min(b[1]) <= p[1] && p[1] <= a[1].max(b[1]);
+    let touch = |a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]| {
+        let (d1, d2, d3, d4) = (area(a, b, c), area(a, b, d), area(c, d, a), area(c, d, b));
+        (d1 * d2 < 0. && d3 * d4 < 0.) || on_segment(c, a, b) || on_segment(d, a, b) || on_segment(a, c, d) || on_segment(b, c, d)
+    };
+    rings.iter().enumerate().any(|(r, vertices)| (0..vertices.len()).any(|j| {
+        let k = (j + 1) % vertices.len();
+        let (c, d) = (vertices[j], vertices[k]);
+        neighbours.iter().enumerate().any(|(side, &n)| {
+            // Skip the edges which share an endpoint with this new edge
+            let shared = r == ring && ([j, k].contains(&i) || [j, k].contains(&[(i + len - 1) % len, (i + 1) % len][side]));
+            !shared && touch(position, n, c, d)
+        })
+    }))
+}
+
+#[test]
+fn dynamic_random_moves() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    let outer: Vec<[f64; 2]> = (0..32).map(|i| {
+        let (y, x) = (std::f64::consts::TAU * i as f64 / 32.).sin_cos();
+        [x * 100., y * 100.]
+    }).collect();
+    let hole = vec![[-20., -20.], [-20., 20.], [20., 20.], [20., -20.]];
+    let mut triangulation = DynamicTriangulation::with_seed(vec![outer, hole], 0).unwrap();
+    check(&triangulation);
+
+    let (mut moves, mut local) = (0, 0);
+    for _ in 0..500 {
+        let ring = rng.gen_range(0..2);
+        let index = [ring, rng.gen_range(0..triangulation.rings()[ring].len())];
+        let old = triangulation.rings()[ring][index[1]];
+        let position = [old[0] + rng.gen_range(-5.0..5.0), old[1] + rng.gen_range(-5.0..5.0)];
+        if crosses(triangulation.rings(), index, position) {
+            continue;
+        }
+        let before: HashMap<_, _> = triangulation.triangles().map(|(id, t)| (id.index(), t)).collect();
+        let change = triangulation.move_vertex(index, position).expect("Valid move failed");
+        moves += 1;
+        assert_eq!(triangulation.rings()[ring][index[1]], position);
+        check(&triangulation);
+
+        assert!(change.added.iter().all(|&id| triangulation.triangle(id).is_some()));
+        assert!(change.removed.iter().all(|id| triangulation.triangle(*id).is_none() || change.added.contains(id)));
+        if !change.rebuilt {
+            local += 1;
+            // A local change only retriangulates the region covered by the removed triangles
+            let corners: HashSet<_> = change.removed.iter().flat_map(|id| before[&id.index()]).collect();
+            assert!(change.added.iter().all(|&id| triangulation.triangle(id).unwrap().iter().all(|v| corners.contains(v))));
+        }
+    }
+    assert!(local * 5 > moves * 4, "Only {} of {} moves were local", local, moves);
+}
+
+#[test]
+fn dynamic_rebuild() {
+    // The moved vertex crosses the square's only diagonal, so the triangles around it cannot be reused
+    let square = vec![vec![[0., 0.], [10., 0.], [10., 10.], [0., 10.]]];
+    let mut triangulation = DynamicTriangulation::with_seed(square, 0).unwrap();
+    let change = triangulation.move_vertex([0, 2], [2., 7.]).unwrap();
+    assert!(change.rebuilt);
+    assert_eq!((change.removed.len(), change.added.len()), (2, 2));
+    check(&triangulation);
+}
+
+#[test]
+fn dynamic_invalid_move() {
+    let rings = vec![
+        vec![[0., 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[2., 2.], [2., 8.], [8., 8.], [8., 2.]],
+    ];
+    let mut triangulation = DynamicTriangulation::with_seed(rings.clone(), 0).unwrap();
+    let triangles: Vec<_> = triangulation.triangles().collect();
+
+    // Moving a corner of the outer square into the hole makes the rings cross
+    assert!(triangulation.move_vertex([0, 2], [5., 5.]).is_err());
+    assert_eq!(triangulation.rings(), &rings[..]);
+    assert_eq!(triangulation.triangles().collect::<Vec<_>>(), triangles);
+    check(&triangulation);
+}
+
+#[test]
+fn dynamic_invalid_move_triangulable() {
+    // Triangulating the rings after these moves can succeed, so they must be rejected before triangulating
+    let cases = [
+        // Two squares which overlap once the second is widened
+        (vec![vec![[0., 0.], [1., 0.], [1., 1.], [0., 1.]], vec![[2., 0.25], [2., 0.75], [2.5, 0.75], [2.5, 0.25]]], [1, 0], [0.75, 0.25]),
+        // A ring which crosses itself once its third vertex is moved
+        (vec![vec![[0., 3.], [1.5, 0.5], [3., 0.], [2., 2.]]], [0, 2], [0., 0.]),
+    ];
+    for (rings, index, position) in cases {
+        for seed in 0..8 {
+            let mut triangulation = DynamicTriangulation::with_seed(rings.clone(), seed).unwrap();
+            let triangles: Vec<_> = triangulation.triangles().collect();
+            let result = triangulation.move_vertex(index, position);
+            assert!(matches!(result, Err(TriangulationError::TrapezoidationError(TrapezoidationError::EdgeCrossing { .. }))), "Move was not rejected: {:?}", result);
+            assert_eq!(triangulation.rings(), &rings[..]);
+            assert_eq!(triangulation.triangles().collect::<Vec<_>>(), triangles);
+        }
+    }
+}
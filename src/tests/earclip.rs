@@ -0,0 +1,51 @@
+use crate::{FanFormat, ListFormat, Polygon, PolygonList, Strategy, TrapezoidationError, TriangulationError, formats::IndexedListFormat};
+
+use super::util;
+
+#[test]
+fn earclip_nested() {
+    util::compare::check_strategy(&vec![
+        vec![[0., 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[2., 2.], [2., 8.], [8., 8.], [8., 2.]],
+        vec![[4., 4.], [6., 4.], [6., 6.], [4., 6.]],
+        // Clockwise island beside the others, and a counterclockwise hole within it
+        vec![[20., 0.], [20., 10.], [30., 10.], [30., 0.]],
+        vec![[22., 2.], [28., 2.], [28., 8.], [22., 8.]],
+    ], Strategy::EarClip);
+}
+
+#[test]
+fn earclip_countries() {
+    for name in ["malta.txt", "belgium.txt", "croatia.txt", "greece.txt"] {
+        util::compare::check_strategy(&util::load_country(name), Strategy::EarClip);
+    }
+}
+
+#[test]
+fn earclip_winding() {
+    let polygon = util::polygon::star();
+    let triangles = polygon.triangulate_with_strategy(IndexedListFormat::new(Vec::<[usize; 3]>::new()).into_fan_format().reverse_winding(), Strategy::EarClip).expect("Triangulation failed");
+    assert_eq!(triangles.len(), polygon.len() - 2);
+    for t in triangles {
+        let [a, b, c] = t.map(|i| polygon[i]);
+        assert!((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]) < 0.);
+    }
+}
+
+#[test]
+fn earclip_errors() {
+    let too_small = vec![vec![[0f32, 0.], [1., 0.], [0., 1.]], vec![[2., 2.], [3., 2.]]];
+    let result = too_small.triangulate_with_strategy(IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format(), Strategy::EarClip);
+    assert!(matches!(result, Err(TriangulationError::TrapezoidationError(TrapezoidationError::ShortRing { ring: 1, ref vertices })) if *vertices == [[1, 0], [1, 1]]));
+
+    let empty: Vec<Vec<[f32; 2]>> = vec![];
+    let result = empty.triangulate_with_strategy(IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format(), Strategy::EarClip);
+    assert!(matches!(result, Err(TriangulationError::NoVertices)));
+}
+
+#[test]
+fn strategy_selection() {
+    assert_eq!(Strategy::select(12, 1), Strategy::EarClip);
+    assert_eq!(Strategy::select(1_000_000, 1), Strategy::Seidel);
+    assert_eq!(Strategy::select(200, 1000), Strategy::Seidel);
+}
@@ -0,0 +1,113 @@
+use crate::{FanFormat, ListFormat, Polygon, PolygonList, TrapezoidationError, TriangulationError, formats::{IndexedFanFormat, IndexedListFormat}};
+
+use super::util;
+
+fn signed_area(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    0.5 * ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]))
+}
+
+fn ring_area(polygon: &[[f64; 2]]) -> f64 {
+    (1..polygon.len() - 1).map(|i| signed_area(polygon[0], polygon[i], polygon[i + 1])).sum()
+}
+
+// Check the triangles cover the polygon with the requested winding, and match the trapezoidation's triangle count
+fn check(polygon: &Vec<[f64; 2]>) {
+    let area = ring_area(polygon).abs();
+    for reversed in [false, true] {
+        let format = IndexedListFormat::new(Vec::<[usize; 3]>::new()).into_fan_format();
+        let triangles = if reversed {
+            polygon.triangulate(format.reverse_winding())
+        } else {
+            polygon.triangulate(format)
+        }.expect("Triangulation failed");
+        assert_eq!(triangles.len(), polygon.len() - 2);
+
+        let areas: Vec<_> = triangles.iter().map(|t| {
+            let [a, b, c] = t.map(|i| polygon[i]);
+            if reversed { -signed_area(a, b, c) } else { signed_area(a, b, c) }
+        }).collect();
+        assert!(areas.iter().all(|&a| a >= 0.), "Triangles must follow the requested winding");
+        let total = areas.iter().sum::<f64>();
+        assert!((total - area).abs() < 1e-9 * area, "{} != {}", total, area);
+    }
+
+    let trapezoidized = polygon.trapezoidize().expect("Trapezoidation failed")
+        .triangulate(IndexedListFormat::new(Vec::<[usize; 3]>::new()).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(trapezoidized.len(), polygon.len() - 2);
+}
+
+#[test]
+fn fast_path_convex() {
+    for n in 3..=64 {
+        let mut polygon: Vec<_> = (0..n).map(|i| {
+            let theta = std::f64::consts::PI * 2. * (i as f64) / (n as f64);
+            let (x, y) = theta.sin_cos();
+            [x * 100., y * 100.]
+        }).collect();
+        for _ in 0..2 {
+            check(&polygon);
+
+            // Convex rings are output as a single fan around their first vertex
+            let fans = polygon.triangulate(IndexedFanFormat::new(Vec::<Vec<usize>>::new())).expect("Triangulation failed");
+            assert_eq!(fans.len(), 1);
+            assert_eq!(fans[0].len(), n);
+            assert_eq!(fans[0][0], 0);
+
+            polygon.reverse();
+        }
+    }
+}
+
+#[test]
+fn fast_path_convex_fan() {
+    // The fan around the first vertex uses the diagonal from it, which the trapezoidation doesn't
+    let square = vec![[0., 0.], [10., 0.], [10., 10.], [0., 10.]];
+    let fan = square.triangulate(IndexedListFormat::new(Vec::<[usize; 3]>::new()).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(fan, vec![[0, 1, 2], [0, 2, 3]]);
+    for seed in 0..4 {
+        let trapezoidized = square.trapezoidize_with_seed(seed).expect("Trapezoidation failed")
+            .triangulate(IndexedListFormat::new(Vec::<[usize; 3]>::new()).into_fan_format()).expect("Triangulation failed");
+        assert_eq!(trapezoidized.len(), 2);
+        assert!(trapezoidized.iter().all(|t| t.contains(&1) && t.contains(&3)));
+    }
+}
+
+#[test]
+fn fast_path_monotone() {
+    // Zigzagging chains on both sides, so the ring splits into many unimonotone pieces
+    let mut polygon = Vec::new();
+    for i in 0..20 {
+        polygon.push([if i % 2 == 0 { 0. } else { 3. }, f64::from(i)]);
+    }
+    for i in (0..20).rev() {
+        polygon.push([if i % 3 == 0 { 10. } else { 6. }, f64::from(i) + 0.5]);
+    }
+    check(&polygon);
+    polygon.reverse();
+    check(&polygon);
+
+    // Horizontal and collinear edges
+    let half_frame: Vec<_> = util::polygon::half_frame().into_iter().map(|v| v.map(f64::from)).collect();
+    check(&half_frame);
+    check(&vec![[0., 0.], [1., 0.], [2., 0.], [2., 1.], [2., 2.], [1., 1.], [0., 2.]]);
+}
+
+#[test]
+fn fast_path_skipped() {
+    // Rings which aren't monotone, and lists of several rings, are still trapezoidized
+    let star: Vec<_> = util::polygon::star().into_iter().map(|v| v.map(f64::from)).collect();
+    let triangles = star.triangulate(IndexedListFormat::new(Vec::<[usize; 3]>::new()).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), star.len() - 2);
+
+    let squares = vec![util::polygon::square(), util::polygon::square().into_iter().map(|[x, y]| [x + 2., y]).collect()];
+    let triangles = squares.triangulate(IndexedListFormat::new(Vec::<[[usize; 2]; 3]>::new()).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 4);
+}
+
+#[test]
+fn fast_path_crossing_chains() {
+    // A single maximum, but the chains cross, so the crossing must still be reported
+    let polygon: Vec<[f64; 2]> = vec![[0., 0.], [2., 1.], [0., 3.], [3., 2.]];
+    let result = polygon.triangulate(IndexedListFormat::new(Vec::<[usize; 3]>::new()).into_fan_format());
+    assert!(matches!(result, Err(TriangulationError::TrapezoidationError(TrapezoidationError::EdgeCrossing { .. }))), "{:?}", result);
+}
@@ -1,6 +1,6 @@
 use std::{error, fmt};
 
-use crate::{FanFormat, Polygon, PolygonList, TriangulationError, formats, FanBuilder, ListFormat};
+use crate::{FanFormat, Polygon, PolygonList, TriangulationError, formats, FanBuilder, ListFormat, ListBuilder};
 
 use super::util;
 
@@ -36,7 +36,9 @@ impl<'b> ErrorFormat<'b> {
         }
     }
 
-    fn set_failed_flag(&mut self) {
+    // The builder is only failed by the error it raised itself
+    fn set_failed_flag<Index>(&mut self, error: &TriangulationError<BuilderError, Index>) {
+        assert!(matches!(error, TriangulationError::FanBuilder(raised) if *raised == self.0), "Failed without raising {:?}", self.0);
         *self.1 = true;
     }
 }
@@ -69,8 +71,36 @@ impl<'p, P: PolygonList<'p> + ?Sized> FanBuilder<'p, P> for ErrorFormat<'p> {
         Ok(())
     }
 
-    fn fail(mut self, _error: &TriangulationError<Self::Error>) { 
-        self.set_failed_flag();
+    fn fail(mut self, error: &TriangulationError<Self::Error, P::Index>) { 
+        self.set_failed_flag(error);
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> ListFormat<'p, P> for ErrorFormat<'p> {
+    type Builder = Self;
+
+    fn initialize(self, _polygon_list: &'p P) -> Result<Self::Builder, <Self::Builder as ListBuilder<'p, P>>::Error> {
+        self.check_error(BuilderError::Initialize)?;
+        Ok(self)
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> ListBuilder<'p, P> for ErrorFormat<'p> {
+    type Output = ();
+    type Error = BuilderError;
+
+    fn add_triangle(&mut self, _vi0: <P as PolygonList<'p>>::Index, _vi1: <P as PolygonList<'p>>::Index, _vi2: <P as PolygonList<'p>>::Index) -> Result<(), Self::Error> {
+        self.check_error(BuilderError::NewFan)?;
+        Ok(())
+    }
+
+    fn build(self) -> Result<Self::Output, Self::Error> {
+        self.check_error(BuilderError::Build)?;
+        Ok(())
+    }
+
+    fn fail(mut self, error: &TriangulationError<Self::Error, P::Index>) { 
+        self.set_failed_flag(error);
     }
 }
 
@@ -87,7 +117,7 @@ fn error_propagation() {
                     assert!(failed);
                 }
             },
-            err @ _ => panic!("Unexpected non-builder error: {:?}", err),
+            err => panic!("Unexpected non-builder error: {:?}", err),
         }
     }
     
@@ -170,6 +200,37 @@ fn reverse_winding() {
     assert_ne!(result0, result1);
 }
 
+#[test]
+fn reverse_winding_area() {
+    let polygon_list = util::load_polygon_list(util::countries_path().join("croatia.txt").to_str().unwrap()).unwrap();
+    let traps = polygon_list.trapezoidize().expect("Trapezoidation failed");
+    let signed_area = |triangles: &[[[f32; 2]; 3]]| triangles.iter()
+        .map(|[a, b, c]| 0.5 * (f64::from(b[0] - a[0]) * f64::from(c[1] - a[1]) - f64::from(c[0] - a[0]) * f64::from(b[1] - a[1])))
+        .sum::<f64>();
+
+    let forward = traps.triangulate(formats::DeindexedListFormat::new(Vec::<[[f32; 2]; 3]>::new()).into_fan_format()).expect("Triangulation failed");
+    let reversed = traps.triangulate(formats::DeindexedListFormat::new(Vec::<[[f32; 2]; 3]>::new()).into_fan_format().reverse_winding()).expect("Triangulation failed");
+    assert_eq!(forward.len(), reversed.len());
+    let (forward_area, reversed_area) = (signed_area(&forward), signed_area(&reversed));
+    assert!(forward_area > 0.);
+    assert!((forward_area + reversed_area).abs() < 1e-6 * forward_area, "{} != {}", forward_area, -reversed_area);
+}
+
+#[test]
+fn reverse_winding_backtracking() {
+    // Reversed fans used to be rooted by which chain they were on rather than by whether they backtracked,
+    // giving a triangle with counterclockwise winding which overlapped its neighbour
+    let polygon: Vec<[f64; 2]> = vec![[0., 0.], [6., 12.], [0., 15.], [-10., 6.]];
+    for seed in 0..16 {
+        let traps = polygon.trapezoidize_with_seed(seed).expect("Trapezoidation failed");
+        let triangles = traps.triangulate(formats::DeindexedListFormat::new(Vec::<[[f64; 2]; 3]>::new()).into_fan_format().reverse_winding()).expect("Triangulation failed");
+        assert_eq!(triangles.len(), 2);
+        for [a, b, c] in triangles {
+            assert!((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]) < 0., "Counterclockwise triangle {:?}", [a, b, c]);
+        }
+    }
+}
+
 #[test]
 fn deindexed_fan() {
     fn require_f32_2(_i: [f32; 2]) { }
@@ -208,3 +269,66 @@ fn delimited_fan() {
 
     assert!(output.into_iter().filter(|i| *i == delimiter).count() > 0);
 }
+
+#[test]
+fn canonical_list() {
+    let polygon = util::polygon::star();
+
+    let mut output0 = Vec::<[usize; 3]>::new();
+    let mut output1 = Vec::<[usize; 3]>::new();
+    let builder0 = formats::IndexedListFormat::new(&mut output0).into_fan_format();
+    let builder1 = formats::IndexedListFormat::new(&mut output1).canonicalize().into_fan_format();
+    let result0 = polygon.triangulate_with_seed(builder0, 7).expect("Triangulation failed");
+    let result1 = polygon.triangulate_with_seed(builder1, 7).expect("Triangulation failed");
+
+    let mut expected: Vec<_> = result0.iter().map(|t| {
+        let mut t = *t;
+        let min = (0..3).min_by_key(|&i| t[i]).unwrap();
+        t.rotate_left(min);
+        t
+    }).collect();
+    expected.sort();
+    assert_eq!(&expected, result1);
+}
+
+#[test]
+fn canonical_list_error() {
+    // The wrapped builder only receives triangles once triangulation is complete, but is still cleaned up if it fails
+    let mut failed = false;
+    let format = ErrorFormat::new(BuilderError::NewFan, &mut failed).canonicalize().into_fan_format();
+    match util::polygon::star().triangulate(format).expect_err("Triangulation completed successfully") {
+        TriangulationError::FanBuilder(err) => assert_eq!(err, BuilderError::NewFan),
+        err => panic!("Unexpected non-builder error: {:?}", err),
+    }
+    assert!(failed);
+}
+
+#[test]
+fn trapezoid_list() {
+    let polygon_list = util::load_polygon_list(util::countries_path().join("cuba.txt").to_str().unwrap()).unwrap();
+
+    let mut triangles = Vec::<[[f32; 2]; 3]>::new();
+    polygon_list.triangulate(formats::DeindexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+    let triangle_area: f64 = triangles.iter().map(|[a, b, c]| {
+        ((b[0] - a[0]) as f64 * (c[1] - a[1]) as f64 - (c[0] - a[0]) as f64 * (b[1] - a[1]) as f64) / 2.
+    }).sum();
+
+    // Existing contents are kept
+    let mut output = vec![[[0f32; 2]; 4]];
+    let traps = polygon_list.trapezoidize().expect("Trapezoidation failed");
+    traps.emit_trapezoids(formats::TrapezoidListFormat::new(&mut output)).expect("Trapezoid output failed");
+    assert!(output.len() > 1);
+    let mut trapezoid_area = 0.;
+    for [bl, br, tr, tl] in &output[1..] {
+        assert_eq!(bl[1], br[1]);
+        assert_eq!(tl[1], tr[1]);
+        assert!(bl[1] < tl[1]);
+        assert!(bl[0] <= br[0] && tl[0] <= tr[0]);
+        trapezoid_area += ((br[0] - bl[0]) as f64 + (tr[0] - tl[0]) as f64) * (tl[1] - bl[1]) as f64 / 2.;
+    }
+    assert!((trapezoid_area - triangle_area).abs() < triangle_area * 1e-4, "{} != {}", trapezoid_area, triangle_area);
+
+    // Other vertex types can be used for the corners
+    let tuples: Vec<[(f32, f32); 4]> = traps.emit_trapezoids(formats::TrapezoidListFormat::new(Vec::new())).expect("Trapezoid output failed");
+    assert_eq!(tuples.len(), output.len() - 1);
+}
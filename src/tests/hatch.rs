@@ -0,0 +1,115 @@
+use crate::{Location, PolygonList, hatch::{HatchError, hatch}};
+
+use super::util;
+
+fn nested_squares() -> Vec<Vec<[f32; 2]>> {
+    vec![
+        vec![[0., 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[2., 2.], [2., 8.], [8., 8.], [8., 2.]],
+        vec![[4., 4.], [6., 4.], [6., 6.], [4., 6.]],
+    ]
+}
+
+fn length([a, b]: [[f32; 2]; 2]) -> f32 {
+    (b[0] - a[0]).hypot(b[1] - a[1])
+}
+
+fn area(polygon_list: &[Vec<[f32; 2]>]) -> f32 {
+    polygon_list.iter().map(|ring| {
+        (0..ring.len()).map(|i| {
+            let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+            a[0] * b[1] - b[0] * a[1]
+        }).sum::<f32>() / 2.
+    }).sum()
+}
+
+// Lines in a canonical direction and order, for comparing sets of lines
+fn sorted_lines(lines: impl Iterator<Item = [[f32; 2]; 2]>) -> Vec<[[f32; 2]; 2]> {
+    let mut lines: Vec<_> = lines.map(|[a, b]| if a <= b { [a, b] } else { [b, a] }).collect();
+    lines.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    lines
+}
+
+#[test]
+fn hatch_square() {
+    let square = vec![vec![[0., 0.], [10., 0.], [10., 10.], [0., 10.]]];
+    let hatch = hatch(&square, 2.5, 0.).unwrap();
+    assert_eq!(hatch.lines(), &[0., 2.5, 5., 7.5].map(|y| [[0., y], [10., y]]));
+
+    let paths = hatch.boustrophedon();
+    assert_eq!(paths, vec![vec![[0., 0.], [10., 0.], [10., 2.5], [0., 2.5], [0., 5.], [10., 5.], [10., 7.5], [0., 7.5]]]);
+}
+
+#[test]
+fn hatch_holes() {
+    let polygon_list = nested_squares();
+    let hatch = hatch(&polygon_list, 1., 0.).unwrap();
+    // A line along a horizontal edge is only included if the interior is above it
+    let per_row = |y: f32| hatch.lines().iter().filter(|line| line[0][1] == y).count();
+    assert_eq!([0., 1., 2., 3., 4., 5., 6., 7., 8., 9.].map(per_row), [1, 1, 2, 2, 3, 3, 2, 2, 1, 1]);
+    let total: f32 = hatch.lines().iter().copied().map(length).sum();
+    assert_eq!(total, area(&polygon_list));
+
+    // A line touching the bottom corner of a hole is not split
+    let diamond_hole = vec![
+        vec![[0., 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[5., 3.], [3., 5.], [5., 7.], [7., 5.]],
+    ];
+    let hatch = crate::hatch::hatch(&diamond_hole, 1., 0.).unwrap();
+    let lines: Vec<_> = hatch.lines().iter().filter(|line| line[0][1] == 3.).collect();
+    assert_eq!(lines, vec![&[[0., 3.], [10., 3.]]]);
+}
+
+#[test]
+fn hatch_angles() {
+    let cuba = util::load_polygon_list(util::countries_path().join("cuba.txt").to_str().unwrap()).unwrap();
+    for (polygon_list, spacing) in [(nested_squares(), 0.2), (cuba, 0.02)] {
+        let traps = polygon_list.trapezoidize().unwrap();
+        // Rings may be wound either way, as long as they are consistent
+        let area = area(&polygon_list).abs();
+        for angle in [0., 0.3, std::f32::consts::FRAC_PI_2, 2.] {
+            let hatch = hatch(&polygon_list, spacing, angle).unwrap();
+            let direction = [angle.cos(), angle.sin()];
+            for &[a, b] in hatch.lines() {
+                let d = [b[0] - a[0], b[1] - a[1]];
+                assert!((d[0] * direction[1] - d[1] * direction[0]).abs() < 1e-3, "Line {:?} is not in the hatch direction", [a, b]);
+                assert!(d[0] * direction[0] + d[1] * direction[1] > 0.);
+                let midpoint = [(a[0] + b[0]) / 2., (a[1] + b[1]) / 2.];
+                assert!(!matches!(traps.locate(&midpoint).unwrap(), Location::Outside), "Line midpoint {:?} is outside", midpoint);
+            }
+
+            // The lines sample the area at regular intervals
+            let total = hatch.lines().iter().copied().map(length).sum::<f32>() * spacing;
+            assert!((total - area).abs() < 0.02 * area, "Hatched area {} differs from polygon area {}", total, area);
+
+            let paths = hatch.boustrophedon();
+            assert!(paths.len() < hatch.lines().len() / 4, "{} paths for {} lines", paths.len(), hatch.lines().len());
+            let steps = sorted_lines(paths.iter().flat_map(|path| path.windows(2).map(|step| [step[0], step[1]])));
+            for line in sorted_lines(hatch.lines().iter().copied()) {
+                assert!(steps.binary_search_by(|step| step.partial_cmp(&line).unwrap()).is_ok(), "Line {:?} is not part of a path", line);
+            }
+            // Connecting moves follow the boundary, up to rounding errors from the rotation
+            for [a, b] in steps {
+                let midpoint = [(a[0] + b[0]) / 2., (a[1] + b[1]) / 2.];
+                if matches!(traps.locate(&midpoint).unwrap(), Location::Outside) {
+                    let distance = traps.nearest_edge(&midpoint).unwrap().unwrap().distance;
+                    assert!(distance < 1e-4, "Path step midpoint {:?} is outside", midpoint);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn hatch_invalid_spacing() {
+    let square = vec![vec![[0f32, 0.], [4., 0.], [4., 4.], [0., 4.]]];
+    for spacing in [0., -1., f32::NAN] {
+        assert!(matches!(hatch(&square, spacing, 0.), Err(HatchError::InvalidSpacing)), "Spacing {} was accepted", spacing);
+    }
+    assert!(matches!(hatch(&square, 1e-9, 0.), Err(HatchError::TooManyLines)));
+
+    // Far from the origin, rows can't be told apart by their y coordinate, but the lines are still counted
+    let far = vec![vec![[0f32, 1e8], [4., 1e8], [4., 1e8 + 64.], [0., 1e8 + 64.]]];
+    let hatch = hatch(&far, 1., 0.).unwrap();
+    assert!(hatch.lines().len() <= 64);
+}
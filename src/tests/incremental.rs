@@ -0,0 +1,53 @@
+use crate::{ListFormat, Location, PolygonList, TrapezoidationBuilder, TrapezoidationError, formats::IndexedListFormat};
+
+use super::util;
+
+#[test]
+fn incremental_matches_batch() {
+    let seed = 7;
+    for name in ["croatia.txt", "greece.txt"] {
+        let rings = util::load_country(name);
+        let mut builder = TrapezoidationBuilder::with_seed(seed);
+        for (i, ring) in rings.iter().enumerate() {
+            assert_eq!(builder.add_ring(ring.clone()).unwrap(), i);
+        }
+        assert_eq!(builder.rings(), &rings[..]);
+
+        let mut incremental = Vec::<[usize; 2]>::new();
+        builder.triangulate(IndexedListFormat::new(&mut incremental).into_fan_format()).unwrap();
+        let mut batch = Vec::<[usize; 2]>::new();
+        rings.triangulate_with_seed(IndexedListFormat::new(&mut batch).into_fan_format(), seed).unwrap();
+        assert_eq!(incremental, batch, "{} triangulated differently", name);
+        assert_eq!(builder.trapezoidation().seed(), Some(seed));
+    }
+}
+
+#[test]
+fn incremental_intermediate_states() {
+    let rings = util::load_country("croatia.txt");
+    let mut builder = TrapezoidationBuilder::new();
+    for (i, ring) in rings.iter().enumerate() {
+        builder.add_ring(ring.clone()).unwrap();
+
+        let mut triangles = Vec::<[usize; 2]>::new();
+        builder.triangulate(IndexedListFormat::new(&mut triangles).into_fan_format()).unwrap();
+        // Each island is triangulated separately, so has two fewer triangles than vertices
+        let expected: usize = rings[..=i].iter().map(|ring| ring.len() - 2).sum();
+        assert_eq!(triangles.len(), expected * 3);
+        assert!(triangles.iter().all(|[ring, _]| *ring <= i));
+
+        let traps = builder.trapezoidation();
+        for (ring, vertices) in rings[..=i].iter().enumerate() {
+            assert_eq!(traps.locate(&vertices[0]).unwrap(), Location::OnVertex { ring, vertex: [ring, 0] });
+        }
+    }
+}
+
+#[test]
+fn incremental_not_enough_vertices() {
+    let mut builder = TrapezoidationBuilder::with_seed(0);
+    builder.add_ring(vec![[0f32, 0.], [1., 0.], [0., 1.]]).unwrap();
+    assert!(matches!(builder.add_ring(vec![[2., 2.], [3., 2.]]), Err(TrapezoidationError::ShortRing { ring: 1, ref vertices }) if *vertices == [[1, 0], [1, 1]]));
+    assert_eq!(builder.rings().len(), 1);
+    assert_eq!(builder.add_ring(vec![[2., 2.], [3., 2.], [2., 3.]]).unwrap(), 1);
+}
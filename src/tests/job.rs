@@ -0,0 +1,86 @@
+use std::{fmt::Debug, sync::{Arc, atomic::{AtomicBool, Ordering}}};
+
+use crate::{FanBuilder, FanFormat, JobStatus, ListFormat, PolygonList, TrapezoidationError, TriangulationError, TriangulationJob, formats::IndexedListFormat};
+
+use super::util;
+
+// Step the job to completion, checking its progress, and return its output and how many steps it took
+fn run<'p, P: PolygonList<'p, Index: Debug>, FB: FanFormat<'p, P>>(job: &mut TriangulationJob<'p, P, FB>, budget: usize) -> (<FB::Builder as FanBuilder<'p, P>>::Output, usize) {
+    let mut steps = 1;
+    let mut progress = job.progress();
+    loop {
+        match job.step(budget).expect("Triangulation failed") {
+            JobStatus::Pending => {
+                assert!(job.progress() >= progress && job.progress() < 1., "{} after {}", job.progress(), progress);
+                progress = job.progress();
+                steps += 1;
+            },
+            JobStatus::Complete(triangles) => {
+                assert!(job.is_finished());
+                assert_eq!(job.progress(), 1.);
+                return (triangles, steps);
+            },
+        }
+    }
+}
+
+#[test]
+fn job_matches_trapezoidation() {
+    for (seed, name) in ["malta.txt", "croatia.txt"].into_iter().enumerate() {
+        let polygon_list = util::load_polygon_list(util::countries_path().join(name).to_str().unwrap()).unwrap();
+        let seed = seed as u64;
+        let expected = polygon_list.trapezoidize_with_seed(seed).unwrap().triangulate(IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format()).expect("Triangulation failed");
+
+        for budget in [1, 100, usize::MAX] {
+            let mut job = TriangulationJob::with_seed(&polygon_list, IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format(), seed);
+            let (triangles, steps) = run(&mut job, budget);
+            assert_eq!(triangles, expected, "{} triangulated differently with a budget of {}", name, budget);
+            if budget == usize::MAX {
+                assert_eq!(steps, 1);
+            } else if budget == 1 {
+                // One step per segment, then one per trapezoid traversed, which is at least one above each vertex
+                assert!(steps > 2 * polygon_list.vertex_count(), "{} steps with a budget of 1", steps);
+            } else {
+                // At least one step per segment
+                assert!(steps > polygon_list.vertex_count() / budget, "{} steps with a budget of {}", steps, budget);
+            }
+        }
+    }
+}
+
+#[test]
+fn job_cancelled() {
+    let polygon_list = util::load_polygon_list(util::countries_path().join("croatia.txt").to_str().unwrap()).unwrap();
+    let flag = Arc::new(AtomicBool::new(false));
+    let mut job = TriangulationJob::new(&polygon_list, IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format()).with_cancel_flag(flag.clone());
+    assert!(matches!(job.step(10), Ok(JobStatus::Pending)));
+    assert!(job.progress() > 0.);
+
+    flag.store(true, Ordering::Relaxed);
+    assert!(matches!(job.step(10), Err(TriangulationError::Cancelled)));
+    assert!(job.is_finished());
+}
+
+#[test]
+fn job_cancelled_triangulating() {
+    let polygon_list = util::load_polygon_list(util::countries_path().join("croatia.txt").to_str().unwrap()).unwrap();
+    let flag = Arc::new(AtomicBool::new(false));
+    let mut job = TriangulationJob::new(&polygon_list, IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format()).with_cancel_flag(flag.clone());
+    // Insert every segment, then traverse a few trapezoids
+    assert!(matches!(job.step(polygon_list.vertex_count() + 10), Ok(JobStatus::Pending)));
+
+    flag.store(true, Ordering::Relaxed);
+    assert!(matches!(job.step(usize::MAX), Err(TriangulationError::Cancelled)));
+    assert!(job.is_finished());
+}
+
+#[test]
+fn job_errors() {
+    let too_small = vec![vec![[0f32, 0.], [1., 0.], [0., 1.]], vec![[2., 2.], [3., 2.]]];
+    let mut job = TriangulationJob::new(&too_small, IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format());
+    assert!(matches!(job.step(1), Err(TriangulationError::TrapezoidationError(TrapezoidationError::ShortRing { ring: 1, ref vertices })) if *vertices == [[1, 0], [1, 1]]));
+
+    let empty: Vec<Vec<[f32; 2]>> = vec![];
+    let mut job = TriangulationJob::new(&empty, IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format());
+    assert!(matches!(job.step(0), Err(TriangulationError::NoVertices)));
+}
@@ -0,0 +1,63 @@
+use std::mem::size_of;
+
+use crate::{PolygonList, TrapezoidationBuilder, idx::Idx, nexus::Nexus, querynode::QueryNode, trapezoidation::{TrapezoidationParts, TrapezoidationStructure}};
+
+use super::util;
+
+#[test]
+fn compact_links() {
+    type V = [f32; 2];
+    type I = [usize; 2];
+    assert_eq!(size_of::<Option<Idx<Nexus<V, I>>>>(), 4);
+    // Two links and the segment or vertex to compare against
+    assert_eq!(size_of::<QueryNode<V, I>>(), 16);
+}
+
+#[test]
+fn stored_bytes() {
+    let polygon_list = util::load_polygon_list(util::countries_path().join("russia.txt").to_str().unwrap()).unwrap();
+    let n = polygon_list.vertex_count();
+    let traps = polygon_list.trapezoidize_with_seed(0).unwrap();
+    // A nexus stores its vertex index (16 bytes here), ring, coordinates, two trapezoid links and up to two dividers of 12 bytes.
+    // A segment stores its two nexuses and its direction, and each of the 2n + 1 trapezoids four links and a sink
+    assert!(traps.ns().heap_bytes() <= n * 64, "{} bytes for {} nexuses", traps.ns().heap_bytes(), n);
+    assert!(traps.ss().heap_bytes() <= n * 9, "{} bytes for {} segments", traps.ss().heap_bytes(), n);
+    assert!(traps.ts().heap_bytes() <= (2 * n + 1) * 20, "{} bytes for {} trapezoids", traps.ts().heap_bytes(), 2 * n + 1);
+}
+
+#[test]
+fn query_node_reservation() {
+    for name in ["croatia.txt", "greece.txt", "japan.txt"] {
+        let polygon_list = util::load_polygon_list(util::countries_path().join(name).to_str().unwrap()).unwrap();
+        let n = polygon_list.vertex_count();
+        for seed in 0..2 {
+            let traps = polygon_list.trapezoidize_with_seed(seed).unwrap();
+            assert_eq!(traps.ns().len(), n);
+            assert_eq!(traps.ts().len(), 2 * n + 1);
+            // The reservation is only a heuristic, but is enough for these inputs
+            let reserved = TrapezoidationParts::<[f32; 2], [usize; 2]>::query_node_reservation(n);
+            assert!(traps.qs().len() <= reserved, "{} has {} query nodes, above the {} reserved", name, traps.qs().len(), reserved);
+        }
+    }
+}
+
+#[test]
+fn query_node_reservation_exceeded() {
+    // A column of small triangles, then tall triangles inserted from left to right. The small triangles' horizontal dividers
+    // extend left to the last tall triangle, so each tall triangle crosses all of them, and there are Θ(n²) query nodes
+    let mut builder = TrapezoidationBuilder::with_seed(0);
+    let count = 40;
+    for i in 0..count {
+        let y = f64::from(i) * 10. + 10.;
+        builder.add_ring(vec![[1000., y], [1001., y], [1000.5, y + 1.]]).unwrap();
+    }
+    for i in 0..count {
+        let x = f64::from(i) * 10.;
+        builder.add_ring(vec![[x, 0.], [x + 1., 0.], [x + 0.5, f64::from(count) * 10. + 20.]]).unwrap();
+    }
+    let n = 6 * count as usize;
+    let reserved = TrapezoidationParts::<[f64; 2], [usize; 2]>::query_node_reservation(n);
+    let qs = builder.trapezoidation().qs().len();
+    assert!(qs >= 1 + 4 * n + 3 * (count * count) as usize, "{} query nodes", qs);
+    assert!(qs > reserved, "{} query nodes, within the {} reserved", qs, reserved);
+}
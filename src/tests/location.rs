@@ -0,0 +1,195 @@
+use std::fs;
+
+use rand::{Rng, SeedableRng};
+
+use crate::{EdgeHit, HorizontalDirection, Location, PolygonList};
+
+use super::util;
+
+fn nested_squares() -> Vec<Vec<[f32; 2]>> {
+    vec![
+        // Outer square, counterclockwise
+        vec![[0., 0.], [10., 0.], [10., 10.], [0., 10.]],
+        // Hole, clockwise
+        vec![[2., 2.], [2., 8.], [8., 8.], [8., 2.]],
+        // Island within the hole
+        vec![[4., 4.], [6., 4.], [6., 6.], [4., 6.]],
+    ]
+}
+
+// Even-odd rule, ignoring points on the boundary
+fn is_inside(polygon_list: &[Vec<[f32; 2]>], p: [f32; 2]) -> bool {
+    let mut inside = false;
+    for ring in polygon_list {
+        for (i, a) in ring.iter().enumerate() {
+            let b = ring[(i + 1) % ring.len()];
+            if (a[1] > p[1]) != (b[1] > p[1]) && p[0] < a[0] + (p[1] - a[1]) / (b[1] - a[1]) * (b[0] - a[0]) {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+#[test]
+fn locate_regions() {
+    let polygon_list = nested_squares();
+    let traps = polygon_list.trapezoidize().expect("Trapezoidation failed");
+
+    assert_eq!(traps.locate(&[1., 1.]).unwrap(), Location::Inside { ring: 0 });
+    assert_eq!(traps.locate(&[9., 5.]).unwrap(), Location::Inside { ring: 0 });
+    assert_eq!(traps.locate(&[3., 5.]).unwrap(), Location::Outside);
+    assert_eq!(traps.locate(&[5., 5.]).unwrap(), Location::Inside { ring: 2 });
+    assert_eq!(traps.locate(&[-1., 5.]).unwrap(), Location::Outside);
+    assert_eq!(traps.locate(&[5., 11.]).unwrap(), Location::Outside);
+    assert_eq!(traps.locate(&[5., -1.]).unwrap(), Location::Outside);
+}
+
+#[test]
+fn locate_boundary() {
+    let polygon_list = nested_squares();
+    let traps = polygon_list.trapezoidize().expect("Trapezoidation failed");
+
+    for (ring, polygon) in polygon_list.iter().enumerate() {
+        for (i, v) in polygon.iter().enumerate() {
+            assert_eq!(traps.locate(v).unwrap(), Location::OnVertex { ring, vertex: [ring, i] });
+
+            let j = (i + 1) % polygon.len();
+            let w = polygon[j];
+            let midpoint = [(v[0] + w[0]) / 2., (v[1] + w[1]) / 2.];
+            assert_eq!(traps.locate(&midpoint).unwrap(), Location::OnEdge { ring, edge: ([ring, i], [ring, j]) });
+        }
+    }
+}
+
+#[test]
+fn locate_geography() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    for name in ["belgium.txt", "bhutan.txt", "cuba.txt"] {
+        let polygon_list = util::load_polygon_list(util::countries_path().join(name).to_str().unwrap()).unwrap();
+        let traps = polygon_list.trapezoidize().expect("Trapezoidation failed");
+
+        let vertices = polygon_list.iter().flatten();
+        let min = vertices.clone().fold([f32::MAX; 2], |min, v| [min[0].min(v[0]), min[1].min(v[1])]);
+        let max = vertices.fold([f32::MIN; 2], |max, v| [max[0].max(v[0]), max[1].max(v[1])]);
+
+        for _ in 0..1000 {
+            let p = [rng.gen_range(min[0]..max[0]), rng.gen_range(min[1]..max[1])];
+            let expected = is_inside(&polygon_list, p);
+            match traps.locate(&p).unwrap() {
+                Location::Inside { .. } => assert!(expected, "{:?} in {} should be outside", p, name),
+                Location::Outside => assert!(!expected, "{:?} in {} should be inside", p, name),
+                location => panic!("{:?} in {} unexpectedly on boundary: {:?}", p, name, location),
+            }
+        }
+    }
+}
+
+#[test]
+fn locate_all_countries() {
+    // Every vertex should be found exactly
+    for file in fs::read_dir(util::countries_path()).unwrap() {
+        let file = file.unwrap();
+        let polygon_list = util::load_polygon_list(file.path().to_str().unwrap()).unwrap();
+        if polygon_list.vertex_count() <= 500 {
+            let traps = polygon_list.trapezoidize().expect("Trapezoidation failed");
+            for (ring, polygon) in polygon_list.iter().filter(|p| !p.is_empty()).enumerate() {
+                for v in polygon {
+                    match traps.locate(v).unwrap() {
+                        Location::OnVertex { ring: vertex_ring, vertex } => {
+                            assert_eq!(ring, vertex_ring);
+                            assert_eq!(polygon_list[vertex[0]][vertex[1]], *v);
+                        }
+                        location => panic!("Vertex {:?} not found: {:?}", v, location),
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn sample_points(polygon_list: &[Vec<[f32; 2]>], count: usize) -> Vec<[f32; 2]> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+    let vertices = polygon_list.iter().flatten();
+    let min = vertices.clone().fold([f32::MAX; 2], |min, v| [min[0].min(v[0]), min[1].min(v[1])]);
+    let max = vertices.fold([f32::MIN; 2], |max, v| [max[0].max(v[0]), max[1].max(v[1])]);
+
+    // Include the vertices and edge midpoints, to cover boundary cases
+    let mut points: Vec<_> = (0..count).map(|_| [rng.gen_range(min[0]..max[0]), rng.gen_range(min[1]..max[1])]).collect();
+    for polygon in polygon_list {
+        for (i, v) in polygon.iter().enumerate() {
+            let w = polygon[(i + 1) % polygon.len()];
+            points.push(*v);
+            points.push([(v[0] + w[0]) / 2., (v[1] + w[1]) / 2.]);
+        }
+    }
+    points
+}
+
+#[test]
+fn classify_points() {
+    for polygon_list in [nested_squares(), util::load_polygon_list(util::countries_path().join("cuba.txt").to_str().unwrap()).unwrap()] {
+        let traps = polygon_list.trapezoidize().expect("Trapezoidation failed");
+        let points = sample_points(&polygon_list, 5000);
+        let expected: Vec<_> = points.iter().map(|p| traps.locate(p).unwrap()).collect();
+        assert_eq!(traps.classify_points(&points).unwrap(), expected);
+        #[cfg(feature = "rayon")]
+        assert_eq!(traps.par_classify_points(&points).unwrap(), expected);
+    }
+}
+
+#[test]
+fn shoot_horizontal() {
+    let polygon_list = nested_squares();
+    let traps = polygon_list.trapezoidize().expect("Trapezoidation failed");
+
+    let hit = traps.shoot_horizontal(&[1., 5.], HorizontalDirection::Left).unwrap();
+    assert_eq!(hit, EdgeHit { ring: 0, edge: ([0, 3], [0, 0]), point: [0., 5.], distance: 1. });
+    let hit = traps.shoot_horizontal(&[1., 5.], HorizontalDirection::Right).unwrap();
+    assert_eq!(hit, EdgeHit { ring: 1, edge: ([1, 0], [1, 1]), point: [2., 5.], distance: 1. });
+    let hit = traps.shoot_horizontal(&[5., 5.5], HorizontalDirection::Right).unwrap();
+    assert_eq!(hit, EdgeHit { ring: 2, edge: ([2, 1], [2, 2]), point: [6., 5.5], distance: 1. });
+    let hit = traps.shoot_horizontal(&[-1., 3.], HorizontalDirection::Right).unwrap();
+    assert_eq!(hit, EdgeHit { ring: 0, edge: ([0, 3], [0, 0]), point: [0., 3.], distance: 1. });
+
+    assert_eq!(traps.shoot_horizontal(&[-1., 3.], HorizontalDirection::Left), None);
+    assert_eq!(traps.shoot_horizontal(&[5., 11.], HorizontalDirection::Left), None);
+
+    // Points on the boundary hit it immediately
+    let hit = traps.shoot_horizontal(&[10., 5.], HorizontalDirection::Right).unwrap();
+    assert_eq!(hit, EdgeHit { ring: 0, edge: ([0, 1], [0, 2]), point: [10., 5.], distance: 0. });
+    let hit = traps.shoot_horizontal(&[8., 8.], HorizontalDirection::Left).unwrap();
+    assert_eq!(hit, EdgeHit { ring: 1, edge: ([1, 2], [1, 3]), point: [8., 8.], distance: 0. });
+}
+
+#[test]
+fn nearest_edge() {
+    for polygon_list in [nested_squares(), util::load_polygon_list(util::countries_path().join("cuba.txt").to_str().unwrap()).unwrap()] {
+        let traps = polygon_list.trapezoidize().expect("Trapezoidation failed");
+        for p in sample_points(&polygon_list, 500) {
+            let hit = traps.nearest_edge(&p).unwrap().unwrap();
+
+            let ring = &polygon_list[hit.edge.0[0]];
+            let (a, b) = (ring[hit.edge.0[1]], ring[hit.edge.1[1]]);
+            assert_eq!(hit.edge.1[1], (hit.edge.0[1] + 1) % ring.len());
+            assert!((distance(p, hit.point) - hit.distance).abs() <= 1e-4);
+            assert!(segment_distance(p, a, b) <= hit.distance + 1e-4);
+
+            let expected = polygon_list.iter()
+                .flat_map(|ring| ring.iter().zip(ring.iter().cycle().skip(1)))
+                .map(|(a, b)| segment_distance(p, *a, *b))
+                .fold(f32::MAX, f32::min);
+            assert!((hit.distance - expected).abs() <= 1e-4, "{:?}: nearest edge at {}, expected {}", p, hit.distance, expected);
+        }
+    }
+}
+
+fn distance(a: [f32; 2], b: [f32; 2]) -> f32 {
+    (a[0] - b[0]).hypot(a[1] - b[1])
+}
+
+fn segment_distance(p: [f32; 2], a: [f32; 2], b: [f32; 2]) -> f32 {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let t = (((p[0] - a[0]) * dx + (p[1] - a[1]) * dy) / (dx * dx + dy * dy)).clamp(0., 1.);
+    distance(p, [a[0] + dx * t, a[1] + dy * t])
+}
@@ -9,4 +9,38 @@ mod regression;
 #[cfg(test)]
 mod triangulate;
 #[cfg(test)]
-mod format;
\ No newline at end of file
+mod format;
+#[cfg(test)]
+mod sprite;
+#[cfg(test)]
+mod location;
+#[cfg(test)]
+mod view;
+#[cfg(test)]
+mod raster;
+#[cfg(test)]
+mod hatch;
+#[cfg(test)]
+mod incremental;
+#[cfg(test)]
+mod dynamic;
+#[cfg(test)]
+mod triangulator;
+#[cfg(test)]
+mod layout;
+#[cfg(test)]
+mod batch;
+#[cfg(test)]
+mod earclip;
+#[cfg(test)]
+mod fast_path;
+#[cfg(test)]
+mod sweep;
+#[cfg(test)]
+mod direction;
+#[cfg(all(test, feature = "rayon"))]
+mod parallel;
+#[cfg(test)]
+mod job;
+#[cfg(test)]
+mod owned;
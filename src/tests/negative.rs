@@ -1,4 +1,4 @@
-use crate::{formats, Polygon, PolygonList};
+use crate::{diagnose, formats, Polygon, PolygonList, TrapezoidationError, TriangulationError};
 
 #[test]
 #[should_panic]
@@ -20,7 +20,6 @@ fn overlapping_vertex() {
 }
 
 #[test]
-#[should_panic]
 fn overlapping_polygons() {
     // +------+
     // |    +---+
@@ -29,5 +28,54 @@ fn overlapping_polygons() {
     // +------+
     let polygon_a: Vec<[f32; 2]> = vec![[0., 0.], [0., 1.], [1., 1.], [1., 0.]];
     let polygon_b: Vec<[f32; 2]> = vec![[0.75, 0.25], [0.75, 0.75], [1.25, 0.75], [1.25, 0.25]];
-    vec![polygon_a, polygon_b].triangulate(formats::IndexedFanFormat::new(&mut Vec::<Vec<_>>::new())).unwrap();
-}
\ No newline at end of file
+    let polygon_list = vec![polygon_a, polygon_b];
+    for seed in 0..64 {
+        assert!(polygon_list.triangulate_with_seed(formats::IndexedFanFormat::new(&mut Vec::<Vec<_>>::new()), seed).is_err(), "Seed {} succeeded", seed);
+    }
+}
+
+#[test]
+fn crossing_polygons() {
+    let polygon_a: Vec<[f32; 2]> = vec![[0., 0.], [0., 1.], [1., 1.], [1., 0.]];
+    let polygon_b: Vec<[f32; 2]> = vec![[0.75, 0.25], [0.75, 0.75], [1.25, 0.75], [1.25, 0.25]];
+    let polygon_list = vec![polygon_a, polygon_b];
+    let expected = (([0, 2], [0, 3]), ([1, 3], [1, 0]), [1., 0.25]);
+    assert!(matches!(diagnose::find_violation(&polygon_list), Some(TrapezoidationError::EdgeCrossing { a, b, point }) if (a, b, point) == expected));
+
+    // Every insertion order must run into the crossing and report it
+    for seed in 0..16 {
+        let mut output = Vec::<Vec<_>>::new();
+        let error = polygon_list.triangulate_with_seed(formats::IndexedFanFormat::new(&mut output), seed).expect_err("Crossing polygons triangulated");
+        assert!(matches!(error, TriangulationError::TrapezoidationError(TrapezoidationError::EdgeCrossing { a, b, point }) if (a, b, point) == expected), "Unexpected error {}", error);
+    }
+}
+
+#[test]
+fn crossing_edges() {
+    let bowtie: Vec<[f32; 2]> = vec![[0., 0.], [1., 1.], [1., 0.], [0., 1.]];
+    let mut output = Vec::<Vec<_>>::new();
+    let result = bowtie.triangulate(formats::IndexedFanFormat::new(&mut output));
+    assert!(matches!(result, Err(TriangulationError::TrapezoidationError(TrapezoidationError::EdgeCrossing { a: (0, 1), b: (2, 3), point: [0.5, 0.5] }))));
+}
+
+#[test]
+fn duplicate_vertex() {
+    let polygon: Vec<[f32; 2]> = vec![[-1., 1.], [1., 1.], [0., 0.], [1., -1.], [-1., -1.], [0., 0.]];
+    let mut output = Vec::<Vec<_>>::new();
+    let result = polygon.triangulate(formats::IndexedFanFormat::new(&mut output));
+    assert!(matches!(result, Err(TriangulationError::TrapezoidationError(TrapezoidationError::DuplicateVertex { a: 2, b: 5 }))));
+}
+
+#[test]
+fn internal_error_seed() {
+    // A NaN coordinate breaks the trapezoidation without violating any precondition that can be diagnosed
+    let polygon: Vec<[f64; 2]> = vec![[0., 0.], [1., 0.], [0.5, f64::NAN], [1., 1.], [0., 1.]];
+    for seed in 0..4 {
+        let mut output = Vec::<Vec<_>>::new();
+        match polygon.triangulate_with_seed(formats::IndexedFanFormat::new(&mut output), seed) {
+            Err(TriangulationError::TrapezoidationError(TrapezoidationError::InternalError(error))) => assert_eq!(error.seed, Some(seed)),
+            result => panic!("Expected an InternalError, got {:?}", result.map(|_| ())),
+        }
+    }
+}
+
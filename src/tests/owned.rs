@@ -0,0 +1,108 @@
+use std::thread;
+
+use crate::{ListFormat, Location, OwnedTrapezoidation, PolygonList, formats::IndexedListFormat};
+
+use super::util;
+
+// Points on a grid over the bounding box of the polygons
+fn grid(polygon_list: &[Vec<[f32; 2]>]) -> Vec<[f32; 2]> {
+    let (mut min, mut max) = ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]);
+    for v in polygon_list.iter().flatten() {
+        min = [min[0].min(v[0]), min[1].min(v[1])];
+        max = [max[0].max(v[0]), max[1].max(v[1])];
+    }
+    (0..20).flat_map(|i| (0..20).map(move |j| [min[0] + (max[0] - min[0]) * (i as f32 + 0.5) / 20., min[1] + (max[1] - min[1]) * (j as f32 + 0.5) / 20.])).collect()
+}
+
+#[test]
+fn owned_matches_trapezoidation() {
+    for (seed, name) in ["malta.txt", "croatia.txt"].into_iter().enumerate() {
+        let polygon_list = util::load_country(name);
+        let seed = seed as u64;
+        let borrowed = polygon_list.trapezoidize_with_seed(seed).expect("Trapezoidation failed");
+        let owned = OwnedTrapezoidation::with_seed(&polygon_list, seed).expect("Trapezoidation failed");
+        assert_eq!(owned.rings(), &polygon_list[..]);
+        assert_eq!(owned.seed(), Some(seed));
+
+        let expected = borrowed.triangulate(IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format()).expect("Triangulation failed");
+        let triangles = owned.triangulate(IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format()).expect("Triangulation failed");
+        assert_eq!(triangles, expected, "{} triangulated differently", name);
+
+        let points = grid(&polygon_list);
+        assert_eq!(owned.trapezoidation().classify_points(&points).unwrap(), borrowed.classify_points(&points).unwrap());
+    }
+}
+
+#[test]
+fn owned_outlives_input() {
+    // Vertices keep their original indices, though the empty ring isn't copied
+    let polygon_list = vec![vec![], vec![[0f32, 0.], [10., 0.], [10., 10.], [0., 10.]], vec![[2., 2.], [2., 8.], [8., 8.], [8., 2.]]];
+    let expected = polygon_list.trapezoidize_with_seed(0).expect("Trapezoidation failed")
+        .triangulate(IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format()).expect("Triangulation failed");
+    let owned = OwnedTrapezoidation::with_seed(&polygon_list, 0).expect("Trapezoidation failed");
+    drop(polygon_list);
+    assert_eq!(owned.rings().len(), 2);
+    assert_eq!(owned.polygons().indices()[0], [[1, 0], [1, 1], [1, 2], [1, 3]]);
+
+    let triangles = thread::spawn(move || {
+        assert_eq!(owned.trapezoidation().locate(&[5f32, 5.]).unwrap(), Location::Outside);
+        assert_eq!(owned.trapezoidation().locate(&[1f32, 5.]).unwrap(), Location::Inside { ring: 0 });
+        assert_eq!(owned.trapezoidation().locate(&[2f32, 5.]).unwrap(), Location::OnEdge { ring: 1, edge: ([2, 0], [2, 1]) });
+        owned.triangulate(IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format()).expect("Triangulation failed")
+    }).join().unwrap();
+    assert_eq!(triangles, expected);
+}
+
+#[test]
+fn owned_custom_index() {
+    // Indices substituted with index_with are kept
+    let indexed = util::load_country("malta.txt").index_with::<usize, u32>();
+    let owned = OwnedTrapezoidation::with_seed(&indexed, 1).expect("Trapezoidation failed");
+    let expected = indexed.trapezoidize_with_seed(1).expect("Trapezoidation failed")
+        .triangulate(IndexedListFormat::new(Vec::<[u32; 2]>::new()).into_fan_format()).expect("Triangulation failed");
+    let triangles = owned.triangulate(IndexedListFormat::new(Vec::<[u32; 2]>::new()).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles, expected);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn owned_serde() {
+    let polygon_list = util::load_country("croatia.txt");
+    let owned = OwnedTrapezoidation::with_seed(&polygon_list, 3).expect("Trapezoidation failed");
+    let expected = owned.triangulate(IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format()).expect("Triangulation failed");
+
+    let json = serde_json::to_value(&owned).unwrap();
+    let deserialized: OwnedTrapezoidation<[f32; 2]> = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(deserialized.seed(), Some(3));
+    let triangles = deserialized.triangulate(IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles, expected);
+    let points = grid(&polygon_list);
+    assert_eq!(deserialized.trapezoidation().classify_points(&points).unwrap(), owned.trapezoidation().classify_points(&points).unwrap());
+
+    // Links out of bounds, columns of different lengths, and vertices which don't match the structure, are rejected
+    let mut corrupt = json.clone();
+    corrupt["parts"]["ts"]["sink"][0] = 1_000_000.into();
+    assert!(serde_json::from_value::<OwnedTrapezoidation<[f32; 2]>>(corrupt).is_err());
+    let mut corrupt = json.clone();
+    corrupt["parts"]["ts"]["left"].as_array_mut().unwrap().pop();
+    assert!(serde_json::from_value::<OwnedTrapezoidation<[f32; 2]>>(corrupt).is_err());
+
+    // Segments have no coordinates of their own, so they can't disagree with the nexuses
+    assert!(json["parts"]["ss"].get("coords").is_none());
+    let mut corrupt = json.clone();
+    corrupt["parts"]["ns"]["coords"][0] = serde_json::json!([1e6, 1e6]);
+    assert!(serde_json::from_value::<OwnedTrapezoidation<[f32; 2]>>(corrupt).is_err());
+    let mut corrupt = json.clone();
+    let ends = corrupt["parts"]["ss"]["nexuses"][0].as_array_mut().unwrap();
+    ends.swap(0, 1);
+    assert!(serde_json::from_value::<OwnedTrapezoidation<[f32; 2]>>(corrupt).is_err());
+    let mut corrupt = json.clone();
+    corrupt["polygons"]["rings"][0][0] = serde_json::json!([1e6, 1e6]);
+    assert!(serde_json::from_value::<OwnedTrapezoidation<[f32; 2]>>(corrupt).is_err());
+    let mut corrupt = json.clone();
+    corrupt["polygons"]["indices"][0].as_array_mut().unwrap().pop();
+    assert!(serde_json::from_value::<OwnedTrapezoidation<[f32; 2]>>(corrupt).is_err());
+    let mut corrupt = json;
+    corrupt["polygons"]["rings"].as_array_mut().unwrap().pop();
+    assert!(serde_json::from_value::<OwnedTrapezoidation<[f32; 2]>>(corrupt).is_err());
+}
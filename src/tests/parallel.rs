@@ -0,0 +1,82 @@
+use crate::{FanFormat, ListFormat, PolygonList, TrapezoidationError, TriangulationError, formats::IndexedListFormat, parallel::ring_groups};
+
+use super::util;
+
+fn load(names: &[&str]) -> Vec<Vec<[f32; 2]>> {
+    names.iter()
+        .flat_map(|name| util::load_country(name))
+        .collect()
+}
+
+fn area(polygon_list: &[Vec<[f32; 2]>], triangles: &[[usize; 2]]) -> f64 {
+    triangles.chunks_exact(3).map(|t| {
+        let [a, b, c] = [t[0], t[1], t[2]].map(|[r, i]| polygon_list[r][i].map(f64::from));
+        0.5 * ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]))
+    }).sum()
+}
+
+fn groups(polygon_list: &Vec<Vec<[f32; 2]>>) -> Vec<Vec<usize>> {
+    let indices: Vec<[usize; 2]> = polygon_list.iter().enumerate()
+        .flat_map(|(r, ring)| (0..ring.len()).map(move |i| [r, i]))
+        .collect();
+    let rings: Vec<_> = polygon_list.iter()
+        .scan(0, |start, ring| { *start += ring.len(); Some(*start - ring.len()..*start) })
+        .collect();
+    ring_groups(polygon_list, &indices, &rings).into_iter()
+        .map(|group| group.into_iter().map(|ring| indices[ring.start][0]).collect())
+        .collect()
+}
+
+#[test]
+fn ring_groups_nested() {
+    let polygon_list = vec![
+        vec![[0f32, 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[20., 0.], [30., 0.], [30., 10.], [20., 10.]],
+        vec![[2., 2.], [2., 8.], [8., 8.], [8., 2.]],
+        vec![[4., 4.], [6., 4.], [6., 6.], [4., 6.]],
+        // Disjoint boxes, bridged by a ring overlapping both
+        vec![[22., 20.], [24., 20.], [24., 22.], [22., 22.]],
+        vec![[26., 20.], [28., 20.], [28., 22.], [26., 22.]],
+        vec![[23., 23.], [23., 21.], [27., 21.], [27., 23.]],
+    ];
+    assert_eq!(groups(&polygon_list), vec![vec![0, 2, 3], vec![1], vec![4, 5, 6]]);
+}
+
+#[test]
+fn par_triangulate_matches_sequential() {
+    let polygon_list = load(&["croatia.txt", "greece.txt", "japan.txt"]);
+    assert!(groups(&polygon_list).len() > 3);
+
+    let mut parallel = Vec::<[usize; 2]>::new();
+    polygon_list.par_triangulate_with_seed(IndexedListFormat::new(&mut parallel).into_fan_format(), 3).expect("Triangulation failed");
+    let sequential = polygon_list.triangulate(IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(parallel.len(), sequential.len());
+    let (parallel_area, sequential_area) = (area(&polygon_list, &parallel), area(&polygon_list, &sequential));
+    assert!((parallel_area - sequential_area).abs() < 1e-6 * sequential_area.abs());
+
+    // Scheduling doesn't affect the output
+    for _ in 0..3 {
+        let again = polygon_list.par_triangulate_with_seed(IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format(), 3).expect("Triangulation failed");
+        assert_eq!(again, parallel);
+    }
+
+    // Winding follows the format, as for sequential triangulation
+    let reversed = polygon_list.par_triangulate_with_seed(IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format().reverse_winding(), 3).expect("Triangulation failed");
+    let sequential = polygon_list.triangulate(IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format().reverse_winding()).expect("Triangulation failed");
+    let (parallel_area, sequential_area) = (area(&polygon_list, &reversed), area(&polygon_list, &sequential));
+    assert!((parallel_area - sequential_area).abs() < 1e-6 * sequential_area.abs());
+}
+
+#[test]
+fn par_triangulate_errors() {
+    let empty: Vec<Vec<[f32; 2]>> = vec![vec![]];
+    let result = empty.par_triangulate(IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format());
+    assert!(matches!(result, Err(TriangulationError::NoVertices)));
+
+    // Earlier groups are removed from the output when a later one fails
+    let too_small = vec![vec![[0f32, 0.], [1., 0.], [0., 1.]], vec![[2., 2.], [3., 2.]]];
+    let mut triangles = vec![[9, 9]; 3];
+    let result = too_small.par_triangulate(IndexedListFormat::new(&mut triangles).into_fan_format());
+    assert!(matches!(result, Err(TriangulationError::TrapezoidationError(TrapezoidationError::ShortRing { ring: 1, ref vertices })) if *vertices == [[1, 0], [1, 1]]));
+    assert_eq!(triangles, vec![[9, 9]; 3]);
+}
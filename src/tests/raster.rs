@@ -0,0 +1,218 @@
+use std::fs;
+
+use crate::{Location, Polygon, PolygonList, SdfError};
+
+use super::util;
+
+const IDENTITY: [[f32; 3]; 2] = [[1., 0., 0.], [0., 1., 0.]];
+
+fn rows(coverage: &[u8], width: usize) -> Vec<&[u8]> {
+    coverage.chunks(width).collect()
+}
+
+// Maps the bounding box of the polygons onto the image, with y pointing down
+fn fit_transform(polygon_list: &[Vec<[f32; 2]>], width: usize, height: usize) -> [[f32; 3]; 2] {
+    let vertices = polygon_list.iter().flatten();
+    let min = vertices.clone().fold([f32::MAX; 2], |min, v| [min[0].min(v[0]), min[1].min(v[1])]);
+    let max = vertices.fold([f32::MIN; 2], |max, v| [max[0].max(v[0]), max[1].max(v[1])]);
+    let scale = (width as f32 / (max[0] - min[0])).min(height as f32 / (max[1] - min[1]));
+    [[scale, 0., -min[0] * scale], [0., -scale, max[1] * scale]]
+}
+
+#[allow(clippy::type_complexity)]
+fn countries_raster(name: &str, width: usize, height: usize) -> (Vec<Vec<[f32; 2]>>, [[f32; 3]; 2], Vec<u8>) {
+    let polygon_list = util::load_polygon_list(util::countries_path().join(name).to_str().unwrap()).unwrap();
+    let transform = fit_transform(&polygon_list, width, height);
+    let coverage = polygon_list.trapezoidize().expect("Trapezoidation failed").rasterize(width, height, transform).unwrap();
+    (polygon_list, transform, coverage)
+}
+
+#[test]
+fn rasterize_aligned() {
+    let square: Vec<[f32; 2]> = vec![[1., 1.], [3., 1.], [3., 3.], [1., 3.]];
+    let coverage = square.trapezoidize().unwrap().rasterize(4, 4, IDENTITY).unwrap();
+    assert_eq!(rows(&coverage, 4), [
+        [0, 0, 0, 0],
+        [0, 255, 255, 0],
+        [0, 255, 255, 0],
+        [0, 0, 0, 0],
+    ]);
+}
+
+#[test]
+fn rasterize_partial() {
+    let square: Vec<[f32; 2]> = vec![[0.5, 0.5], [2.5, 0.5], [2.5, 2.5], [0.5, 2.5]];
+    let coverage = square.trapezoidize().unwrap().rasterize(3, 3, IDENTITY).unwrap();
+    assert_eq!(rows(&coverage, 3), [
+        [64, 128, 64],
+        [128, 255, 128],
+        [64, 128, 64],
+    ]);
+
+    let triangle: Vec<[f32; 2]> = vec![[0., 0.], [4., 0.], [0., 4.]];
+    let coverage = triangle.trapezoidize().unwrap().rasterize(4, 4, IDENTITY).unwrap();
+    assert_eq!(rows(&coverage, 4), [
+        [255, 255, 255, 128],
+        [255, 255, 128, 0],
+        [255, 128, 0, 0],
+        [128, 0, 0, 0],
+    ]);
+}
+
+#[test]
+fn rasterize_transformed() {
+    // Partially outside the image, and flipped vertically
+    let square: Vec<[f32; 2]> = vec![[-1., -1.], [2., -1.], [2., 2.], [-1., 2.]];
+    let coverage = square.trapezoidize().unwrap().rasterize(4, 4, [[1., 0., 0.], [0., -1., 4.]]).unwrap();
+    assert_eq!(rows(&coverage, 4), [
+        [0, 0, 0, 0],
+        [0, 0, 0, 0],
+        [255, 255, 0, 0],
+        [255, 255, 0, 0],
+    ]);
+
+    // A rotation by 45 degrees around the center of a 2x2 square covers exactly half of each pixel
+    let square: Vec<[f32; 2]> = vec![[-1., -1.], [1., -1.], [1., 1.], [-1., 1.]];
+    let (s, c) = std::f32::consts::FRAC_PI_4.sin_cos();
+    let scale = std::f32::consts::SQRT_2;
+    let coverage = square.trapezoidize().unwrap().rasterize(2, 2, [[c * scale / 2., -s * scale / 2., 1.], [s * scale / 2., c * scale / 2., 1.]]).unwrap();
+    assert!(coverage.iter().all(|&value| value.abs_diff(128) <= 1), "{:?}", coverage);
+}
+
+#[test]
+fn rasterize_holes() {
+    let nested_squares = vec![
+        vec![[0., 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[2., 2.], [2., 8.], [8., 8.], [8., 2.]],
+        vec![[4., 4.], [6., 4.], [6., 6.], [4., 6.]],
+    ];
+    let coverage = nested_squares.trapezoidize().unwrap().rasterize(10, 10, IDENTITY).unwrap();
+    let expected: Vec<u8> = (0..10).flat_map(|y| (0..10).map(move |x| {
+        let ring = [x, y, 9 - x, 9 - y].into_iter().min().unwrap();
+        if (2..4).contains(&ring) { 0 } else { 255 }
+    })).collect();
+    assert_eq!(coverage, expected);
+}
+
+#[test]
+fn rasterize_supersampled() {
+    const SAMPLES: usize = 8;
+    let (width, height) = (48, 48);
+    let (polygon_list, transform, coverage) = countries_raster("cuba.txt", width, height);
+
+    // Count the samples within each pixel which are inside the polygons, using the even-odd rule
+    let [[a, b, c], [d, e, f]] = transform;
+    let det = a * e - b * d;
+    let mut total_error = 0.;
+    for (i, &value) in coverage.iter().enumerate() {
+        let (px, py) = ((i % width) as f32, (i / width) as f32);
+        let mut inside = 0;
+        for sy in 0..SAMPLES {
+            for sx in 0..SAMPLES {
+                let (x, y) = (px + (sx as f32 + 0.5) / SAMPLES as f32 - c, py + (sy as f32 + 0.5) / SAMPLES as f32 - f);
+                let p = [(e * x - b * y) / det, (a * y - d * x) / det];
+                let mut crossings = false;
+                for ring in &polygon_list {
+                    for (v, w) in ring.iter().zip(ring.iter().cycle().skip(1)) {
+                        if (v[1] > p[1]) != (w[1] > p[1]) && p[0] < v[0] + (p[1] - v[1]) / (w[1] - v[1]) * (w[0] - v[0]) {
+                            crossings = !crossings;
+                        }
+                    }
+                }
+                inside += crossings as usize;
+            }
+        }
+        let expected = inside as f32 / (SAMPLES * SAMPLES) as f32 * 255.;
+        let error = (value as f32 - expected).abs();
+        assert!(error < 64., "Pixel {} has coverage {}, expected about {}", i, value, expected);
+        total_error += error;
+    }
+    assert!(total_error / (coverage.len() as f32) < 2., "Mean error {}", total_error / coverage.len() as f32);
+}
+
+#[test]
+fn rasterize_golden() {
+    let (width, height) = (64, 64);
+    let (_, _, coverage) = countries_raster("belgium.txt", width, height);
+
+    // A binary PGM image
+    let golden = fs::read(util::resources_path().join("raster").join("belgium.pgm")).unwrap();
+    let header = format!("P5\n{} {}\n255\n", width, height);
+    assert!(golden.starts_with(header.as_bytes()));
+    let golden = &golden[header.len()..];
+    assert_eq!(golden.len(), coverage.len());
+    for (i, (&value, &expected)) in coverage.iter().zip(golden).enumerate() {
+        assert!(value.abs_diff(expected) <= 1, "Pixel {} has coverage {}, expected {}", i, value, expected);
+    }
+}
+
+
+#[test]
+fn signed_distance_square() {
+    let square: Vec<[f32; 2]> = vec![[0., 0.], [10., 0.], [10., 10.], [0., 10.]];
+    let traps = square.trapezoidize().unwrap();
+    // Cell (i, j) is centered on (i - 1, j - 1)
+    let transform = [[1., 0., 1.5], [0., 1., 1.5]];
+    let field = traps.signed_distance_field(14, 14, transform, 100.).unwrap();
+    for (k, &value) in field.iter().enumerate() {
+        let [x, y] = [(k % 14) as f32 - 1., (k / 14) as f32 - 1.];
+        let outside = [(-x).max(x - 10.).max(0.), (-y).max(y - 10.).max(0.)];
+        let expected = if outside == [0., 0.] {
+            -x.min(10. - x).min(y).min(10. - y)
+        } else {
+            outside[0].hypot(outside[1])
+        };
+        assert_eq!(value, expected, "Unexpected distance at {:?}", [x, y]);
+    }
+
+    // Distances are clamped
+    let field = traps.signed_distance_field(14, 14, transform, 2.).unwrap();
+    assert_eq!(field[0], 2f32.sqrt());
+    assert_eq!(field[14 * 14 - 1], 2.);
+    assert_eq!(field[7 * 14 + 7], -2.);
+    assert_eq!(field[2 * 14 + 2], -1.);
+}
+
+#[test]
+fn signed_distance_polygon_list() {
+    let rings: Vec<Vec<[f32; 2]>> = vec![
+        vec![[0., 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[3., 3.], [3., 7.], [7., 7.], [7., 3.]],
+    ];
+    let transform = [[1., 0., 1.5], [0., 1., 1.5]];
+    let expected = rings.trapezoidize().unwrap().signed_distance_field(14, 14, transform, 100.).unwrap();
+    assert_eq!(rings.signed_distance_field(14, 14, transform, 100.).unwrap(), expected);
+
+    // Every point maps onto a single line
+    let singular = [[1., 2., 0.], [2., 4., 0.]];
+    assert!(matches!(rings.signed_distance_field(14, 14, singular, 100.), Err(SdfError::NonInvertibleTransform)));
+}
+
+#[test]
+fn signed_distance_geography() {
+    fn segment_distance(p: [f32; 2], a: [f32; 2], b: [f32; 2]) -> f32 {
+        let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+        let t = (((p[0] - a[0]) * dx + (p[1] - a[1]) * dy) / (dx * dx + dy * dy)).clamp(0., 1.);
+        (p[0] - a[0] - dx * t).hypot(p[1] - a[1] - dy * t)
+    }
+
+    let polygon_list = util::load_polygon_list(util::countries_path().join("cuba.txt").to_str().unwrap()).unwrap();
+    let traps = polygon_list.trapezoidize().unwrap();
+    let (width, height) = (48, 24);
+    let transform = fit_transform(&polygon_list, width, height);
+    let max_distance = 0.5;
+    let field = traps.signed_distance_field(width, height, transform, max_distance).unwrap();
+
+    let [[m00, _, m02], [_, m11, m12]] = transform;
+    for (k, &value) in field.iter().enumerate() {
+        let p = [((k % width) as f32 + 0.5 - m02) / m00, ((k / width) as f32 + 0.5 - m12) / m11];
+        let expected = polygon_list.iter()
+            .flat_map(|ring| ring.iter().zip(ring.iter().cycle().skip(1)))
+            .map(|(a, b)| segment_distance(p, *a, *b))
+            .fold(max_distance, f32::min);
+        let sign = if matches!(traps.locate(&p).unwrap(), Location::Inside { .. }) { -1. } else { 1. };
+        assert!((value - sign * expected).abs() <= 1e-4, "{:?}: distance {}, expected {}", p, value, sign * expected);
+    }
+    assert!(field.iter().any(|&value| value < 0.));
+    assert!(field.contains(&max_distance));
+}
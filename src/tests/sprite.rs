@@ -0,0 +1,109 @@
+use crate::sprite::{AlphaMask, SpriteMesh};
+
+fn mask_from_rows(rows: &[&str]) -> (Vec<u8>, usize, usize) {
+    let width = rows[0].len();
+    let data = rows.iter()
+        .flat_map(|row| row.bytes().map(|b| if b == b'#' { 255 } else { 0 }))
+        .collect();
+    (data, width, rows.len())
+}
+
+fn mesh_area(mesh: &SpriteMesh) -> f32 {
+    mesh.indices.chunks_exact(3).map(|t| {
+        let [a, b, c] = [t[0], t[1], t[2]].map(|i| mesh.positions[i as usize]);
+        ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs() / 2.
+    }).sum()
+}
+
+#[test]
+fn empty_mask() {
+    let data = [0; 16];
+    let mask = AlphaMask::new(&data, 4, 4);
+    assert!(mask.contours(127).is_empty());
+    assert_eq!(mask.mesh(127, 0.5).unwrap(), SpriteMesh::default());
+}
+
+#[test]
+fn square() {
+    let (data, width, height) = mask_from_rows(&[
+        "......",
+        ".####.",
+        ".####.",
+        ".####.",
+        ".####.",
+        "......",
+    ]);
+    let mask = AlphaMask::new(&data, width, height);
+    let contours = mask.contours(127);
+    assert_eq!(contours.len(), 1);
+    assert!(!contours[0].is_hole);
+
+    let mesh = mask.mesh(127, 0.25).unwrap();
+    assert_eq!(mesh.positions.len(), mesh.uvs.len());
+    assert_eq!(mesh.indices.len() % 3, 0);
+    assert!(mesh.indices.iter().all(|&i| (i as usize) < mesh.positions.len()));
+    for (position, uv) in mesh.positions.iter().zip(mesh.uvs.iter()) {
+        assert_eq!(uv[0], position[0] / width as f32);
+        assert_eq!(uv[1], position[1] / height as f32);
+    }
+    // The outline passes halfway between pixel centers, cutting off the corners
+    let area = mesh_area(&mesh);
+    assert!((area - 15.5).abs() < 0.01, "Unexpected area {}", area);
+}
+
+#[test]
+fn ring_with_hole() {
+    let (data, width, height) = mask_from_rows(&[
+        ".......",
+        ".#####.",
+        ".#####.",
+        ".##.##.",
+        ".#####.",
+        ".#####.",
+        ".......",
+    ]);
+    let mask = AlphaMask::new(&data, width, height);
+    let contours = mask.contours(127);
+    assert_eq!(contours.len(), 2);
+    assert_eq!(contours.iter().filter(|c| c.is_hole).count(), 1);
+
+    let mesh = mask.mesh(127, 0.).unwrap();
+    let hole = &contours.iter().find(|c| c.is_hole).unwrap().points;
+    let outer = &contours.iter().find(|c| !c.is_hole).unwrap().points;
+    let expected = shoelace(outer) + shoelace(hole);
+    assert!((mesh_area(&mesh) - expected).abs() < 0.01);
+    assert!(shoelace(hole) < 0.);
+}
+
+#[test]
+fn separate_blobs() {
+    let (data, width, height) = mask_from_rows(&[
+        "........",
+        ".##..##.",
+        ".##..##.",
+        "........",
+        "...##...",
+        "...##...",
+        "........",
+    ]);
+    let mask = AlphaMask::new(&data, width, height);
+    assert_eq!(mask.contours(127).len(), 3);
+    let mesh = mask.mesh(127, 0.).unwrap();
+    assert_eq!(mesh.indices.len() / 3, 3 * (8 - 2));
+}
+
+#[test]
+#[should_panic]
+fn wrong_size() {
+    let data = [0; 15];
+    AlphaMask::new(&data, 4, 4);
+}
+
+fn shoelace(points: &[[f32; 2]]) -> f32 {
+    let mut area = 0.;
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+        area += a[0] * b[1] - b[0] * a[1];
+    }
+    area / 2.
+}
@@ -52,3 +52,43 @@ fn regular_polygons() {
         std::hint::black_box(output);
     }
 }
+
+#[test]
+fn triangulate_with_seed() {
+    let polygon_list = util::load_polygon_list(util::countries_path().join("belgium.txt").to_str().unwrap()).unwrap();
+    for seed in [0, 1, u64::MAX] {
+        let mut a = Vec::<[usize; 2]>::new();
+        polygon_list.triangulate_with_seed(formats::IndexedListFormat::new(&mut a).into_fan_format(), seed).expect("Triangulation failed");
+        let mut b = Vec::<[usize; 2]>::new();
+        polygon_list.triangulate_with_seed(formats::IndexedListFormat::new(&mut b).into_fan_format(), seed).expect("Triangulation failed");
+        assert_eq!(a, b);
+    }
+
+    let traps = polygon_list.trapezoidize().expect("Trapezoidation failed");
+    let seed = traps.seed().expect("Default trapezoidation should be seeded");
+    let mut a = Vec::<[usize; 2]>::new();
+    traps.triangulate(formats::IndexedListFormat::new(&mut a).into_fan_format()).expect("Triangulation failed");
+    let mut b = Vec::<[usize; 2]>::new();
+    polygon_list.triangulate_with_seed(formats::IndexedListFormat::new(&mut b).into_fan_format(), seed).expect("Triangulation failed");
+    assert_eq!(a, b);
+}
+
+#[test]
+fn triangulate_many_rings() {
+    // A square with a grid of square holes, each containing a triangular island
+    let mut polygon_list = vec![vec![[0f64, 0.], [100., 0.], [100., 100.], [0., 100.]]];
+    for i in 0..10 {
+        for j in 0..10 {
+            let (x, y) = (i as f64 * 10. + 1., j as f64 * 10. + 1.);
+            polygon_list.push(vec![[x, y], [x, y + 3.], [x + 3., y + 3.], [x + 3., y]]);
+            polygon_list.push(vec![[x + 1., y + 1.], [x + 2., y + 1.], [x + 1.5, y + 2.]]);
+        }
+    }
+
+    for seed in 0..4 {
+        let mut output = Vec::<[usize; 2]>::new();
+        polygon_list.triangulate_with_seed(formats::IndexedListFormat::new(&mut output).into_fan_format(), seed).expect("Triangulation failed");
+        // The outer region has 404 vertices and 100 holes, and each island is a single triangle
+        assert_eq!(output.len(), (404 + 2 * 100 - 2 + 100) * 3);
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{formats, ListFormat, PolygonList, TrapezoidationError, TriangulationError, Triangulator};
+
+use super::util;
+
+#[test]
+fn triangulator_matches_polygon_list() {
+    let mut triangulator = Triangulator::new();
+    // Alternate between large and small inputs, so buffers are reused both with and without spare capacity
+    for (seed, name) in ["croatia.txt", "malta.txt", "greece.txt", "belgium.txt"].into_iter().enumerate() {
+        let polygon_list = util::load_polygon_list(util::countries_path().join(name).to_str().unwrap()).unwrap();
+        let seed = seed as u64;
+
+        let mut reused = Vec::<[usize; 2]>::new();
+        triangulator.triangulate_with_seed(&polygon_list, formats::IndexedListFormat::new(&mut reused).into_fan_format(), seed).expect("Triangulation failed");
+        let mut fresh = Vec::<[usize; 2]>::new();
+        polygon_list.triangulate_with_seed(formats::IndexedListFormat::new(&mut fresh).into_fan_format(), seed).expect("Triangulation failed");
+        assert_eq!(reused, fresh, "{} triangulated differently", name);
+    }
+}
+
+#[test]
+fn triangulator_after_error() {
+    let mut triangulator = Triangulator::new();
+    let too_small = vec![vec![[0f32, 0.], [1., 0.], [0., 1.]], vec![[2., 2.], [3., 2.]]];
+    let result = triangulator.triangulate(&too_small, formats::IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format());
+    assert!(matches!(result, Err(TriangulationError::TrapezoidationError(TrapezoidationError::ShortRing { ring: 1, ref vertices })) if *vertices == [[1, 0], [1, 1]]));
+
+    let square = vec![vec![[0f32, 0.], [1., 0.], [1., 1.], [0., 1.]]];
+    let triangles = triangulator.triangulate(&square, formats::IndexedListFormat::new(Vec::<[usize; 2]>::new()).into_fan_format()).expect("Triangulation failed");
+    assert_eq!(triangles.len(), 6);
+}
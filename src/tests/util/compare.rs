@@ -0,0 +1,23 @@
+//! Comparisons between triangulations
+
+use crate::{ListFormat, PolygonList, Strategy, formats::IndexedListFormat};
+
+/// The signed area of each triangle, positive for counterclockwise triangles
+pub fn areas(polygon_list: &[Vec<[f32; 2]>], triangles: &[[[usize; 2]; 3]]) -> Vec<f64> {
+    triangles.iter().map(|t| {
+        let [a, b, c] = t.map(|[r, i]| polygon_list[r][i].map(f64::from));
+        0.5 * ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1]))
+    }).collect()
+}
+
+/// Compare the triangulation by `strategy` with Seidel's, which covers the same area with the same number of triangles
+pub fn check_strategy(polygon_list: &Vec<Vec<[f32; 2]>>, strategy: Strategy) {
+    let triangles = polygon_list.triangulate_with_strategy(IndexedListFormat::new(Vec::<[[usize; 2]; 3]>::new()).into_fan_format(), strategy).expect("Triangulation failed");
+    let seidel = polygon_list.triangulate_with_strategy(IndexedListFormat::new(Vec::<[[usize; 2]; 3]>::new()).into_fan_format(), Strategy::Seidel).expect("Triangulation failed");
+    assert_eq!(triangles.len(), seidel.len());
+
+    let triangle_areas = areas(polygon_list, &triangles);
+    assert!(triangle_areas.iter().all(|&area| area >= 0.), "{:?} triangles must be counterclockwise", strategy);
+    let (area, seidel_area) = (triangle_areas.iter().sum::<f64>(), areas(polygon_list, &seidel).iter().sum::<f64>());
+    assert!((area - seidel_area).abs() < 1e-6 * seidel_area.abs(), "{} != {}", area, seidel_area);
+}
@@ -1,12 +1,23 @@
 //! Testing and benchmark utilities
 
+pub mod compare;
 pub mod polygon;
 mod load_polygon_list;
 use std::{env, path};
 
 pub use load_polygon_list::load_polygon_list;
 
+/// Returns the directory containing test resources
+pub fn resources_path() -> path::PathBuf {
+    path::Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("resources")
+}
+
 /// Returns a directory containing sample polygon lists
 pub fn countries_path() -> path::PathBuf {
-    path::Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("resources").join("geometry").join("countries")
-}
\ No newline at end of file
+    resources_path().join("geometry").join("countries")
+}
+
+/// Loads a sample polygon list from [countries_path]
+pub fn load_country(name: &str) -> Vec<Vec<[f32; 2]>> {
+    load_polygon_list(countries_path().join(name).to_str().unwrap()).unwrap()
+}
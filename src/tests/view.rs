@@ -0,0 +1,52 @@
+use crate::{Location, PolygonList, TrapezoidView};
+
+use super::util;
+
+// A point in the interior of a bounded trapezoid
+fn interior_point(polygon_list: &[Vec<[f32; 2]>], t: &TrapezoidView<Vec<Vec<[f32; 2]>>>) -> Option<[f32; 2]> {
+    let vertex = |[ring, i]: [usize; 2]| polygon_list[ring][i];
+    let (bottom, top) = (vertex(t.bottom()?), vertex(t.top()?));
+    if bottom[1] == top[1] {
+        return None;
+    }
+    let y = (bottom[1] + top[1]) / 2.;
+    let x_at = |(a, b): ([usize; 2], [usize; 2])| {
+        let (a, b) = (vertex(a), vertex(b));
+        a[0] + (b[0] - a[0]) * (y - a[1]) / (b[1] - a[1])
+    };
+    Some([(x_at(t.left()?) + x_at(t.right()?)) / 2., y])
+}
+
+#[test]
+fn trapezoid_views() {
+    let nested_squares = vec![
+        vec![[0., 0.], [10., 0.], [10., 10.], [0., 10.]],
+        vec![[2., 2.], [2., 8.], [8., 8.], [8., 2.]],
+        vec![[4., 4.], [6., 4.], [6., 6.], [4., 6.]],
+    ];
+    for polygon_list in [nested_squares, util::load_polygon_list(util::countries_path().join("cuba.txt").to_str().unwrap()).unwrap()] {
+        let traps = polygon_list.trapezoidize().expect("Trapezoidation failed");
+        let views: Vec<_> = traps.trapezoids().unwrap().collect();
+        assert!(views.iter().any(|t| t.is_inside()));
+
+        for (i, t) in views.iter().enumerate() {
+            assert_eq!(t.id().index(), i);
+            assert_eq!(traps.trapezoid(t.id()).unwrap().id(), t.id());
+
+            for neighbour in t.neighbours() {
+                let neighbour = &views[neighbour.index()];
+                assert!(neighbour.neighbours().any(|id| id == t.id()), "{:?} is not a neighbour of {:?}", t.id(), neighbour.id());
+                assert_eq!(neighbour.is_inside(), t.is_inside());
+                assert_eq!(neighbour.ring(), t.ring());
+            }
+
+            if let Some(p) = interior_point(&polygon_list, t) {
+                match traps.locate(&p).unwrap() {
+                    Location::Inside { ring } => assert_eq!(t.ring(), Some(ring)),
+                    Location::Outside => assert!(!t.is_inside()),
+                    location => panic!("{:?} is on the boundary: {:?}", p, location),
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use crate::{Vertex, VertexIndex, idx::{Idx, IdxDisplay}, nexus::Nexus, querynode::QueryNode, segment::Segment};
+use crate::{Vertex, VertexIndex, idx::{Column, Idx, IdxDisplay}, nexus::Nexus, querynode::QueryNode, segment::Segment};
 
 #[derive(Debug)]
 pub(crate) struct Trapezoid<V: Vertex, Index: VertexIndex> {
@@ -11,6 +11,14 @@ pub(crate) struct Trapezoid<V: Vertex, Index: VertexIndex> {
     sink: Idx<QueryNode<V, Index>>,
 }
 
+impl<V: Vertex, Index: VertexIndex> Clone for Trapezoid<V, Index> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> Copy for Trapezoid<V, Index> { }
+
 impl<V: Vertex, Index: VertexIndex> std::fmt::Display for Trapezoid<V, Index> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if let Some(up) = self.up {
@@ -78,27 +86,127 @@ impl<V: Vertex, Index: VertexIndex> Trapezoid<V, Index> {
         t_up
     }
 
-    pub fn set_down(&mut self, ni: Idx<Nexus<V, Index>>) {
-        self.down = Some(ni);
+    pub fn left(&self) -> Option<Idx<Segment<V, Index>>> { self.left }
+    pub fn right(&self) -> Option<Idx<Segment<V, Index>>> { self.right }
+
+    pub fn up(&self) -> Option<Idx<Nexus<V, Index>>> { self.up }
+    pub fn down(&self) -> Option<Idx<Nexus<V, Index>>> { self.down }
+    
+    pub fn sink(&self) -> Idx<QueryNode<V, Index>> { self.sink }
+}
+
+/// The trapezoids of a trapezoidation, stored as a structure of arrays.
+///
+/// Each [Trapezoid] is read and written whole by value, and the links used on their own, such as the sinks, can be set directly.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound = ""))]
+#[allow(clippy::type_complexity)]
+pub(crate) struct Trapezoids<V: Vertex, Index: VertexIndex> {
+    left: Column<Trapezoid<V, Index>, Option<Idx<Segment<V, Index>>>>,
+    right: Column<Trapezoid<V, Index>, Option<Idx<Segment<V, Index>>>>,
+    down: Column<Trapezoid<V, Index>, Option<Idx<Nexus<V, Index>>>>,
+    up: Column<Trapezoid<V, Index>, Option<Idx<Nexus<V, Index>>>>,
+    sink: Column<Trapezoid<V, Index>, Idx<QueryNode<V, Index>>>,
+}
+
+impl<V: Vertex, Index: VertexIndex> Trapezoids<V, Index> {
+    pub fn len(&self) -> usize {
+        self.sink.len()
+    }
+
+    #[inline]
+    pub fn get(&self, ti: Idx<Trapezoid<V, Index>>) -> Trapezoid<V, Index> {
+        Trapezoid { left: self.left[ti], right: self.right[ti], down: self.down[ti], up: self.up[ti], sink: self.sink[ti] }
     }
 
-    pub fn set_left(&mut self, si: Idx<Segment<V, Index>>) {
-        self.left = Some(si);
+    pub fn set(&mut self, ti: Idx<Trapezoid<V, Index>>, t: Trapezoid<V, Index>) {
+        self.left[ti] = t.left;
+        self.right[ti] = t.right;
+        self.down[ti] = t.down;
+        self.up[ti] = t.up;
+        self.sink[ti] = t.sink;
     }
 
-    pub fn set_right(&mut self, si: Idx<Segment<V, Index>>) {
-        self.right = Some(si);
+    pub fn push(&mut self, t: Trapezoid<V, Index>) {
+        self.left.push(t.left);
+        self.right.push(t.right);
+        self.down.push(t.down);
+        self.up.push(t.up);
+        self.sink.push(t.sink);
     }
 
-    pub fn set_sink(&mut self, qi: Idx<QueryNode<V, Index>>) {
-        self.sink = qi;
+    pub fn next_index(&self) -> Idx<Trapezoid<V, Index>> {
+        Idx::new(self.len())
     }
 
-    pub fn left(&self) -> Option<Idx<Segment<V, Index>>> { self.left }
-    pub fn right(&self) -> Option<Idx<Segment<V, Index>>> { self.right }
+    pub fn set_down(&mut self, ti: Idx<Trapezoid<V, Index>>, ni: Idx<Nexus<V, Index>>) {
+        self.down[ti] = Some(ni);
+    }
 
-    pub fn up(&self) -> Option<Idx<Nexus<V, Index>>> { self.up }
-    pub fn down(&self) -> Option<Idx<Nexus<V, Index>>> { self.down }
-    
-    pub fn sink(&self) -> Idx<QueryNode<V, Index>> { self.sink }
-}
\ No newline at end of file
+    pub fn set_left(&mut self, ti: Idx<Trapezoid<V, Index>>, si: Idx<Segment<V, Index>>) {
+        self.left[ti] = Some(si);
+    }
+
+    pub fn set_right(&mut self, ti: Idx<Trapezoid<V, Index>>, si: Idx<Segment<V, Index>>) {
+        self.right[ti] = Some(si);
+    }
+
+    pub fn set_sink(&mut self, ti: Idx<Trapezoid<V, Index>>, qi: Idx<QueryNode<V, Index>>) {
+        self.sink[ti] = qi;
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Trapezoid<V, Index>> + '_ {
+        self.iter_index().map(|ti| self.get(ti))
+    }
+
+    pub fn iter_index(&self) -> impl ExactSizeIterator<Item = Idx<Trapezoid<V, Index>>> {
+        (0..self.len()).map(Idx::new)
+    }
+
+    /// Whether every column has a value for each trapezoid, which deserialized trapezoids need not
+    #[cfg(feature = "serde")]
+    pub fn columns_match(&self) -> bool {
+        [self.left.len(), self.right.len(), self.down.len(), self.up.len()].iter().all(|&len| len == self.len())
+    }
+
+    pub fn clear(&mut self) {
+        self.left.clear();
+        self.right.clear();
+        self.down.clear();
+        self.up.clear();
+        self.sink.clear();
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.left.reserve(additional);
+        self.right.reserve(additional);
+        self.down.reserve(additional);
+        self.up.reserve(additional);
+        self.sink.reserve(additional);
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.left.shrink_to_fit();
+        self.right.shrink_to_fit();
+        self.down.shrink_to_fit();
+        self.up.shrink_to_fit();
+        self.sink.shrink_to_fit();
+    }
+
+    /// The bytes allocated for the trapezoids
+    #[cfg(test)]
+    pub fn heap_bytes(&self) -> usize {
+        self.left.heap_bytes() + self.right.heap_bytes() + self.down.heap_bytes() + self.up.heap_bytes() + self.sink.heap_bytes()
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> Default for Trapezoids<V, Index> {
+    fn default() -> Self {
+        Self { left: Column::new(), right: Column::new(), down: Column::new(), up: Column::new(), sink: Column::new() }
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> Clone for Trapezoids<V, Index> {
+    fn clone(&self) -> Self {
+        Self { left: self.left.clone(), right: self.right.clone(), down: self.down.clone(), up: self.up.clone(), sink: self.sink.clone() }
+    }
+}
@@ -1,47 +1,47 @@
-use std::iter;
+use std::{borrow::Cow, cmp::Ordering, collections::HashSet, convert::Infallible, iter, sync::{OnceLock, atomic::{self, AtomicBool}}};
 
-use rand::prelude::SliceRandom;
+use rand::{Rng, prelude::SliceRandom};
+use num_traits::{NumCast, ToPrimitive, Zero};
 use zot::Ot;
-use crate::{FanFormat, FanBuilderState, PolygonList, PolygonListExt, PolygonElement, Vertex, VertexIndex, errors::{TriangulationError, InternalError, TrapezoidationError}, idx::{Idx, VecExt, SliceExt}, math::{math_n, is_left_of_line}, monotone::MonotoneBuilder, nexus::{FinalNexusType, Nexus, DividerDirection}, querynode::{QueryNode, QueryNodeBranch}, segment::Segment, trapezoid::Trapezoid, Coords, FanBuilder};
+use crate::{FanError, FanFormat, FanResult, TrapezoidFormat, TrapezoidBuilder, Real, FanBuilderState, PolygonList, hatch::{HatchError, HatchSpan}, PolygonListExt, PolygonElement, Vertex, VertexIndex, errors::{TriangulationError, InternalError, SdfError, TrapezoidationError}, location::{self, EdgeHit, Face, HorizontalDirection, Location}, idx::{Idx, VecExt}, math::{self, math_n}, monotone::MonotoneBuilder, nexus::{FinalNexusType, Nexus, Nexuses, DividerDirection}, querynode::{QueryNode, QueryNodeBranch}, raster::CoverageBuilder, segment::{Segment, Segments}, trapezoid::{Trapezoid, Trapezoids}, view::{TrapezoidId, TrapezoidView}, Coords, VertexExt};
 
 #[cfg(feature = "_debugging")]
 use std::fmt;
 #[cfg(feature = "_debugging")]
-use crate::{debug, monotone::Monotone, VertexExt};
-#[cfg(feature = "_debugging")]
-use num_traits::ToPrimitive;
+use crate::{debug, monotone::Monotone};
 
-trait TrapezoidationStructure<'p, P: PolygonList<'p> + ?Sized + 'p> {
+pub(crate) trait TrapezoidationStructure<'p, P: PolygonList<'p> + ?Sized + 'p> {
+    // Kept alongside the other accessors, though nothing reads it yet
+    #[allow(dead_code)]
     fn ps(&self) -> PolygonListExt<'p, P>;
-    fn ns(&self) -> &[Nexus<P::Vertex, P::Index>];
-    fn ss(&self) -> &[Segment<P::Vertex, P::Index>];
-    fn ts(&self) -> &[Trapezoid<P::Vertex, P::Index>];
+    fn ns(&self) -> &Nexuses<P::Vertex, P::Index>;
+    fn ss(&self) -> &Segments<P::Vertex, P::Index>;
+    fn ts(&self) -> &Trapezoids<P::Vertex, P::Index>;
     fn qs(&self) -> &[QueryNode<P::Vertex, P::Index>];
 
     fn query_node_root(&self) -> Idx<QueryNode<P::Vertex, P::Index>> {
         Idx::new(0)
     }
 
+    #[allow(clippy::type_complexity)]
     fn find_trapezoid(&self, c: Coords<<P::Vertex as Vertex>::Coordinate>) -> (Idx<QueryNode<P::Vertex, P::Index>>, Idx<Trapezoid<P::Vertex, P::Index>>) {
         self.find_trapezoid_from_root(c, self.query_node_root())
     }
 
     #[inline(never)]
+    #[allow(clippy::type_complexity)]
     fn find_trapezoid_from_root(&self, c: Coords<<P::Vertex as Vertex>::Coordinate>, qi_root: Idx<QueryNode<P::Vertex, P::Index>>) -> (Idx<QueryNode<P::Vertex, P::Index>>, Idx<Trapezoid<P::Vertex, P::Index>>) {
         let mut qi = qi_root;
         loop {
             // unsafe: `qs` is append-only and `Idx`s are never modified, so they will always remain valid within the same trapezoidation
             match unsafe { self.qs().get_unchecked(qi.usize()) } {
                 QueryNode::Branch(left, right, branch) => {
+                    // Branches only refer to segments and nexuses which were inserted before them, so the unchecked lookups are in bounds
                     let use_left = match *branch {
                         // The right trapezoid will be chosen if the vertex is one of the edge's endpoints
-                        QueryNodeBranch::X(c_min_x, c_max_x) => is_left_of_line(c_min_x, c_max_x, c),
+                        QueryNodeBranch::X(si) => self.ss().get(self.ns(), si).is_on_left(c),
                         // Choose the lower trapezoid if this corresponds to an existing vertex (to make horizontal splitting easier)
-                        QueryNodeBranch::Y(c_y) => { //self.ps()[vi.clone()] <= self.ps()[self.ns()[*ni_y].vertex()],
-                            let left = c;
-                            let right = c_y;
-                            left <= right
-                        }
+                        QueryNodeBranch::Y(ni) => c <= self.ns().coords(ni),
                     };
                     qi = if use_left { *left } else { *right };
                 },
@@ -62,7 +62,7 @@ fn trapezoidation_fmt<'p, P: PolygonList<'p> + ?Sized, T: TrapezoidationStructur
     writeln!(w, "segments:")?;
     write!(w, "[")?;
     if trapezoidation.ss().len() > 0 {
-        write!(w, "{:?}", trapezoidation.ss()[0])?;
+        write!(w, "{:?}", trapezoidation.ss().get(trapezoidation.ns(), Idx::new(0)))?;
     }
     for s in trapezoidation.ss().iter().skip(1) {
         write!(w, ", {:?}", s)?;
@@ -82,24 +82,25 @@ fn trapezoidation_fmt<'p, P: PolygonList<'p> + ?Sized, T: TrapezoidationStructur
 
 #[derive(Debug)]
 enum VertexLocation<V: Vertex, Index: VertexIndex> {
-    Pending(Coords<V::Coordinate>, Index, Idx<QueryNode<V, Index>>),
-    Inserted(Coords<V::Coordinate>, Idx<Nexus<V, Index>>),
+    // The coordinates are available from the polygon list, and then from the nexus
+    Pending(Index, Idx<QueryNode<V, Index>>),
+    Inserted(Idx<Nexus<V, Index>>),
 }
 
-impl<V: Vertex, Index: VertexIndex> VertexLocation<V, Index> {
-    pub fn coords(&self) -> Coords<V::Coordinate> {
+impl<V: Vertex, Index: VertexIndex> Clone for VertexLocation<V, Index> {
+    fn clone(&self) -> Self {
         match self {
-            VertexLocation::Pending(c, _, _) => *c,
-            VertexLocation::Inserted(c, _) => *c,
+            Self::Pending(arg0, arg1) => Self::Pending(arg0.clone(), *arg1),
+            Self::Inserted(arg0) => Self::Inserted(*arg0),
         }
     }
 }
 
-impl<V: Vertex, Index: VertexIndex> Clone for VertexLocation<V, Index> {
-    fn clone(&self) -> Self {
+impl<V: Vertex, Index: VertexIndex> VertexLocation<V, Index> {
+    fn pending_index(&self) -> Option<&Index> {
         match self {
-            Self::Pending(arg0, arg1, arg2) => Self::Pending(*arg0, arg1.clone(), *arg2),
-            Self::Inserted(arg0, arg1) => Self::Inserted(*arg0, *arg1),
+            Self::Pending(index, _) => Some(index),
+            Self::Inserted(_) => None,
         }
     }
 }
@@ -174,45 +175,295 @@ impl DebugInfo {
 
 pub(crate) struct TrapezoidationState<'p, P: PolygonList<'p> + ?Sized> {
     ps: PolygonListExt<'p, P>,
-    ns: Vec<Nexus<P::Vertex, P::Index>>,
-    ss: Vec<Segment<P::Vertex, P::Index>>,
-    ts: Vec<Trapezoid<P::Vertex, P::Index>>,
+    ns: Nexuses<P::Vertex, P::Index>,
+    ss: Segments<P::Vertex, P::Index>,
+    ts: Trapezoids<P::Vertex, P::Index>,
     qs: Vec<QueryNode<P::Vertex, P::Index>>,
+    seed: Option<u64>,
     #[cfg(feature = "_debugging")]
     debug_info: DebugInfo,
 }
 
-impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
-    pub fn new(ps: &'p P) -> Self {
-        let ps = PolygonListExt::new(ps);
-        let vertex_count = ps.vertex_count();
+/// The structure of a [TrapezoidationState] without the polygons, so it can be kept between ring insertions
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(bound(
+    serialize = "Index: serde::Serialize, V::Coordinate: serde::Serialize",
+    deserialize = "Index: serde::Deserialize<'de>, V::Coordinate: serde::Deserialize<'de>",
+)))]
+pub(crate) struct TrapezoidationParts<V: Vertex, Index: VertexIndex> {
+    ns: Nexuses<V, Index>,
+    ss: Segments<V, Index>,
+    ts: Trapezoids<V, Index>,
+    qs: Vec<QueryNode<V, Index>>,
+}
+
+impl<V: Vertex, Index: VertexIndex> TrapezoidationParts<V, Index> {
+    /// A single trapezoid covering the plane, with space for `vertex_count` vertices
+    pub fn new(vertex_count: usize) -> Self {
+        let mut parts = Self::default();
+        parts.reset(vertex_count);
+        parts
+    }
+
+    /// Return to a single trapezoid covering the plane, keeping the existing allocations
+    pub fn reset(&mut self, vertex_count: usize) {
+        self.ns.clear();
+        self.ss.clear();
+        self.ts.clear();
+        self.qs.clear();
 
-        // TODO What is the upper bound for number of query nodes?
-        // Just allocate a large amount for now
-        let mut qs = Vec::with_capacity(vertex_count * 4);
+        self.qs.reserve(Self::query_node_reservation(vertex_count));
         let ti = Idx::new(0);
         let q = QueryNode::root(ti);
-        let qi = qs.push_get_index(q);
+        let qi = self.qs.push_get_index(q);
         let t = Trapezoid::all(qi);
 
-        let mut ts = Vec::with_capacity(vertex_count * 2 + 1);
-        ts.push(t);
+        self.ts.reserve(vertex_count * 2 + 1);
+        self.ts.push(t);
+
+        self.ns.reserve(vertex_count);
+        self.ss.reserve(vertex_count);
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> TrapezoidationParts<V, Index> {
+    /// How many query nodes to reserve for `n` vertices, so the query structure rarely needs to grow while segments are inserted.
+    ///
+    /// This is a heuristic sized from an estimate of the expected number of query nodes, when the segments are inserted in random order.
+    ///
+    /// There is one root node, each vertex adds 2, and each segment adds 2 plus 1 for every vertex it crosses on insertion,
+    /// so there are exactly 1 + 4*n* + *c* nodes, for *c* crossings in total. By backwards analysis, the set of the first *i* segments
+    /// determines the trapezoids after inserting them, and the *i*th segment is equally likely to be any of them.
+    /// There are 1 + *v* + *i* trapezoids, where *v* ≤ min(2*i*, *n*) is the number of vertices inserted so far, each bounded by
+    /// at most 2 segments, and a segment which crossed *c<sub>i</sub>* vertices bounds *c<sub>i</sub>* + 2 of them.
+    /// So E\[*c<sub>i</sub>* + 2\] ≤ 2(1 + *v* + *i*) / *i*, or E\[*c<sub>i</sub>*\] ≤ 2(1 + *v*) / *i*, and summing over *i* gives
+    /// E\[*c*\] ≤ 2*n*(1 + ln 2) + 2 ln *n* + 4 < 3.4*n* + 2 log₂ *n* + 4.
+    ///
+    /// The capacity stays a heuristic, as there is no useful bound. A segment can cross every other vertex, so the worst case
+    /// is Θ(*n*²) nodes, and an unlucky order or one chosen by [TrapezoidationBuilder](crate::TrapezoidationBuilder) can exceed
+    /// the reservation, which the query structure then grows past.
+    pub(crate) fn query_node_reservation(n: usize) -> usize {
+        let log2_n = (usize::BITS - n.leading_zeros()) as usize;
+        1 + 4 * n + (17 * n).div_ceil(5) + 2 * log2_n + 4
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<V: Vertex, Index: VertexIndex> TrapezoidationParts<V, Index> {
+    /// Check that a deserialized structure is safe to use.
+    ///
+    /// Links between the structures are followed without bounds checks, so every one must be in bounds. Query nodes must
+    /// only branch to later nodes, so point location terminates, and each nexus must be at the coordinates of its vertex,
+    /// as given by `coords`. A structure passing these checks can still fail to triangulate if it was not built by a trapezoidation.
+    pub fn check(&self, coords: impl Fn(&Index, usize) -> Option<Coords<V::Coordinate>>) -> Result<(), String> {
+        fn in_bounds<T>(idx: Idx<T>, len: usize, kind: &str) -> Result<(), String> {
+            if idx.usize() < len {
+                Ok(())
+            } else {
+                Err(format!("Link to {} {} out of bounds", kind, idx.usize()))
+            }
+        }
+
+        let (n_len, s_len, t_len, q_len) = (self.ns.len(), self.ss.len(), self.ts.len(), self.qs.len());
+        if t_len == 0 || q_len == 0 {
+            return Err("No root trapezoid".to_string());
+        }
+        if !self.ns.columns_match() || !self.ss.columns_match() || !self.ts.columns_match() {
+            return Err("Columns have different lengths".to_string());
+        }
+        for (i, n) in self.ns.iter().enumerate() {
+            if coords(&n.vertex(), n.ring()) != Some(n.coords()) {
+                return Err(format!("Nexus {} is not at its vertex", i));
+            }
+            n.trapezoids().try_for_each(|ti| in_bounds(ti, t_len, "trapezoid"))?;
+            n.segments().try_for_each(|si| in_bounds(si, s_len, "segment"))?;
+        }
+        // Segments read their endpoints from the nexuses, which were checked against the vertices above
+        for (i, [ni_min, ni_max]) in self.ss.iter_nexuses().enumerate() {
+            in_bounds(ni_min, n_len, "nexus")?;
+            in_bounds(ni_max, n_len, "nexus")?;
+            if self.ns.coords(ni_min) >= self.ns.coords(ni_max) {
+                return Err(format!("Segment {} does not go from its lower to its upper nexus", i));
+            }
+        }
+        for t in self.ts.iter() {
+            t.left().into_iter().chain(t.right()).try_for_each(|si| in_bounds(si, s_len, "segment"))?;
+            t.up().into_iter().chain(t.down()).try_for_each(|ni| in_bounds(ni, n_len, "nexus"))?;
+            in_bounds(t.sink(), q_len, "query node")?;
+        }
+        for (i, q) in self.qs.iter().enumerate() {
+            match q {
+                QueryNode::Branch(left, right, branch) => {
+                    if left.usize() <= i || right.usize() <= i {
+                        return Err(format!("Query node {} branches to an earlier node", i));
+                    }
+                    in_bounds(*left, q_len, "query node")?;
+                    in_bounds(*right, q_len, "query node")?;
+                    match *branch {
+                        QueryNodeBranch::X(si) => in_bounds(si, s_len, "segment")?,
+                        QueryNodeBranch::Y(ni) => in_bounds(ni, n_len, "nexus")?,
+                    }
+                },
+                QueryNode::Sink(ti) => in_bounds(*ti, t_len, "trapezoid")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> Default for TrapezoidationParts<V, Index> {
+    /// No trapezoids at all, which must be [reset](TrapezoidationParts::reset) before use
+    fn default() -> Self {
+        Self { ns: Nexuses::default(), ss: Segments::default(), ts: Trapezoids::default(), qs: Vec::new() }
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> Clone for TrapezoidationParts<V, Index> {
+    fn clone(&self) -> Self {
+        Self { ns: self.ns.clone(), ss: self.ss.clone(), ts: self.ts.clone(), qs: self.qs.clone() }
+    }
+}
+
+/// Scratch space for building and triangulating a trapezoidation, which can be kept between calls to avoid reallocating
+pub(crate) struct Buffers<V: Vertex, Index: VertexIndex> {
+    v_lookup: Vec<VertexLocation<V, Index>>,
+    ring_starts: Vec<usize>,
+    segment_order: Vec<usize>,
+    pending_vertices: Vec<usize>,
+    inserted_vertex: Vec<Option<usize>>,
+    branch_stack: Vec<BranchState<V, Index>>,
+    monotone_stack: Vec<MonotoneBuilder<Index, V::Coordinate>>,
+}
+
+impl<V: Vertex, Index: VertexIndex> Buffers<V, Index> {
+    pub fn new() -> Self {
+        Self {
+            v_lookup: Vec::new(),
+            ring_starts: Vec::new(),
+            segment_order: Vec::new(),
+            pending_vertices: Vec::new(),
+            inserted_vertex: Vec::new(),
+            branch_stack: Vec::new(),
+            monotone_stack: Vec::new(),
+        }
+    }
+}
+
+/// Scratch space for searching the trapezoids around a point for the nearest segment
+struct NearestSearch<V: Vertex, Index: VertexIndex> {
+    visited: HashSet<Idx<Trapezoid<V, Index>>>,
+    stack: Vec<Idx<Trapezoid<V, Index>>>,
+}
+
+impl<V: Vertex, Index: VertexIndex> NearestSearch<V, Index> {
+    fn new() -> Self {
+        Self { visited: HashSet::new(), stack: Vec::new() }
+    }
+}
+
+/// A trapezoid waiting to be traversed during triangulation, with the monotone chains it continues if it is inside the polygons
+struct BranchState<V: Vertex, Index: VertexIndex> {
+    ti: Idx<Trapezoid<V, Index>>,
+    monotones: Option<Ot<MonotoneBuilder<Index, V::Coordinate>>>,
+}
+
+impl<V: Vertex, Index: VertexIndex> BranchState<V, Index> {
+    fn new(ti: Idx<Trapezoid<V, Index>>, monotones: Option<Ot<MonotoneBuilder<Index, V::Coordinate>>>) -> Self {
+        Self { ti, monotones }
+    }
+}
+
+/// How far the segments gathered in a [Buffers] have been inserted, so insertion can be paused between segments
+pub(crate) struct SegmentCursor {
+    inserted: usize,
+    len: usize,
+    update_count: usize,
+    next_update: usize,
+}
+
+impl SegmentCursor {
+    /// Choose the insertion order of the segments gathered in `buffers`, none of which are inserted yet
+    pub fn new<V: Vertex, Index: VertexIndex, R: Rng + ?Sized>(buffers: &mut Buffers<V, Index>, rng: &mut R) -> Self {
+        let Buffers { v_lookup, ring_starts, segment_order, pending_vertices, inserted_vertex, .. } = buffers;
+        let len = v_lookup.len();
+
+        pending_vertices.clear();
+        pending_vertices.extend(ring_starts.windows(2).map(|w| w[1] - w[0]));
+        // A vertex of each ring which has been inserted, from which the ring's pending vertices can be traced
+        inserted_vertex.clear();
+        inserted_vertex.resize(ring_starts.len() - 1, None);
+
+        // Random insertion order of the segments avoids constant worst-case scenarios
+        segment_order.clear();
+        segment_order.extend(0..len);
+        segment_order.shuffle(rng);
+
+        // Periodically, at a decreasing rate, find the trapezoid each uninserted vertex is contained within, based on the current query structure
+        // The next search can begin from that query node
+        let update_count = 1;
+        Self { inserted: 0, len, update_count, next_update: math_n(len, update_count) }
+    }
+
+    /// The number of segments inserted so far
+    pub fn inserted(&self) -> usize {
+        self.inserted
+    }
+
+    /// The total number of segments
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.inserted == self.len
+    }
+}
+
+/// The position of the depth-first traversal of the trapezoids which triangulates a [Trapezoidation],
+/// so it can be paused between trapezoids
+pub(crate) struct Walk<V: Vertex, Index: VertexIndex> {
+    ti: Idx<Trapezoid<V, Index>>,
+    monotones: Option<Ot<MonotoneBuilder<Index, V::Coordinate>>>,
+    visited: usize,
+}
+
+impl<V: Vertex, Index: VertexIndex> Walk<V, Index> {
+    /// The number of trapezoids traversed so far
+    pub fn visited(&self) -> usize {
+        self.visited
+    }
+}
+
+impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
+    /// `seed` is only recorded for error reporting, the segment order is determined by the rng passed to `build`
+    pub fn new(ps: &'p P, seed: Option<u64>) -> Self {
+        let parts = TrapezoidationParts::new(ps.vertex_count());
+        Self::from_parts(ps, parts, seed)
+    }
+
+    /// Continue a trapezoidation of the first rings of `ps`
+    pub fn from_parts(ps: &'p P, parts: TrapezoidationParts<P::Vertex, P::Index>, seed: Option<u64>) -> Self {
+        let ps = PolygonListExt::new(ps);
+        let TrapezoidationParts { ns, ss, ts, qs } = parts;
 
         #[cfg(feature = "_debugging")]
         let debug_info = DebugInfo::new(&ps);
 
         Self {
             ps,
-            ns: Vec::with_capacity(vertex_count),
-            ss: Vec::with_capacity(vertex_count),
+            ns,
+            ss,
             ts,
             qs,
+            seed,
             #[cfg(feature = "_debugging")]
             debug_info,
         }
     }
 
-    
+    pub fn into_parts(self) -> TrapezoidationParts<P::Vertex, P::Index> {
+        let Self { ns, ss, ts, qs, .. } = self;
+        TrapezoidationParts { ns, ss, ts, qs }
+    }
 
     #[cfg(feature = "_debugging")]
     fn output_svg(&mut self, style: debug::svg::SvgTriangulationStyle<'p, P::Vertex, P::Index>, level: debug::svg::SvgOutputLevel) {
@@ -254,91 +505,142 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
         }
     }
 
-    pub fn build(mut self) -> Result<Trapezoidation<'p, P>, TrapezoidationError> {
+    pub fn build<R: Rng + ?Sized>(mut self, rng: &mut R) -> Result<Trapezoidation<'p, P>, TrapezoidationError<P::Index>> {
+        self.add_polygon_list(&mut Buffers::new(), rng)?;
+        Ok(Trapezoidation::new(self))
+    }
+
+    /// Insert every ring of the [PolygonList], using `buffers` for scratch space
+    pub fn add_polygon_list<R: Rng + ?Sized>(&mut self, buffers: &mut Buffers<P::Vertex, P::Index>, rng: &mut R) -> Result<(), TrapezoidationError<P::Index>> {
+        self.load_polygon_list(buffers)?;
+        self.add_rings(buffers, 0, rng)
+            .map_err(|e| TrapezoidationError::InternalError(e).diagnose(self.ps.polygon_list(), self.seed))
+    }
+
+    /// Gather the rings of the [PolygonList] into `buffers`, ready for their segments to be inserted
+    pub fn load_polygon_list(&self, buffers: &mut Buffers<P::Vertex, P::Index>) -> Result<(), TrapezoidationError<P::Index>> {
         // Track the best-known location of each vertex. Initially, all we have is the root QueryNode.
         // Periodically, for each uninserted vertex, we search for the trapezoid that contains the point and update the QueryNode.
         // Finally, once a vertex is inserted, we replace the QueryNode with the exact Nexus we created for the vertex
-
-        // Allocate as if there is a single polygon (ensuring no reallocations)
-        let mut v_lookup: Vec<VertexLocation<P::Vertex, P::Index>> = Vec::with_capacity(self.ps.vertex_count());
+        let v_lookup = &mut buffers.v_lookup;
+        v_lookup.clear();
+        v_lookup.reserve(self.ps.vertex_count());
+        // The start of each ring within `v_lookup`, followed by the total vertex count
+        let ring_starts = &mut buffers.ring_starts;
+        ring_starts.clear();
+        ring_starts.push(0);
 
         // Ensure the iteration ends with NewPolygon
         for polygon_vertex in self.ps.clone().iter_polygon_vertices().map(Into::into).chain(iter::once(PolygonElement::NewPolygon)) {
             match polygon_vertex {
                 PolygonElement::ContinuePolygon(index) => {
-                    let c = self.ps[index.clone()].coords();
-                    v_lookup.push(VertexLocation::Pending(c, index, self.query_node_root()));
+                    v_lookup.push(VertexLocation::Pending(index, self.query_node_root()));
                 }
                 PolygonElement::NewPolygon => {
-                    let v_count = v_lookup.len();
+                    let start = ring_starts[ring_starts.len() - 1];
+                    let v_count = v_lookup.len() - start;
                     if v_count > 0 {
                         if v_count < 3 {
-                            return Err(TrapezoidationError::NotEnoughVertices(v_count));
-                        } else {
-                            self.add_polygon(v_lookup.as_mut_slice()).map_err(TrapezoidationError::InternalError)?;
-                            v_lookup.clear();
+                            let vertices = v_lookup[start..].iter().filter_map(VertexLocation::pending_index).cloned().collect();
+                            return Err(TrapezoidationError::ShortRing { ring: ring_starts.len() - 1, vertices });
                         }
+                        ring_starts.push(v_lookup.len());
                     }
                 }
             }
         }
 
-        Ok(Trapezoidation::new(self))
+        if v_lookup.len() > TrapezoidationError::<P::Index>::MAX_VERTICES {
+            return Err(TrapezoidationError::TooManyVertices(v_lookup.len()));
+        }
+        Ok(())
+    }
+
+    /// Insert the polygon made up of `indices`, which is the `ring`th non-empty polygon of the [PolygonList]
+    pub fn add_ring<R: Rng + ?Sized>(&mut self, indices: impl IntoIterator<Item = P::Index>, ring: usize, rng: &mut R) -> Result<(), TrapezoidationError<P::Index>> {
+        let mut buffers = Buffers::new();
+        buffers.v_lookup.extend(indices.into_iter()
+            .map(|index| VertexLocation::Pending(index, self.query_node_root())));
+        let len = buffers.v_lookup.len();
+        if len < 3 {
+            let vertices = buffers.v_lookup.iter().filter_map(VertexLocation::pending_index).cloned().collect();
+            return Err(TrapezoidationError::ShortRing { ring, vertices });
+        } else if self.ns.len() + len > TrapezoidationError::<P::Index>::MAX_VERTICES {
+            return Err(TrapezoidationError::TooManyVertices(self.ns.len() + len));
+        }
+        buffers.ring_starts.extend([0, len]);
+        self.add_rings(&mut buffers, ring, rng)
+            .map_err(|e| TrapezoidationError::InternalError(e).diagnose(self.ps.polygon_list(), self.seed))
     }
 
+    pub fn finish(self) -> Trapezoidation<'p, P> {
+        Trapezoidation::new(self)
+    }
+
+    /// Insert the segments of every ring in a single random order.
+    ///
+    /// Ring `r` is made up of `buffers.v_lookup[ring_starts[r]..ring_starts[r + 1]]`, and is the `first_ring + r`th ring of the [PolygonList].
+    /// Sharing one order and one re-location schedule between all the rings keeps the expected O(*n* log\* *n*) running time
+    /// for inputs made up of many small rings, which would otherwise each restart the schedule.
+    fn add_rings<R: Rng + ?Sized>(&mut self, buffers: &mut Buffers<P::Vertex, P::Index>, first_ring: usize, rng: &mut R) -> Result<(), InternalError> {
+        let mut cursor = SegmentCursor::new(buffers, rng);
+        self.insert_segments(buffers, first_ring, &mut cursor, usize::MAX)?;
+        Ok(())
+    }
+
+    /// Insert up to `budget` more segments in the order chosen by `cursor`, returning how many were inserted
     #[inline(never)]
-    fn add_polygon(&mut self, vls: &mut [VertexLocation<P::Vertex, P::Index>]) -> Result<(), InternalError> {
+    pub fn insert_segments(&mut self, buffers: &mut Buffers<P::Vertex, P::Index>, first_ring: usize, cursor: &mut SegmentCursor, budget: usize) -> Result<usize, InternalError> {
         #[inline(never)]
-        fn add_nth_segment<'p, P: PolygonList<'p> + ?Sized>(state: &mut TrapezoidationState<'p, P>, vls: &mut [VertexLocation<P::Vertex, P::Index>], si: usize) -> Result<usize, InternalError> {
+        fn add_nth_segment<'p, P: PolygonList<'p> + ?Sized>(state: &mut TrapezoidationState<'p, P>, vls: &mut [VertexLocation<P::Vertex, P::Index>], ring: usize, vli0: usize, vli1: usize) -> Result<usize, InternalError> {
             #[inline(never)]
-            fn add_vertex<'p, P: PolygonList<'p> + ?Sized>(state: &mut TrapezoidationState<'p, P>, vl: &mut VertexLocation<P::Vertex, P::Index>, index: P::Index, qi: Idx<QueryNode<P::Vertex, P::Index>>) -> Result<Idx<Nexus<P::Vertex, P::Index>>, InternalError> {
-                let ni = state.add_vertex(index, qi)?;
+            #[allow(clippy::type_complexity)]
+            fn add_vertex<'p, P: PolygonList<'p> + ?Sized>(state: &mut TrapezoidationState<'p, P>, vl: &mut VertexLocation<P::Vertex, P::Index>, index: P::Index, ring: usize, qi: Idx<QueryNode<P::Vertex, P::Index>>) -> Result<Idx<Nexus<P::Vertex, P::Index>>, InternalError> {
+                let ni = state.add_vertex(index, ring, qi)?;
 
                 #[cfg(feature = "_debugging")]
                 state.output_svg(debug::svg::SvgTriangulationStyle::highlight_nexus(ni), debug::svg::SvgOutputLevel::AllSteps);
                 
-                *vl = VertexLocation::Inserted(vl.coords(), ni);
+                *vl = VertexLocation::Inserted(ni);
                 Ok(ni)
             }
 
-            let vli0 = si;
-            let vli1 = (si + 1) % vls.len();
             let vl0 = vls[vli0].clone();
             let vl1 = vls[vli1].clone();
 
             let (ni0, ni1, added_vertices) = match (vl0, vl1) {
-                (VertexLocation::Pending(c0, index0, qi0), VertexLocation::Pending(c1, index1, qi1)) => {
-                    if c0 < c1 {
-                        let ni0 = add_vertex(state, &mut vls[vli0], index0, qi0)?;
-                        let ni1 = add_vertex(state, &mut vls[vli1], index1, qi1)?;
+                (VertexLocation::Pending(index0, qi0), VertexLocation::Pending(index1, qi1)) => {
+                    if state.ps[index0.clone()].coords() < state.ps[index1.clone()].coords() {
+                        let ni0 = add_vertex(state, &mut vls[vli0], index0, ring, qi0)?;
+                        let ni1 = add_vertex(state, &mut vls[vli1], index1, ring, qi1)?;
                         (ni0, ni1, 2)
                     } else {
-                        let ni1 = add_vertex(state, &mut vls[vli1], index1, qi1)?;
-                        let ni0 = add_vertex(state, &mut vls[vli0], index0, qi0)?;
+                        let ni1 = add_vertex(state, &mut vls[vli1], index1, ring, qi1)?;
+                        let ni0 = add_vertex(state, &mut vls[vli0], index0, ring, qi0)?;
                         (ni0, ni1, 2)
                     }
                 }
-                (VertexLocation::Pending(_, index0, qi0), VertexLocation::Inserted(_, ni1)) => {
-                    let ni0 = add_vertex(state, &mut vls[vli0], index0, qi0)?;
+                (VertexLocation::Pending(index0, qi0), VertexLocation::Inserted(ni1)) => {
+                    let ni0 = add_vertex(state, &mut vls[vli0], index0, ring, qi0)?;
                     (ni0, ni1, 1)
                 }
-                (VertexLocation::Inserted(_, ni0), VertexLocation::Pending(_, index1, qi1)) => {
-                    let ni1 = add_vertex(state, &mut vls[vli1], index1, qi1)?;
+                (VertexLocation::Inserted(ni0), VertexLocation::Pending(index1, qi1)) => {
+                    let ni1 = add_vertex(state, &mut vls[vli1], index1, ring, qi1)?;
                     (ni0, ni1, 1)
                 }
-                (VertexLocation::Inserted(_, ni0), VertexLocation::Inserted(_, ni1)) => (ni0, ni1, 0),
+                (VertexLocation::Inserted(ni0), VertexLocation::Inserted(ni1)) => (ni0, ni1, 0),
             };
             
-            let c0 = state.ns[ni0].coords();
-            let c1 = state.ns[ni1].coords();
+            let c0 = state.ns.coords(ni0);
+            let c1 = state.ns.coords(ni1);
 
-            let (ni_min, ni_max, c_min, c_max) = if c0 < c1 {
-                (ni0, ni1, c0, c1)
+            let (ni_min, ni_max, c_min, c_max, forward) = if c0 < c1 {
+                (ni0, ni1, c0, c1, true)
             } else {
-                (ni1, ni0, c1, c0)
+                (ni1, ni0, c1, c0, false)
             };
 
-            state.add_segment(ni_min, ni_max, c_min, c_max)?;
+            state.add_segment(ni_min, ni_max, c_min, c_max, forward)?;
 
             #[cfg(feature = "_debugging")]
             state.advance_step();
@@ -346,34 +648,31 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
             Ok(added_vertices)
         }
 
+        let Buffers { v_lookup: vls, ring_starts, segment_order, pending_vertices, inserted_vertex, .. } = buffers;
         let len = vls.len();
-
-        let mut pending_vertices = len;
-
-        // Random insertion order of the segments avoids constant worst-case scenarios
-        let mut segment_order: Vec<_> = (0..len).collect();
-        segment_order[..].shuffle(&mut rand::thread_rng());
-
-        // Periodically, at a decreasing rate, find the trapezoid each uninserted vertex is contained within, based on the current query structure
-        // The next search can begin from that query node
-        let mut update_count = 1;
-        let mut next_update = math_n(len, update_count);
-
-        for (i, vi0) in segment_order.into_iter().enumerate() {
-            pending_vertices -= add_nth_segment(self, &mut vls[..], vi0)?;
-
-            if i == next_update {
+        let ring_count = ring_starts.len() - 1;
+        // The ring containing each vertex, and the vertex following it around that ring
+        let ring_of = |vli: usize| ring_starts.partition_point(|&start| start <= vli) - 1;
+        let next = |vli: usize, r: usize| if vli + 1 == ring_starts[r + 1] { ring_starts[r] } else { vli + 1 };
+
+        let end = len.min(cursor.inserted.saturating_add(budget));
+        for (i, &vi0) in segment_order.iter().enumerate().take(end).skip(cursor.inserted) {
+            let r = ring_of(vi0);
+            pending_vertices[r] -= add_nth_segment(self, &mut vls[..], first_ring + r, vi0, next(vi0, r))?;
+            inserted_vertex[r] = Some(vi0);
+
+            if i == cursor.next_update {
                 enum Location<N, T> {
                     Nexus(N),
                     Trapezoid(T),
                 }
 
-                fn reached_containing_trapezoid<V: Vertex, Index: VertexIndex>(ns: &[Nexus<V, Index>], t: &Trapezoid<V, Index>, direction: DividerDirection, c: Coords<V::Coordinate>) -> bool {
+                fn reached_containing_trapezoid<V: Vertex, Index: VertexIndex>(ns: &Nexuses<V, Index>, t: &Trapezoid<V, Index>, direction: DividerDirection, c: Coords<V::Coordinate>) -> bool {
                     match direction {
                         DividerDirection::Ascending => t.up(),
                         DividerDirection::Descending => t.down(),
                     }.map(|ni| {
-                        let c_far = ns[ni].coords();
+                        let c_far = ns.coords(ni);
                         match direction {
                             DividerDirection::Ascending => c_far > c,
                             DividerDirection::Descending => c_far < c,
@@ -381,65 +680,105 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
                     }).unwrap_or(true)
                 }
 
-                let mut unlocated_pending_vertices = pending_vertices;
-
-                let mut vli_target = vi0;
-
-                // Dummy value, will be overwritten on first loop iteration
-                let mut location = Location::Nexus(Idx::new(0));
-
-                let mut c_origin = Coords::zero();
+                for r in 0..ring_count {
+                    let mut unlocated_pending_vertices = pending_vertices[r];
+                    if unlocated_pending_vertices == 0 {
+                        continue;
+                    }
 
-                // Trace the edges of the polygon sequentially, recording the Trapezoid 
-                // the uninserted Vertices currently reside within
-                // We can stop early if the only remaining vertices are ones that have already been inserted
-                while unlocated_pending_vertices != 0 {
-                    vli_target = (vli_target + 1) % len;
-                    let vl_target = &vls[vli_target];
-                    match *vl_target {
-                        // First iteration will always be Inserted
-                        VertexLocation::Inserted(_, ni) => {
-                            // Inserted vertices already have a known location
-                            // Just set location to the nexus
-                            location = Location::Nexus(ni);
-                            c_origin = self.ns[ni].coords();
-                        }
-                        VertexLocation::Pending(c_target, ref index, _) => {
-                            let ascending = c_target > c_origin;
-                            let direction = if ascending { DividerDirection::Ascending } else { DividerDirection::Descending };
-
-                            // If on an inserted vertex, move to an adjacent Trapezoid toward the target
-                            let mut ti = match location {
-                                Location::Nexus(ni) => self.ns[ni].get_trapezoid_toward_coords(&self.ss, &self.ns, direction, c_target)?,
-                                Location::Trapezoid(ti) => ti,
+                    let (mut vli_target, mut location, mut c_origin) = match inserted_vertex[r] {
+                        // The trace begins from the vertex after this one
+                        Some(vli) => (vli, Location::Nexus(Idx::new(0)), Coords::zero()),
+                        // No segment of this ring has been inserted yet, so locate its first vertex from the query structure
+                        None => {
+                            let vli = ring_starts[r];
+                            let VertexLocation::Pending(index, qi) = vls[vli].clone() else {
+                                return Err(InternalError::new(format!("Vertex {vli} inserted without any of its segments")));
                             };
+                            let c = self.ps[index.clone()].coords();
+                            let (_, ti) = self.find_trapezoid_from_root(c, qi);
+                            vls[vli] = VertexLocation::Pending(index, self.ts.get(ti).sink());
+                            unlocated_pending_vertices -= 1;
+                            (vli, Location::Trapezoid(ti), c)
+                        }
+                    };
 
-                            while !reached_containing_trapezoid(&self.ns, &self.ts[ti], direction, c_target) {
-                                let t = &self.ts[ti];
-                                let ni = if direction == DividerDirection::Ascending { t.up() } else { t.down() };
-                                let ni = ni.ok_or_else(|| InternalError::new(format!("Trapezoid containing {c_target} (from {c_origin}) not found")))?;
-                                ti = self.ns[ni].get_trapezoid_between_coords(direction, c_origin, c_target)?;
+                    // Trace the edges of the polygon sequentially, recording the Trapezoid
+                    // the uninserted Vertices currently reside within
+                    // We can stop early if the only remaining vertices are ones that have already been inserted
+                    while unlocated_pending_vertices != 0 {
+                        vli_target = next(vli_target, r);
+                        let vl_target = &vls[vli_target];
+                        match *vl_target {
+                            VertexLocation::Inserted(ni) => {
+                                // Inserted vertices already have a known location
+                                // Just set location to the nexus
+                                location = Location::Nexus(ni);
+                                c_origin = self.ns.coords(ni);
                             }
+                            VertexLocation::Pending(ref index, _) => {
+                                let c_target = self.ps[index.clone()].coords();
+                                let ascending = c_target > c_origin;
+                                let direction = if ascending { DividerDirection::Ascending } else { DividerDirection::Descending };
+
+                                // If on an inserted vertex, move to an adjacent Trapezoid toward the target
+                                let mut ti = match location {
+                                    Location::Nexus(ni) => self.ns.get(ni).get_trapezoid_toward_coords(&self.ss, &self.ns, direction, c_target)?,
+                                    Location::Trapezoid(ti) => ti,
+                                };
+
+                                while !reached_containing_trapezoid(&self.ns, &self.ts.get(ti), direction, c_target) {
+                                    let t = self.ts.get(ti);
+                                    let ni = if direction == DividerDirection::Ascending { t.up() } else { t.down() };
+                                    let ni = ni.ok_or_else(|| InternalError::new(format!("Trapezoid containing {c_target} (from {c_origin}) not found")))?;
+                                    ti = self.ns.get(ni).get_trapezoid_between_coords(direction, c_origin, c_target)?;
+                                }
 
-                            unlocated_pending_vertices -= 1;
-                            let qi_target = self.ts[ti].sink();
-                            vls[vli_target] = VertexLocation::Pending(c_target, index.clone(), qi_target);
-                            location = Location::Trapezoid(ti);
-                            c_origin = c_target;
+                                unlocated_pending_vertices -= 1;
+                                let qi_target = self.ts.get(ti).sink();
+                                vls[vli_target] = VertexLocation::Pending(index.clone(), qi_target);
+                                location = Location::Trapezoid(ti);
+                                c_origin = c_target;
+                            }
                         }
                     }
                 }
 
-                update_count += 1;
-                next_update = math_n(len, update_count);
+                cursor.update_count += 1;
+                cursor.next_update = math_n(len, cursor.update_count);
             }
         }
 
+        let inserted = end - cursor.inserted;
+        cursor.inserted = end;
+        if cursor.is_finished() {
+            self.check_sides()?;
+        }
+        Ok(inserted)
+    }
+
+    /// Check that the ends of each trapezoid lie between its sides.
+    ///
+    /// Segments which cross are threaded through the trapezoids as if they didn't, leaving a trapezoid with its sides swapped
+    /// past the crossing. Depending on the insertion order, that may not upset the structure anywhere else, so this catches
+    /// crossings whichever order the segments were inserted in, in O(*n*) time.
+    fn check_sides(&self) -> Result<(), InternalError> {
+        for (i, t) in self.ts.iter().enumerate() {
+            for ni in [t.down(), t.up()].into_iter().flatten() {
+                let c = self.ns.coords(ni);
+                let left_of_left = t.left().map(|si| self.ss.get(&self.ns, si)).is_some_and(|s| s.is_on_left(c) && !s.contains(c));
+                let right_of_right = t.right().map(|si| self.ss.get(&self.ns, si)).is_some_and(|s| !s.is_on_left(c) && !s.contains(c));
+                if left_of_left || right_of_right {
+                    return Err(InternalError::new(format!("Trapezoid {i} has its end at {c} outside its sides")));
+                }
+            }
+        }
         Ok(())
     }
 
     #[inline(never)]
-    fn add_vertex(&mut self, vi: P::Index, qi_root: Idx<QueryNode<P::Vertex, P::Index>>) -> Result<Idx<Nexus<P::Vertex, P::Index>>, InternalError> {
+    #[allow(clippy::type_complexity)]
+    fn add_vertex(&mut self, vi: P::Index, ring: usize, qi_root: Idx<QueryNode<P::Vertex, P::Index>>) -> Result<Idx<Nexus<P::Vertex, P::Index>>, InternalError> {
         let c = self.ps[vi.clone()].coords();
         let (qi_parent, ti) = self.find_trapezoid_from_root(c, qi_root);
         let ti_new = self.ts.next_index();
@@ -448,43 +787,44 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
         let qi_up = qi_down + 1;
 
         let c = self.ps[vi.clone()].coords();
-        let ni = self.ns.push_get_index(Nexus::new(vi, c, ti_new, ti));
+        let ni = self.ns.push_get_index(Nexus::new(vi, ring, c, ti_new, ti));
 
-        let (q_left, q_right) = self.qs[qi_parent].branch_y(qi_down, qi_up, c, ti_new);
-        self.ts[ti].set_sink(qi_down);
+        let (q_left, q_right) = self.qs[qi_parent].branch_y(qi_down, qi_up, ni, ti_new);
         self.qs.push(q_left);
         self.qs.push(q_right);
 
-        let t_new = self.ts[ti].split_horizontal(qi_down, qi_up, ni);
+        let mut t = self.ts.get(ti);
+        let t_new = t.split_horizontal(qi_down, qi_up, ni);
+        self.ts.set(ti, t);
 
         if let Some(ni_up) = t_new.up() {
-            let n_up = &mut self.ns[ni_up];
-            n_up.replace_trapezoid(ti, ti_new)?;
+            self.ns.modify(ni_up, |n_up| n_up.replace_trapezoid(ti, ti_new))?;
         }
 
         self.ts.push(t_new);
 
-        self.check_consistency();
+        self.check_consistency()?;
 
         Ok(ni)
     }
 
-    pub fn add_segment(&mut self, ni_min: Idx<Nexus<P::Vertex, P::Index>>, ni_max: Idx<Nexus<P::Vertex, P::Index>>, c_min: Coords<<P::Vertex as Vertex>::Coordinate>, c_max: Coords<<P::Vertex as Vertex>::Coordinate>) -> Result<(), InternalError> {
-        let si = self.ss.push_get_index(Segment::new(ni_min, ni_max, c_min, c_max));
+    pub fn add_segment(&mut self, ni_min: Idx<Nexus<P::Vertex, P::Index>>, ni_max: Idx<Nexus<P::Vertex, P::Index>>, c_min: Coords<<P::Vertex as Vertex>::Coordinate>, c_max: Coords<<P::Vertex as Vertex>::Coordinate>, forward: bool) -> Result<(), InternalError> {
+        let si = self.ss.push_get_index(Segment::new(ni_min, ni_max, c_min, c_max, forward));
 
-        let ti = self.ns[ni_max].get_down_trapezoid_in_direction( &self.ns, &self.ss, &self.ss[si])?;
+        let s = self.ss.get(&self.ns, si);
+        let ti = self.ns.get(ni_max).get_down_trapezoid_in_direction(&self.ns, &self.ss, &s)?;
 
         // Each segment adds one additional trapezoid
-        let qi = self.ts[ti].sink();
+        let mut t = self.ts.get(ti);
         let ti_new = self.ts.next_index();
         let qi_left = self.qs.next_index();
         let qi_right = qi_left + 1;
-        let (q_left, q_right) = self.qs[qi].branch_x(qi_left, qi_right, c_min, c_max, ti_new);
-        self.ts[ti].set_sink(qi_left);
+        let (q_left, q_right) = self.qs[t.sink()].branch_x(qi_left, qi_right, si, ti_new);
         self.qs.push(q_left);
         self.qs.push(q_right);
 
-        let t_new = self.ts[ti].split_vertical(qi_left, qi_right, si);
+        let t_new = t.split_vertical(qi_left, qi_right, si);
+        self.ts.set(ti, t);
         self.ts.push(t_new);
 
         Nexus::add_segment(&mut self.ns, &self.ss, ni_max, si, ti_new)?;
@@ -492,50 +832,52 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
         #[cfg(feature = "_debugging")]
         self.output_svg(debug::svg::SvgTriangulationStyle::highlight_segment(si), debug::svg::SvgOutputLevel::AllSteps);
 
-        let t= &self.ts[ti];
-        let mut ni = t.down().ok_or_else(|| InternalError::new(format!("Segment min nexus not found at {}", ti)))?;
-        let n = &self.ns[ni];
-        if ni != ni_min && !self.ss[si].is_on_left(n.coords()) {
-            let n = &mut self.ns[ni];
-            n.replace_trapezoid(ti, ti_new)?;
+        let mut ni = self.ts.get(ti).down().ok_or_else(|| InternalError::new(format!("Segment min nexus not found at {}", ti)))?;
+        if ni != ni_min && !s.is_on_left(self.ns.coords(ni)) {
+            self.ns.modify(ni, |n| n.replace_trapezoid(ti, ti_new))?;
         }
 
         let mut ti_upleft = ti;
         let mut ti_upright = ti_new;
 
         while ni != ni_min {
-            let ti = self.ns[ni].get_down_trapezoid_in_direction(&self.ns, &self.ss, &self.ss[si])?;
+            let ti = self.ns.get(ni).get_down_trapezoid_in_direction(&self.ns, &self.ss, &s)?;
 
-            ni = self.ts[ti].down().ok_or_else(|| InternalError::new(format!("Segment min nexus not found at {}", ti)))?;
+            let t = self.ts.get(ti);
+            ni = t.down().ok_or_else(|| InternalError::new(format!("Segment min nexus not found at {}", ti)))?;
             
-            let t = &self.ts[ti];
-            let t_upleft = &self.ts[ti_upleft];
-            let t_upright = &self.ts[ti_upright];
+            let t_upleft = self.ts.get(ti_upleft);
+            let t_upright = self.ts.get(ti_upright);
             let qi_sink = t.sink();
 
+            // Only the crossings are not accounted for by the vertex limit
+            if self.qs.len() > Idx::<QueryNode<P::Vertex, P::Index>>::MAX {
+                return Err(InternalError::new("Query structure exceeded 32 bit indices"));
+            }
+
             if t.right() == t_upright.right() {
                 let qi_left = self.qs.next_index();
-                let q_left = self.qs[qi_sink].merge_x(qi_left, t_upright.sink(), c_min, c_max);
-                self.ts[ti].set_sink(qi_left);
+                let q_left = self.qs[qi_sink].merge_x(qi_left, t_upright.sink(), si);
+                self.ts.set_sink(ti, qi_left);
                 self.qs.push(q_left);
-                self.ts[ti].set_right(si);
-                self.ts[ti_upright].set_down(ni);
+                self.ts.set_right(ti, si);
+                self.ts.set_down(ti_upright, ni);
                 
-                if ni != ni_min && !self.ss[si].is_on_left(self.ns[ni].coords()) {
-                    self.ns[ni].replace_trapezoid(ti, ti_upright)?;
+                if ni != ni_min && !s.is_on_left(self.ns.coords(ni)) {
+                    self.ns.modify(ni, |n| n.replace_trapezoid(ti, ti_upright))?;
                 }
                 ti_upleft = ti;
                 // ti_upright remains the same
             } else if t.left() == t_upleft.left() {
                 let qi_right = self.qs.next_index();
-                let q_right = self.qs[qi_sink].merge_x(t_upleft.sink(), qi_right, c_min, c_max);
-                self.ts[ti].set_sink(qi_right);
+                let q_right = self.qs[qi_sink].merge_x(t_upleft.sink(), qi_right, si);
+                self.ts.set_sink(ti, qi_right);
                 self.qs.push(q_right);
-                self.ts[ti].set_left(si);
-                self.ts[ti_upleft].set_down(ni);
+                self.ts.set_left(ti, si);
+                self.ts.set_down(ti_upleft, ni);
                 
-                if ni == ni_min || self.ss[si].is_on_left(self.ns[ni].coords()) {
-                    self.ns[ni].replace_trapezoid(ti, ti_upleft)?;
+                if ni == ni_min || s.is_on_left(self.ns.coords(ni)) {
+                    self.ns.modify(ni, |n| n.replace_trapezoid(ti, ti_upleft))?;
                 }
                 ti_upright = ti;
                 // ti_upleft remains the same
@@ -552,27 +894,30 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
         #[cfg(feature = "_debugging")]
         self.output_svg(debug::svg::SvgTriangulationStyle::highlight_segment(si), debug::svg::SvgOutputLevel::MajorSteps);
 
-        self.check_consistency();
+        self.check_consistency()?;
 
         Ok(())
     }
 
     #[cfg(debug_assertions)]
-    fn check_consistency(&self) {
+    fn check_consistency(&self) -> Result<(), InternalError> {
         // Trapezoid adjacency
         for ni in self.ns.iter_index() {
-            self.check_consistency_nexus(ni);
+            self.check_consistency_nexus(ni)?;
         }
 
         for ti in self.ts.iter_index() {
-            self.check_consistency_trapezoid(ti);
+            self.check_consistency_trapezoid(ti)?;
         }
 
         self.check_consistency_query_node(self.query_node_root());
+        Ok(())
     }
 
     #[cfg(not(debug_assertions))]
-    fn check_consistency(&self) { }
+    fn check_consistency(&self) -> Result<(), InternalError> {
+        Ok(())
+    }
 
     fn check_consistency_query_node(&self, qi: Idx<QueryNode<P::Vertex, P::Index>>) -> usize {
         let q = &self.qs[qi];
@@ -581,7 +926,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
                 self.check_consistency_query_node(*qi_left) + self.check_consistency_query_node(*qi_right)
             },
             QueryNode::Sink(ti) => {
-                let _t= &self.ts[*ti];
+                let _t = self.ts.get(*ti);
                 // A trapezoid will have multiple sinks after merging, so this needs a more robust check
                 // if t.sink() != qi {
                 //     panic!("Inconsistent sink trapezoid: {} {}({})", qi, ti, t.sink());
@@ -591,88 +936,112 @@ impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationState<'p, P> {
         }) + 1
     }
 
-    fn check_consistency_nexus(&self, ni: Idx<Nexus<P::Vertex, P::Index>>) {
-        let n = &self.ns[ni];
+    fn check_consistency_nexus(&self, ni: Idx<Nexus<P::Vertex, P::Index>>) -> Result<(), InternalError> {
+        let n = self.ns.get(ni);
 
         for ti_up in n.up_trapezoids().iter() {
-            let t_up = &self.ts[*ti_up];
+            let t_up = self.ts.get(*ti_up);
             if t_up.down() != Some(ni) {
-                panic!("Inconsistent nexus-trapezoid connection: {}->{} (down: {})", ni, ti_up, t_up.down().map_or("None".to_string(), |ti| format!("{}", ti)));
+                return Err(InternalError::new(format!("Inconsistent nexus-trapezoid connection: {}->{} (down: {})", ni, ti_up, t_up.down().map_or("None".to_string(), |ti| format!("{}", ti)))));
             }
         }
 
         for ti_down in n.down_trapezoids().iter() {
-            let t_down = &self.ts[*ti_down];
+            let t_down = self.ts.get(*ti_down);
             if t_down.up() != Some(ni) {
-                panic!("Inconsistent nexus-trapezoid connection: {}->{} (up: {})", ni, ti_down, t_down.up().map_or("None".to_string(), |ti| format!("{}", ti)));
+                return Err(InternalError::new(format!("Inconsistent nexus-trapezoid connection: {}->{} (up: {})", ni, ti_down, t_down.up().map_or("None".to_string(), |ti| format!("{}", ti)))));
             }
         }
+        Ok(())
     }
 
-    fn check_consistency_trapezoid(&self, ti: Idx<Trapezoid<P::Vertex, P::Index>>) {
-        let t = &self.ts[ti];
+    fn check_consistency_trapezoid(&self, ti: Idx<Trapezoid<P::Vertex, P::Index>>) -> Result<(), InternalError> {
+        let t = self.ts.get(ti);
 
         if let Some(ni_down) = t.down() {
-            let n = &self.ns[ni_down];
+            let n = self.ns.get(ni_down);
             if !n.iter_up_trapezoids().any(|ti_up| ti == ti_up) {
-                panic!("Inconsistent trapezoid-nexus connection: {}->{}", ti, ni_down);
+                return Err(InternalError::new(format!("Inconsistent trapezoid-nexus connection: {}->{}", ti, ni_down)));
             }
         }
         if let Some(ni_up) = t.up() {
-            let n = &self.ns[ni_up];
+            let n = self.ns.get(ni_up);
             if !n.iter_down_trapezoids().any(|ti_down| ti == ti_down) {
-                panic!("Inconsistent trapezoid-nexus connection: {}->{}", ti, ni_up);
+                return Err(InternalError::new(format!("Inconsistent trapezoid-nexus connection: {}->{}", ti, ni_up)));
             }
         }
-        let qi = self.ts[ti].sink();
+        let qi = t.sink();
         if let QueryNode::Sink(ti_other) = self.qs[qi] {
             if ti != ti_other {
-                panic!("Inconsistent trapezoid-query node connection: {}->{}({})", ti, qi, ti_other);
+                return Err(InternalError::new(format!("Inconsistent trapezoid-query node connection: {}->{}({})", ti, qi, ti_other)));
             }
         } else {
-            panic!("Trapezoid points to a non-sink query node: {}->{}", ti, qi);
+            return Err(InternalError::new(format!("Trapezoid points to a non-sink query node: {}->{}", ti, qi)));
         }
+        Ok(())
     }
 }
 
-impl<'t, 'p: 't, P: PolygonList<'p> + ?Sized> TrapezoidationStructure<'p, P> for TrapezoidationState<'p, P> {
+impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationStructure<'p, P> for TrapezoidationState<'p, P> {
     fn ps(&self) -> PolygonListExt<'p, P> { self.ps }
 
-    fn ns(&self) -> &[Nexus<P::Vertex, P::Index>] { &self.ns }
+    fn ns(&self) -> &Nexuses<P::Vertex, P::Index> { &self.ns }
 
-    fn ss(&self) -> &[Segment<P::Vertex, P::Index>] { &self.ss }
+    fn ss(&self) -> &Segments<P::Vertex, P::Index> { &self.ss }
 
-    fn ts(&self) -> &[Trapezoid<P::Vertex, P::Index>] { &self.ts }
+    fn ts(&self) -> &Trapezoids<P::Vertex, P::Index> { &self.ts }
 
     fn qs(&self) -> &[QueryNode<P::Vertex, P::Index>] { &self.qs }
 }
 
 /// The trapezoidation of a [PolygonList] generated as the first step of triangulation.
+// The structure is borrowed when viewing an OwnedTrapezoidation
 pub struct Trapezoidation<'p, P: PolygonList<'p> + ?Sized> {
     ps: PolygonListExt<'p, P>,
-    ns: Box<[Nexus<P::Vertex, P::Index>]>,
-    ss: Box<[Segment<P::Vertex, P::Index>]>,
-    ts: Box<[Trapezoid<P::Vertex, P::Index>]>,
-    qs: Box<[QueryNode<P::Vertex, P::Index>]>,
+    parts: Cow<'p, TrapezoidationParts<P::Vertex, P::Index>>,
+    seed: Option<u64>,
+    // Only needed for point location, so computed on first use
+    faces: OnceLock<Result<Box<[Face]>, String>>,
 }
 
 impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
-    fn new(state: TrapezoidationState<'p, P>) -> Self {
-        let TrapezoidationState { ps, ns, ss, ts, qs, .. } = state;
-        let ns = ns.into_boxed_slice();
-        let ss = ss.into_boxed_slice();
-        let ts = ts.into_boxed_slice();
-        let qs = qs.into_boxed_slice();
+    fn new(mut state: TrapezoidationState<'p, P>) -> Self {
+        state.ns.shrink_to_fit();
+        state.ss.shrink_to_fit();
+        state.ts.shrink_to_fit();
+        state.qs.shrink_to_fit();
+        Self::with_spare_capacity(state)
+    }
+
+    /// Keep any unused capacity, for a trapezoidation which is only used briefly before its parts are reused
+    pub(crate) fn with_spare_capacity(state: TrapezoidationState<'p, P>) -> Self {
+        let (ps, seed) = (state.ps, state.seed);
+        Self { ps, parts: Cow::Owned(state.into_parts()), seed, faces: OnceLock::new() }
+    }
+
+    /// View a trapezoidation of `ps` without copying its structure
+    pub(crate) fn borrowed(ps: &'p P, parts: &'p TrapezoidationParts<P::Vertex, P::Index>, seed: Option<u64>) -> Self {
+        Self { ps: PolygonListExt::new(ps), parts: Cow::Borrowed(parts), seed, faces: OnceLock::new() }
+    }
+
+    pub(crate) fn into_parts(self) -> TrapezoidationParts<P::Vertex, P::Index> {
+        self.parts.into_owned()
+    }
 
-        Self { ps, ns, ss, ts, qs }
+    /// The seed used to order segment insertion, if the trapezoidation was generated from one.
+    /// 
+    /// Passing the seed to [PolygonList::triangulate_with_seed] reproduces the same triangulation.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
     }
 
+    #[allow(clippy::type_complexity)]
     fn top_trapezoid(&self) -> Result<Idx<Trapezoid<P::Vertex, P::Index>>, InternalError> {
         let mut qi = Idx::<QueryNode<P::Vertex, P::Index>>::new(0);
         loop {
-            match &self.qs[qi] {
+            match &self.parts.qs[qi] {
                 QueryNode::Branch(_, right, kind) => match kind {
-                    QueryNodeBranch::X(_, _) => return Err(InternalError::new("Finding the top trapezoid should not reach an X node")),
+                    QueryNodeBranch::X(_) => return Err(InternalError::new("Finding the top trapezoid should not reach an X node")),
                     QueryNodeBranch::Y(_) => qi = *right, // Always take the 'above' branch
                 },
                 QueryNode::Sink(ti) => return Ok(*ti),
@@ -680,37 +1049,53 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
         }
     }
 
-    fn triangulate_inner<FB: FanFormat<'p, P>>(&self, fbs: &mut FanBuilderState<'p, P, FB>) -> Result<(), TriangulationError<<FB::Builder as FanBuilder<'p, P>>::Error>> {
-        struct State<V: Vertex, Index: VertexIndex> {
-            ti: Idx<Trapezoid<V, Index>>,
-            monotones: Option<Ot<MonotoneBuilder<Index, V::Coordinate>>>,
-        }
-        impl<V: Vertex, Index: VertexIndex> State<V, Index> {
-            pub fn new(ti: Idx<Trapezoid<V, Index>>, monotones: Option<Ot<MonotoneBuilder<Index, V::Coordinate>>>) -> Self {
-                Self { ti, monotones }
-            }
-        }
+    fn triangulate_inner<FB: FanFormat<'p, P>>(&self, fbs: &mut FanBuilderState<'p, P, FB>, buffers: &mut Buffers<P::Vertex, P::Index>) -> Result<(), FanError<'p, P, FB>> {
+        let mut walk = self.begin_walk(buffers).map_err(TriangulationError::InternalError)?;
+        self.walk(&mut walk, fbs, buffers, usize::MAX, None)?;
+        Ok(())
+    }
+
+    /// Start a traversal from the top trapezoid, using `buffers` for its stacks
+    pub(crate) fn begin_walk(&self, buffers: &mut Buffers<P::Vertex, P::Index>) -> Result<Walk<P::Vertex, P::Index>, InternalError> {
+        buffers.branch_stack.clear();
+        buffers.monotone_stack.clear();
+        Ok(Walk { ti: self.top_trapezoid()?, monotones: None, visited: 0 })
+    }
 
+    /// Continue the traversal until `budget` more trapezoids have been visited, returning whether it finished.
+    /// Stops with [TriangulationError::Cancelled] if `cancel` is set before a trapezoid is visited
+    pub(crate) fn walk<FB: FanFormat<'p, P>>(&self, walk: &mut Walk<P::Vertex, P::Index>, fbs: &mut FanBuilderState<'p, P, FB>, buffers: &mut Buffers<P::Vertex, P::Index>, budget: usize, cancel: Option<&AtomicBool>) -> Result<bool, FanError<'p, P, FB>> {
         const INNER_POLYGON_ERROR: &str = "A trapezoid inside the polygon must be enclosed";
 
-        let mut ti = self.top_trapezoid().map_err(TriangulationError::InternalError)?;
+        let mut ti = walk.ti;
         // If the current trapezoid is inside the polygon, monotones is Some, outside it is None
-        let mut monotones = Option::<Ot<MonotoneBuilder<P::Index, <P::Vertex as Vertex>::Coordinate>>>::None;
+        let mut monotones = walk.monotones.take();
+        let budget_end = walk.visited.saturating_add(budget);
 
         // We will treat the graph of trapezoids as a tree and perform a depth-first traversal.
         // Whenever we reach an 'A' nexus, continue traversing the leftmost branch, but store the center
         // and rightmost branches here. Once the left branch hits a dead-end (i.e. 'V' nexus), it will
         // pick up from the next branch in this queue
-        let mut branch_stack = Vec::<State<P::Vertex, P::Index>>::new();
+        let branch_stack = &mut buffers.branch_stack;
         // At 'V' nexuses where the left and right trapezoids are inside the polygon, 
         // the left trapezoid should push its monotone to this stack and yield.
         // Once the right trapezoid reaches this point, it will pop from this stack and combine with its current monotone
         // to have a Ot::Two monotone going down
-        let mut monotone_stack = Vec::<MonotoneBuilder<P::Index, <P::Vertex as Vertex>::Coordinate>>::new();
+        let monotone_stack = &mut buffers.monotone_stack;
         
-        while let Some(ni_down) = self.ts[ti].down() {
-            let t = &self.ts[ti];
-            let n_down = &self.ns[ni_down];
+        while let Some(ni_down) = self.parts.ts.get(ti).down() {
+            if walk.visited >= budget_end {
+                walk.ti = ti;
+                walk.monotones = monotones;
+                return Ok(false);
+            }
+            if cancel.is_some_and(|flag| flag.load(atomic::Ordering::Relaxed)) {
+                return Err(TriangulationError::Cancelled);
+            }
+            walk.visited += 1;
+
+            let t = self.parts.ts.get(ti);
+            let n_down = self.parts.ns.get(ni_down);
 
             if let Some(mut monotones_some) = monotones.take() {
                 // Add this nexus to all monotone chains
@@ -719,11 +1104,11 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
                 }
 
                 let s_left = match t.left() {
-                    Some(si_left) => &self.ss[si_left],
+                    Some(si_left) => self.parts.ss.get(&self.parts.ns, si_left),
                     None => return Err(TriangulationError::internal(INNER_POLYGON_ERROR)),
                 };
                 let s_right = match t.right() {
-                    Some(si_right) => &self.ss[si_right],
+                    Some(si_right) => self.parts.ss.get(&self.parts.ns, si_right),
                     None => return Err(TriangulationError::internal(INNER_POLYGON_ERROR)),
                 };
 
@@ -771,7 +1156,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
                     // If that was the only monotone, we need to start a new one
                     if monotones.is_none() {
                         // Begin with the upper and lower nexuses' vertices
-                        let vi = self.ns[ni_up].vertex().clone();
+                        let vi = self.parts.ns.vertex(ni_up);
                         let c = self.ps[vi.clone()].coords();
                         let mut monotone_new = MonotoneBuilder::new(vi, c);
                         monotone_new.add_vertex(n_down.vertex(), n_down.coords());
@@ -835,14 +1220,14 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
                                 let ni_up = t.up().ok_or_else(|| TriangulationError::internal(INNER_POLYGON_ERROR))?;
 
                                 // Start a second monotone with the current and previous nexuses' vertices
-                                let n = &self.ns[ni_up];
+                                let n = self.parts.ns.get(ni_up);
                                 let mut monotone_new = MonotoneBuilder::new(n.vertex(), n.coords());
                                 monotone_new.add_vertex(n_down.vertex(), n_down.coords());
 
                                 // Put the new monotone on the correct side
-                                if ni_up == self.ss[t.left().ok_or_else(|| TriangulationError::internal(INNER_POLYGON_ERROR))?].ni_max() {
+                                if ni_up == self.parts.ss.get(&self.parts.ns, t.left().ok_or_else(|| TriangulationError::internal(INNER_POLYGON_ERROR))?).ni_max() {
                                     (monotone_new, monotone)
-                                } else if ni_up == self.ss[t.right().ok_or_else(|| TriangulationError::internal(INNER_POLYGON_ERROR))?].ni_max() {
+                                } else if ni_up == self.parts.ss.get(&self.parts.ns, t.right().ok_or_else(|| TriangulationError::internal(INNER_POLYGON_ERROR))?).ni_max() {
                                     (monotone, monotone_new)
                                 } else {
                                     return Err(TriangulationError::internal("Expected nexus on top of left or right segment"));
@@ -853,14 +1238,14 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
                         };
                         monotones = Some(monotone_left.into());
                         
-                        branch_stack.push(State::new(ti_downright, Some(monotone_right.into())));
-                        branch_stack.push(State::new(ti_downcenter, None));
+                        branch_stack.push(BranchState::new(ti_downright, Some(monotone_right.into())));
+                        branch_stack.push(BranchState::new(ti_downcenter, None));
                     } else {
                         // The left and right trapezoids are still outside the polygon
-                        branch_stack.push(State::new(ti_downright, None));
+                        branch_stack.push(BranchState::new(ti_downright, None));
                         // Start a new monotone from the center trapezoid
                         let monotone_new = MonotoneBuilder::new(n_down.vertex(), n_down.coords());
-                        branch_stack.push(State::new(ti_downcenter, Some(monotone_new.into())));
+                        branch_stack.push(BranchState::new(ti_downcenter, Some(monotone_new.into())));
                     }
                     ti_downleft
                 }
@@ -874,17 +1259,463 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
         } else if monotones.is_some() {
             Err(TriangulationError::internal("Unexpected partial monotones"))
         } else {
-            Ok(())
+            walk.ti = ti;
+            Ok(true)
+        }
+    }
+
+    fn faces(&self) -> Result<&[Face], TrapezoidationError<P::Index>> {
+        let faces = self.faces.get_or_init(|| {
+            self.top_trapezoid()
+                .and_then(|ti_top| location::trapezoid_faces(&self.parts.ns, &self.parts.ts, ti_top))
+                .map_err(|e| e.msg)
+        });
+        faces.as_deref().map_err(|msg| TrapezoidationError::InternalError(InternalError::new(msg.clone())).diagnose(self.ps.polygon_list(), self.seed))
+    }
+
+    /// Iterate over all trapezoids of the decomposition.
+    /// 
+    /// The first call additionally takes O(*n*) time to determine which trapezoids are inside the polygons.
+    /// This fails if the [PolygonList] violates the triangulation preconditions.
+    pub fn trapezoids(&self) -> Result<impl ExactSizeIterator<Item = TrapezoidView<'_, 'p, P>>, TrapezoidationError<P::Index>> {
+        let faces = self.faces()?;
+        Ok((0..self.parts.ts.len()).map(move |i| TrapezoidView::new(self, faces, Idx::new(i))))
+    }
+
+    /// Get the trapezoid identified by `id`.
+    /// 
+    /// Panics if `id` does not belong to this trapezoidation. See [Trapezoidation::trapezoids] for possible errors.
+    pub fn trapezoid(&self, id: TrapezoidId) -> Result<TrapezoidView<'_, 'p, P>, TrapezoidationError<P::Index>> {
+        assert!(id.index() < self.parts.ts.len(), "{:?} is not part of this trapezoidation", id);
+        Ok(TrapezoidView::new(self, self.faces()?, Idx::new(id.index())))
+    }
+
+    /// Find the [Location] of `point` relative to the polygons, in expected O(log *n*) time.
+    /// 
+    /// [Location::OnEdge] and [Location::OnVertex] are only reported when `point` lies exactly on the edge or vertex.
+    /// 
+    /// The first call additionally takes O(*n*) time to determine which trapezoids are inside the polygons.
+    /// This fails if the [PolygonList] violates the triangulation preconditions.
+    pub fn locate<V: Vertex<Coordinate = <P::Vertex as Vertex>::Coordinate>>(&self, point: &V) -> Result<Location<P::Index>, TrapezoidationError<P::Index>> {
+        let c = VertexExt::to_newtype_ref(point).coords();
+        let faces = self.faces()?;
+        let (_, ti) = self.find_trapezoid(c);
+        Ok(self.location_in_trapezoid(faces, ti, c))
+    }
+
+    /// Find the [Location] of each of `points`, in the same order.
+    /// 
+    /// This is equivalent to calling [Trapezoidation::locate] for each point, but faster for large batches.
+    /// Points are visited in Z-order, so that a point within the same trapezoid as a recent point is found
+    /// without searching the query structure again.
+    pub fn classify_points<V: Vertex<Coordinate = <P::Vertex as Vertex>::Coordinate>>(&self, points: &[V]) -> Result<Vec<Location<P::Index>>, TrapezoidationError<P::Index>> {
+        let faces = self.faces()?;
+        let coords: Vec<_> = points.iter().map(|point| VertexExt::to_newtype_ref(point).coords()).collect();
+        let morton = math::morton_mapper(&coords);
+        let mut order: Vec<_> = coords.iter().enumerate().map(|(i, c)| (morton(*c), i)).collect();
+        order.sort_unstable();
+
+        let mut locations: Vec<_> = iter::repeat_with(|| None).take(points.len()).collect();
+        for (i, location) in self.classify_sorted(faces, &coords, &order) {
+            locations[i] = Some(location);
+        }
+        Ok(locations.into_iter().flatten().collect())
+    }
+
+    /// Find the [Location] of each of `points`, in the same order, using multiple threads.
+    /// 
+    /// See [Trapezoidation::classify_points].
+    #[cfg(feature = "rayon")]
+    pub fn par_classify_points<V: Vertex<Coordinate = <P::Vertex as Vertex>::Coordinate> + Sync>(&self, points: &[V]) -> Result<Vec<Location<P::Index>>, TrapezoidationError<P::Index>>
+    where Self: Sync, P::Index: Send, <P::Vertex as Vertex>::Coordinate: Send + Sync {
+        use rayon::prelude::*;
+
+        // Large enough chunks that most points still benefit from recent points' trapezoids
+        const CHUNK_SIZE: usize = 4096;
+
+        let faces = self.faces()?;
+        let coords: Vec<_> = points.par_iter().map(|point| VertexExt::to_newtype_ref(point).coords()).collect();
+        let morton = math::morton_mapper(&coords);
+        let mut order: Vec<_> = coords.par_iter().enumerate().map(|(i, c)| (morton(*c), i)).collect();
+        order.par_sort_unstable();
+
+        let chunks: Vec<Vec<_>> = order.par_chunks(CHUNK_SIZE)
+            .map(|chunk| self.classify_sorted(faces, &coords, chunk).collect())
+            .collect();
+
+        let mut locations: Vec<_> = iter::repeat_with(|| None).take(points.len()).collect();
+        for (i, location) in chunks.into_iter().flatten() {
+            locations[i] = Some(location);
+        }
+        Ok(locations.into_iter().flatten().collect())
+    }
+
+    // Locate points in the given order, yielding each point's index with its location
+    fn classify_sorted<'a>(&'a self, faces: &'a [Face], coords: &'a [Coords<<P::Vertex as Vertex>::Coordinate>], order: &'a [(u32, usize)]) -> impl Iterator<Item = (usize, Location<P::Index>)> + 'a {
+        // Checking a handful of recently used trapezoids is much cheaper than searching the query structure,
+        // and points visited in Z-order frequently return to a recent trapezoid
+        const RECENT_TRAPEZOIDS: usize = 8;
+
+        // The most recently used trapezoids, most recent first
+        let mut recent = [Option::<Idx<Trapezoid<P::Vertex, P::Index>>>::None; RECENT_TRAPEZOIDS];
+        order.iter().map(move |&(_, i)| {
+            let c = coords[i];
+            let ti = match recent.iter().enumerate().find_map(|(j, ti)| ti.filter(|&ti| self.is_strictly_inside(ti, c)).map(|ti| (j, ti))) {
+                Some((j, ti)) => {
+                    recent[..=j].rotate_right(1);
+                    ti
+                }
+                None => {
+                    let ti = self.find_trapezoid(c).1;
+                    recent.rotate_right(1);
+                    recent[0] = Some(ti);
+                    ti
+                }
+            };
+            (i, self.location_in_trapezoid(faces, ti, c))
+        })
+    }
+
+    // Whether `c` is in the interior of the trapezoid, not touching any of its boundaries, 
+    // so that searching the query structure would certainly find the same trapezoid
+    fn is_strictly_inside(&self, ti: Idx<Trapezoid<P::Vertex, P::Index>>, c: Coords<<P::Vertex as Vertex>::Coordinate>) -> bool {
+        let t = self.parts.ts.get(ti);
+        t.down().is_none_or(|ni| self.parts.ns.coords(ni) < c) &&
+        t.up().is_none_or(|ni| c < self.parts.ns.coords(ni)) &&
+        t.left().is_none_or(|si| !self.parts.ss.get(&self.parts.ns, si).is_on_left(c) && !self.parts.ss.get(&self.parts.ns, si).contains(c)) &&
+        t.right().is_none_or(|si| self.parts.ss.get(&self.parts.ns, si).is_on_left(c) && !self.parts.ss.get(&self.parts.ns, si).contains(c))
+    }
+
+    fn location_in_trapezoid(&self, faces: &[Face], ti: Idx<Trapezoid<P::Vertex, P::Index>>, c: Coords<<P::Vertex as Vertex>::Coordinate>) -> Location<P::Index> {
+        let t = self.parts.ts.get(ti);
+
+        let segments = t.left().into_iter().chain(t.right()).map(|si| self.parts.ss.get(&self.parts.ns, si));
+
+        // A point on a vertex may be in any trapezoid touching the vertex, which is either above, below, or on the side of the vertex
+        let nexuses = t.up().into_iter()
+            .chain(t.down())
+            .chain(segments.clone().flat_map(|s| [s.ni_min(), s.ni_max()]));
+        for ni in nexuses {
+            let n = self.parts.ns.get(ni);
+            if n.coords() == c {
+                return Location::OnVertex { ring: n.ring(), vertex: n.vertex() };
+            }
+        }
+
+        for s in segments {
+            if s.contains(c) {
+                let (ni_from, ni_to) = s.ni_ordered();
+                let (n_from, n_to) = (self.parts.ns.get(ni_from), self.parts.ns.get(ni_to));
+                return Location::OnEdge { ring: n_from.ring(), edge: (n_from.vertex(), n_to.vertex()) };
+            }
+        }
+
+        let face = faces[ti.usize()];
+        match face.ring {
+            Some(ring) if face.inside => Location::Inside { ring },
+            _ => Location::Outside,
+        }
+    }
+
+    /// Find the first edge hit by a horizontal ray from `point`, in expected O(log *n*) time.
+    /// 
+    /// If `point` lies exactly on an edge or vertex, that edge is hit at `point` itself, in either direction.
+    /// A vertex is reported with the edge which leaves it. Returns [None] if the ray does not hit any edge.
+    pub fn shoot_horizontal<V: Vertex<Coordinate = <P::Vertex as Vertex>::Coordinate>>(&self, point: &V, direction: HorizontalDirection) -> Option<EdgeHit<P::Index, <P::Vertex as Vertex>::Coordinate>> {
+        let c = VertexExt::to_newtype_ref(point).coords();
+        let (_, ti) = self.find_trapezoid(c);
+        let (si, c_hit) = self.boundary_segment_at(ti, c)
+            .map(|si| (si, c))
+            .or_else(|| self.horizontal_hit(ti, c, direction))?;
+        Some(self.edge_hit(si, c_hit, (c_hit.x() - c.x()).abs()))
+    }
+
+    /// Find the edge nearest to `point`, and the nearest point on it.
+    /// 
+    /// The nearer of the horizontal hits bounds the search, which then only visits trapezoids reachable from `point`
+    /// without crossing an edge, and within that distance of it vertically. 
+    /// Returns [None] if the polygons have no edges.
+    /// 
+    /// This fails if the [PolygonList] violates the triangulation preconditions.
+    #[allow(clippy::type_complexity)]
+    pub fn nearest_edge<V: Vertex<Coordinate = <P::Vertex as Vertex>::Coordinate>>(&self, point: &V) -> Result<Option<EdgeHit<P::Index, <P::Vertex as Vertex>::Coordinate>>, TrapezoidationError<P::Index>> {
+        let c = VertexExt::to_newtype_ref(point).coords();
+        let (_, ti_start) = self.find_trapezoid(c);
+        let nearest = self.nearest_segment(ti_start, c, None, &mut NearestSearch::new())?;
+        Ok(nearest.map(|(si, c_nearest, d)| self.edge_hit(si, c_nearest, d)))
+    }
+
+    // The segment nearest to `c`, which lies in the trapezoid `ti_start`, with the nearest point on it and its distance.
+    // Segments at least `max_distance` away are ignored. `search` is cleared and reused, so repeated searches don't allocate
+    #[allow(clippy::type_complexity)]
+    fn nearest_segment(&self, ti_start: Idx<Trapezoid<P::Vertex, P::Index>>, c: Coords<<P::Vertex as Vertex>::Coordinate>, max_distance: Option<<P::Vertex as Vertex>::Coordinate>, search: &mut NearestSearch<P::Vertex, P::Index>) -> Result<Option<(Idx<Segment<P::Vertex, P::Index>>, Coords<<P::Vertex as Vertex>::Coordinate>, <P::Vertex as Vertex>::Coordinate)>, TrapezoidationError<P::Index>> {
+        if let Some(si) = self.boundary_segment_at(ti_start, c) {
+            return Ok(Some((si, c, <P::Vertex as Vertex>::Coordinate::zero())));
+        }
+
+        let distance = |c_other: Coords<<P::Vertex as Vertex>::Coordinate>| (c_other.x() - c.x()).hypot(c_other.y() - c.y());
+        let mut nearest = None;
+        // The distance which a segment must beat to be the nearest
+        let bound = |nearest: Option<(_, _, _)>| nearest.map(|(_, _, d)| d).or(max_distance);
+        for direction in [HorizontalDirection::Left, HorizontalDirection::Right] {
+            if let Some((si, c_hit)) = self.horizontal_hit(ti_start, c, direction) {
+                let d = distance(c_hit);
+                if bound(nearest).is_none_or(|bound| d < bound) {
+                    nearest = Some((si, c_hit, d));
+                }
+            }
+        }
+
+        // The path from `point` to the nearest point on any edge cannot cross another edge,
+        // and only passes through trapezoids within the nearest distance vertically
+        let NearestSearch { visited, stack } = search;
+        visited.clear();
+        visited.insert(ti_start);
+        stack.clear();
+        stack.push(ti_start);
+        while let Some(ti) = stack.pop() {
+            let t = self.parts.ts.get(ti);
+            let y_min = t.down().map(|ni| self.parts.ns.coords(ni).y());
+            let y_max = t.up().map(|ni| self.parts.ns.coords(ni).y());
+            let d_vertical = y_min.filter(|&y| c.y() < y).map(|y| y - c.y())
+                .or_else(|| y_max.filter(|&y| y < c.y()).map(|y| c.y() - y))
+                .unwrap_or_else(<P::Vertex as Vertex>::Coordinate::zero);
+            if bound(nearest).is_some_and(|bound| d_vertical > bound) {
+                continue;
+            }
+
+            for si in t.left().into_iter().chain(t.right()) {
+                let c_closest = self.parts.ss.get(&self.parts.ns, si).closest_point(c);
+                let d = distance(c_closest);
+                if bound(nearest).is_none_or(|bound| d < bound) {
+                    nearest = Some((si, c_closest, d));
+                }
+            }
+
+            for ni in t.up().into_iter().chain(t.down()) {
+                let [face0, face1] = self.parts.ns.get(ni).final_type().map_err(|e| TrapezoidationError::InternalError(e).diagnose(self.ps.polygon_list(), self.seed))?.faces();
+                let same = if face0.contains(&ti) { face0 } else { face1 };
+                for ti_same in same {
+                    if visited.insert(ti_same) {
+                        stack.push(ti_same);
+                    }
+                }
+            }
+        }
+
+        Ok(nearest)
+    }
+
+    // The segment which `c` lies exactly on, if any, among those bounding the trapezoid containing `c`.
+    // A vertex gives the segment leaving it in the order of the polygon's vertices
+    fn boundary_segment_at(&self, ti: Idx<Trapezoid<P::Vertex, P::Index>>, c: Coords<<P::Vertex as Vertex>::Coordinate>) -> Option<Idx<Segment<P::Vertex, P::Index>>> {
+        let t = self.parts.ts.get(ti);
+        let mut segments = t.left().into_iter().chain(t.right());
+
+        let nexuses = t.up().into_iter()
+            .chain(t.down())
+            .chain(segments.clone().flat_map(|si| [self.parts.ss.get(&self.parts.ns, si).ni_min(), self.parts.ss.get(&self.parts.ns, si).ni_max()]));
+        for ni in nexuses {
+            let n = self.parts.ns.get(ni);
+            if n.coords() == c {
+                return n.segments().find(|&si| self.parts.ss.ni_ordered(si).0 == ni);
+            }
+        }
+
+        segments.find(|&si| self.parts.ss.get(&self.parts.ns, si).contains(c))
+    }
+
+    // The segment bounding the trapezoid in `direction`, and where the horizontal line through `c` crosses it
+    #[allow(clippy::type_complexity)]
+    fn horizontal_hit(&self, ti: Idx<Trapezoid<P::Vertex, P::Index>>, c: Coords<<P::Vertex as Vertex>::Coordinate>, direction: HorizontalDirection) -> Option<(Idx<Segment<P::Vertex, P::Index>>, Coords<<P::Vertex as Vertex>::Coordinate>)> {
+        let t = self.parts.ts.get(ti);
+        let si = match direction {
+            HorizontalDirection::Left => t.left(),
+            HorizontalDirection::Right => t.right(),
+        }?;
+        Some((si, Coords::new(self.parts.ss.get(&self.parts.ns, si).x_at(c.y(), c.x()), c.y())))
+    }
+
+    fn edge_hit(&self, si: Idx<Segment<P::Vertex, P::Index>>, c: Coords<<P::Vertex as Vertex>::Coordinate>, distance: <P::Vertex as Vertex>::Coordinate) -> EdgeHit<P::Index, <P::Vertex as Vertex>::Coordinate> {
+        let (ni_from, ni_to) = self.parts.ss.ni_ordered(si);
+        let (n_from, n_to) = (self.parts.ns.get(ni_from), self.parts.ns.get(ni_to));
+        EdgeHit { ring: n_from.ring(), edge: (n_from.vertex(), n_to.vertex()), point: [c.x(), c.y()], distance }
+    }
+
+    /// Output the trapezoids inside the polygons into the layout specified by `format`.
+    /// 
+    /// Trapezoids with zero height, between vertices with equal y coordinates, are skipped.
+    /// See [Trapezoidation::trapezoids] for possible errors.
+    #[allow(clippy::type_complexity)]
+    pub fn emit_trapezoids<TF: TrapezoidFormat<'p, P>>(&self, format: TF) -> Result<<TF::Builder as TrapezoidBuilder<'p, P>>::Output, TriangulationError<<TF::Builder as TrapezoidBuilder<'p, P>>::Error, P::Index>> {
+        let faces = self.faces().map_err(TriangulationError::TrapezoidationError)?;
+        let mut builder = format.initialize(self.ps.polygon_list())?;
+        match self.emit_trapezoids_inner(faces, &mut builder) {
+            Ok(()) => builder.build().map_err(TriangulationError::from),
+            Err(error) => {
+                let error = error.diagnose(self.ps.polygon_list(), self.seed);
+                builder.fail(&error);
+                Err(error)
+            }
+        }
+    }
+
+    fn emit_trapezoids_inner<TB: TrapezoidBuilder<'p, P>>(&self, faces: &[Face], builder: &mut TB) -> Result<(), TriangulationError<TB::Error, P::Index>> {
+        for (t, face) in self.parts.ts.iter().zip(faces) {
+            if !face.inside {
+                continue;
+            }
+
+            let (Some(ni_down), Some(ni_up), Some(si_left), Some(si_right)) = (t.down(), t.up(), t.left(), t.right()) else {
+                return Err(TriangulationError::internal("A trapezoid inside the polygon must be enclosed"));
+            };
+            let (c_down, c_up) = (self.parts.ns.coords(ni_down), self.parts.ns.coords(ni_up));
+            if c_down.y() == c_up.y() {
+                continue;
+            }
+
+            let (s_left, s_right) = (self.parts.ss.get(&self.parts.ns, si_left), self.parts.ss.get(&self.parts.ns, si_right));
+            builder.add_trapezoid([
+                [s_left.x_at(c_down.y(), c_down.x()), c_down.y()],
+                [s_right.x_at(c_down.y(), c_down.x()), c_down.y()],
+                [s_right.x_at(c_up.y(), c_up.x()), c_up.y()],
+                [s_left.x_at(c_up.y(), c_up.x()), c_up.y()],
+            ])?;
+        }
+        Ok(())
+    }
+
+    /// Intersect the interior with the horizontal lines `y = k * spacing`, for [hatch](crate::hatch::hatch).
+    /// 
+    /// Each span lies within one trapezoid, so spans are unordered and may touch at vertices.
+    /// The lines crossing each trapezoid are counted up front, so no more than [HatchError::MAX_LINES] are generated.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn hatch_spans(&self, spacing: <P::Vertex as Vertex>::Coordinate) -> Result<Vec<HatchSpan<<P::Vertex as Vertex>::Coordinate>>, HatchError<P::Index>> {
+        let faces = self.faces()?;
+        let row_at = |y: <P::Vertex as Vertex>::Coordinate| (y / spacing).ceil().to_i64().ok_or(HatchError::TooManyLines);
+        let mut spans = Vec::new();
+        let mut line_count = 0usize;
+        for (t, face) in self.parts.ts.iter().zip(faces) {
+            if !face.inside {
+                continue;
+            }
+
+            let (Some(ni_down), Some(ni_up), Some(si_left), Some(si_right)) = (t.down(), t.up(), t.left(), t.right()) else {
+                return Err(TrapezoidationError::InternalError(InternalError::new("A trapezoid inside the polygon must be enclosed")).diagnose(self.ps.polygon_list(), self.seed).into());
+            };
+            let (c_down, c_up) = (self.parts.ns.coords(ni_down), self.parts.ns.coords(ni_up));
+            let (s_left, s_right) = (self.parts.ss.get(&self.parts.ns, si_left), self.parts.ss.get(&self.parts.ns, si_right));
+
+            // Each line belongs to the trapezoid above it, so lines through a vertex are only found once
+            let rows = row_at(c_down.y())?..row_at(c_up.y())?;
+            line_count = line_count.saturating_add(rows.end.saturating_sub(rows.start).try_into().unwrap_or(usize::MAX));
+            if line_count > HatchError::<P::Index>::MAX_LINES {
+                return Err(HatchError::TooManyLines);
+            }
+            for row in rows {
+                let y = <<P::Vertex as Vertex>::Coordinate as NumCast>::from(row).ok_or(HatchError::TooManyLines)? * spacing;
+                let x = [s_left.x_at(y, c_down.x()), s_right.x_at(y, c_down.x())];
+                if y >= c_down.y() && y < c_up.y() && x[0] < x[1] {
+                    spans.push(HatchSpan { row, y, x, edges: [si_left.usize(), si_right.usize()] });
+                }
+            }
         }
+        Ok(spans)
+    }
+
+    /// Follow the boundary upward from segment `si` to the line at `y`, for joining hatch lines.
+    /// 
+    /// Returns the segment crossing the line and the vertices passed on the way,
+    /// or [None] if the boundary turns back down first.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn boundary_above(&self, si: usize, y: <P::Vertex as Vertex>::Coordinate) -> Option<(usize, Vec<[<P::Vertex as Vertex>::Coordinate; 2]>)> {
+        let mut si = Idx::<Segment<P::Vertex, P::Index>>::new(si);
+        let mut vertices = Vec::new();
+        loop {
+            let ni = self.parts.ss.get(&self.parts.ns, si).ni_max();
+            let n = self.parts.ns.get(ni);
+            if n.coords().y() > y {
+                break;
+            }
+            let si_next = n.segments().find(|&si_next| si_next != si)?;
+            if self.parts.ss.get(&self.parts.ns, si_next).ni_min() != ni {
+                return None;
+            }
+            if n.coords().y() < y {
+                vertices.push([n.coords().x(), n.coords().y()]);
+            }
+            si = si_next;
+        }
+        Some((si.usize(), vertices))
+    }
+
+    /// Rasterize the polygons to a `width` × `height` coverage mask, with one byte per pixel in row-major order.
+    /// 
+    /// Each pixel's value is the exact fraction of its area covered by the polygons, scaled to `0..=255`.
+    /// `transform` maps polygon coordinates to pixel coordinates, where pixel `(i, j)` spans `[i, i + 1] × [j, j + 1]`.
+    /// It is an affine transform given as the rows of a 2×3 matrix, so `[x, y]` is mapped to
+    /// `[t[0][0] * x + t[0][1] * y + t[0][2], t[1][0] * x + t[1][1] * y + t[1][2]]`.
+    /// 
+    /// See [Trapezoidation::trapezoids] for possible errors.
+    pub fn rasterize(&self, width: usize, height: usize, transform: [[<P::Vertex as Vertex>::Coordinate; 3]; 2]) -> Result<Vec<u8>, TriangulationError<Infallible, P::Index>> {
+        self.emit_trapezoids(CoverageBuilder::new(width, height, transform))
+    }
+
+    /// Compute a signed distance field of the polygons on a `width` × `height` grid, with one value per cell in row-major order.
+    /// 
+    /// Each value is the distance from the center of a cell to the nearest edge, negative inside the polygons.
+    /// `transform` maps polygon coordinates to grid coordinates in the same way as for [Trapezoidation::rasterize],
+    /// so cell `(i, j)` is sampled at the point which `transform` maps to `[i + 0.5, j + 0.5]`.
+    /// Distances are measured in polygon coordinates and clamped to `max_distance`, which also limits how far the nearest edge is searched for.
+    /// 
+    /// Neighbouring cells usually lie in the same trapezoid, which is then reused instead of searching the query structure again.
+    /// Returns [SdfError::NonInvertibleTransform] if `transform` is not invertible, and [SdfError::GridTooLarge]
+    /// if the coordinate type cannot represent the grid's cell centers. See [Trapezoidation::trapezoids] for other possible errors.
+    pub fn signed_distance_field(&self, width: usize, height: usize, transform: [[<P::Vertex as Vertex>::Coordinate; 3]; 2], max_distance: <P::Vertex as Vertex>::Coordinate) -> Result<Vec<<P::Vertex as Vertex>::Coordinate>, SdfError<P::Index>> {
+        let [[m00, m01, m02], [m10, m11, m12]] = transform;
+        let det = m00 * m11 - m01 * m10;
+        // Also rejects a NaN determinant
+        if det.abs().partial_cmp(&<P::Vertex as Vertex>::Coordinate::zero()) != Some(Ordering::Greater) {
+            return Err(SdfError::NonInvertibleTransform);
+        }
+        let cast = |v: usize| num_traits::cast::<f64, <P::Vertex as Vertex>::Coordinate>(v as f64 + 0.5).ok_or(SdfError::GridTooLarge);
+        let us = (0..width).map(|i| Ok(cast(i)? - m02)).collect::<Result<Vec<_>, SdfError<P::Index>>>()?;
+
+        let faces = self.faces()?;
+        let mut field = Vec::with_capacity(width * height);
+        let mut ti_recent = None;
+        let mut search = NearestSearch::new();
+        for j in 0..height {
+            let v = cast(j)? - m12;
+            for &u in &us {
+                let c = Coords::new((m11 * u - m01 * v) / det, (m00 * v - m10 * u) / det);
+                let ti = match ti_recent.filter(|&ti| self.is_strictly_inside(ti, c)) {
+                    Some(ti) => ti,
+                    None => self.find_trapezoid(c).1,
+                };
+                ti_recent = Some(ti);
+
+                let distance = self.nearest_segment(ti, c, Some(max_distance), &mut search)?.map_or(max_distance, |(_, _, d)| d);
+                field.push(match self.location_in_trapezoid(faces, ti, c) {
+                    Location::Inside { .. } => -distance,
+                    _ => distance,
+                });
+            }
+        }
+        Ok(field)
     }
 
     /// Triangulate the trapezoidation.
     /// 
     /// See [PolygonList::triangulate].
-    pub fn triangulate<FB: FanFormat<'p, P>>(&self, format: FB) -> Result<<FB::Builder as FanBuilder<'p, P>>::Output, TriangulationError<<FB::Builder as FanBuilder<'p, P>>::Error>> {
+    pub fn triangulate<FB: FanFormat<'p, P>>(&self, format: FB) -> FanResult<'p, P, FB> {
+        self.triangulate_with_buffers(format, &mut Buffers::new())
+    }
+
+    pub(crate) fn triangulate_with_buffers<FB: FanFormat<'p, P>>(&self, format: FB, buffers: &mut Buffers<P::Vertex, P::Index>) -> FanResult<'p, P, FB> {
         let mut fbs = FanBuilderState::<'p, P, FB>::Uninitialized(format);
         // Separate out the actual triangulation logic, so FanBuilder error handling can be consolidated to one location
-        let result = self.triangulate_inner(&mut fbs);
+        let result = self.triangulate_inner(&mut fbs, buffers).map_err(|e| e.diagnose(self.ps.polygon_list(), self.seed));
         fbs.complete(result)
     }
 }
@@ -892,13 +1723,13 @@ impl<'p, P: PolygonList<'p> + ?Sized> Trapezoidation<'p, P> {
 impl<'p, P: PolygonList<'p> + ?Sized> TrapezoidationStructure<'p, P> for Trapezoidation<'p, P> {
     fn ps(&self) -> PolygonListExt<'p, P> { self.ps }
 
-    fn ns(&self) -> &[Nexus<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index>] { &self.ns }
+    fn ns(&self) -> &Nexuses<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index> { &self.parts.ns }
 
-    fn ss(&self) -> &[Segment<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index>] { &self.ss }
+    fn ss(&self) -> &Segments<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index> { &self.parts.ss }
 
-    fn ts(&self) -> &[Trapezoid<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index>] { &self.ts }
+    fn ts(&self) -> &Trapezoids<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index> { &self.parts.ts }
 
-    fn qs(&self) -> &[QueryNode<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index>] { &self.qs }
+    fn qs(&self) -> &[QueryNode<<P as PolygonList<'p>>::Vertex, <P as PolygonList<'p>>::Index>] { &self.parts.qs }
 }
 
 #[cfg(feature = "_debugging")]
@@ -987,22 +1818,22 @@ impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTria
         use svg_fmt::*;
         use fmt::Write;
 
-        let style = svg_output.style.get_n_style(*self, &state.ns[*self]);
+        let style = svg_output.style.get_n_style(*self, &state.ns.get(*self));
 
-        let n = &state.ns[*self];
+        let n = &state.ns.get(*self);
 
         let v = &state.ps[n.vertex()];
-        let t_left = &state.ts[*n.up_trapezoids().first()];
+        let t_left = &state.ts.get(*n.up_trapezoids().first());
         let x_left = if let Some(si_left) = t_left.left() {
-            let s_left = &state.ss[si_left];
-            get_x_intercept(&state.ps[state.ns[s_left.ni_min()].vertex()], &state.ps[state.ns[s_left.ni_max()].vertex()], v.y().to_f32().unwrap())
+            let s_left = &state.ss.get(&state.ns, si_left);
+            get_x_intercept(&state.ps[state.ns.vertex(s_left.ni_min())], &state.ps[state.ns.vertex(s_left.ni_max())], v.y().to_f32().unwrap())
         } else {
             v.x().to_f32().unwrap()
         };
-        let t_right = &state.ts[*n.up_trapezoids().last()];
+        let t_right = &state.ts.get(*n.up_trapezoids().last());
         let x_right = if let Some(si_right) = t_right.right() {
-            let s_right = &state.ss[si_right];
-            get_x_intercept(&state.ps[state.ns[s_right.ni_min()].vertex()], &state.ps[state.ns[s_right.ni_max()].vertex()], v.y().to_f32().unwrap())
+            let s_right = &state.ss.get(&state.ns, si_right);
+            get_x_intercept(&state.ps[state.ns.vertex(s_right.ni_min())], &state.ps[state.ns.vertex(s_right.ni_max())], v.y().to_f32().unwrap())
         } else {
             v.x().to_f32().unwrap()
         };
@@ -1047,11 +1878,11 @@ impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTria
         use svg_fmt::*;
         use fmt::Write;
 
-        let style = svg_output.style.get_s_style(*self, &state.ss[*self]);
+        let style = svg_output.style.get_s_style(*self, &state.ss.get(&state.ns, *self));
 
-        let s = &state.ss[*self];
-        let n_min = &state.ns[s.ni_min()];
-        let n_max = &state.ns[s.ni_max()];
+        let s = &state.ss.get(&state.ns, *self);
+        let n_min = &state.ns.get(s.ni_min());
+        let n_max = &state.ns.get(s.ni_max());
         let v_min = &state.ps[n_min.vertex()];
         let v_max = &state.ps[n_max.vertex()];
         let width = svg_output.context.percent(0.5);
@@ -1076,7 +1907,7 @@ impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTria
         use svg_fmt::*;
         use fmt::Write;
 
-        let style = svg_output.style.get_t_style(*self, &state.ts[*self]);
+        let style = svg_output.style.get_t_style(*self, &state.ts.get(*self));
         let width = svg_output.context.percent(0.1);
         let color = match style {
             debug::svg::SvgElementStyle::Hide => return Ok(()),
@@ -1084,33 +1915,33 @@ impl<'p, P: PolygonList<'p> + ?Sized> debug::svg::SvgElement<debug::svg::SvgTria
             debug::svg::SvgElementStyle::Highlight => rgb(255, 126, 0),
         };
 
-        let t = &state.ts[*self];
+        let t = &state.ts.get(*self);
         let y_min = if let Some(ni_down) = t.down() {
-            state.ps[state.ns[ni_down].vertex()].y().to_f32().unwrap()
+            state.ps[state.ns.vertex(ni_down)].y().to_f32().unwrap()
         } else {
             svg_output.context.view_y_min
         };
         let y_max = if let Some(ni_up) = t.up() {
-            state.ps[state.ns[ni_up].vertex()].y().to_f32().unwrap()
+            state.ps[state.ns.vertex(ni_up)].y().to_f32().unwrap()
         } else {
             svg_output.context.view_y_max
         };
         let y = (y_max + y_min) / 2.0;
 
         let (x_min, x_topleft, x_bottomleft) = if let Some(si_left) = t.left() {
-            let s_left = &state.ss[si_left];
+            let s_left = &state.ss.get(&state.ns, si_left);
 
-            let v_min = &state.ps[state.ns[s_left.ni_min()].vertex()];
-            let v_max = &state.ps[state.ns[s_left.ni_max()].vertex()];
+            let v_min = &state.ps[state.ns.vertex(s_left.ni_min())];
+            let v_max = &state.ps[state.ns.vertex(s_left.ni_max())];
             (get_x_intercept(v_min, v_max, y).to_f32().unwrap(), get_x_intercept(v_min, v_max, y_max).to_f32().unwrap(), get_x_intercept(v_min, v_max, y_min).to_f32().unwrap())
         } else {
             (svg_output.context.view_x_min, svg_output.context.view_x_min, svg_output.context.view_x_min)
         };
         let (x_max, x_topright, x_bottomright) = if let Some(si_right) = t.right() {
-            let s_right = &state.ss[si_right];
+            let s_right = &state.ss.get(&state.ns, si_right);
 
-            let v_min = &state.ps[state.ns[s_right.ni_min()].vertex()];
-            let v_max = &state.ps[state.ns[s_right.ni_max()].vertex()];
+            let v_min = &state.ps[state.ns.vertex(s_right.ni_min())];
+            let v_max = &state.ps[state.ns.vertex(s_right.ni_max())];
             (get_x_intercept(v_min, v_max, y).to_f32().unwrap(), get_x_intercept(v_min, v_max, y_max).to_f32().unwrap(), get_x_intercept(v_min, v_max, y_min).to_f32().unwrap())
         } else {
             (svg_output.context.view_x_max, svg_output.context.view_x_max, svg_output.context.view_x_max)
@@ -0,0 +1,80 @@
+use std::mem;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{FanFormat, FanResult, PolygonList, TriangulationError, Vertex, VertexIndex, trapezoidation::{Buffers, Trapezoidation, TrapezoidationParts, TrapezoidationState}};
+
+/// Triangulates many polygons in turn, keeping its working memory between calls.
+///
+/// [PolygonList::triangulate] allocates the trapezoidation and its scratch space afresh for every call.
+/// When triangulating many small shapes, such as every frame of an animation, reusing a [Triangulator] avoids
+/// those allocations once it has grown to fit the largest input. The output is identical to [PolygonList::triangulate].
+///
+/// ```
+/// # use triangulate::{Triangulator, formats::IndexedListFormat, ListFormat};
+/// let mut triangulator = Triangulator::new();
+/// let mut triangles = Vec::<[usize; 2]>::new();
+/// for size in 1..10 {
+///     let size = size as f32;
+///     let square = vec![vec![[0f32, 0.], [size, 0.], [size, size], [0., size]]];
+///     triangles.clear();
+///     triangulator.triangulate(&square, IndexedListFormat::new(&mut triangles).into_fan_format()).expect("Triangulation failed");
+///     assert_eq!(triangles.len(), 6);
+/// }
+/// ```
+pub struct Triangulator<V: Vertex, Index: VertexIndex> {
+    parts: TrapezoidationParts<V, Index>,
+    buffers: Buffers<V, Index>,
+}
+
+impl<V: Vertex, Index: VertexIndex> Default for Triangulator<V, Index> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Vertex, Index: VertexIndex> Triangulator<V, Index> {
+    /// Create a triangulator, which allocates as it is first used
+    pub fn new() -> Self {
+        Self {
+            parts: TrapezoidationParts::default(),
+            buffers: Buffers::new(),
+        }
+    }
+
+    /// Triangulate the polygons into the layout specified by `format`. See [PolygonList::triangulate].
+    pub fn triangulate<'p, P, FB>(&mut self, polygon_list: &'p P, format: FB) -> FanResult<'p, P, FB>
+    where P: PolygonList<'p, Vertex = V, Index = Index> + ?Sized,
+          FB: FanFormat<'p, P>,
+          V: 'p,
+          Index: 'p {
+        self.triangulate_with_seed(polygon_list, format, rand::thread_rng().gen())
+    }
+
+    /// Triangulate the polygons into the layout specified by `format`, ordering segment insertion by `seed`.
+    /// See [PolygonList::triangulate_with_seed].
+    pub fn triangulate_with_seed<'p, P, FB>(&mut self, polygon_list: &'p P, format: FB, seed: u64) -> FanResult<'p, P, FB>
+    where P: PolygonList<'p, Vertex = V, Index = Index> + ?Sized,
+          FB: FanFormat<'p, P>,
+          V: 'p,
+          Index: 'p {
+        let format = match crate::fast_path::try_triangulate(polygon_list, format) {
+            Ok(result) => return result,
+            Err(format) => format,
+        };
+
+        let mut parts = mem::take(&mut self.parts);
+        parts.reset(polygon_list.vertex_count());
+        let mut state = TrapezoidationState::from_parts(polygon_list, parts, Some(seed));
+        if let Err(e) = state.add_polygon_list(&mut self.buffers, &mut ChaCha8Rng::seed_from_u64(seed)) {
+            self.parts = state.into_parts();
+            return Err(TriangulationError::TrapezoidationError(e));
+        }
+
+        let trapezoidation = Trapezoidation::with_spare_capacity(state);
+        let result = trapezoidation.triangulate_with_buffers(format, &mut self.buffers);
+        self.parts = trapezoidation.into_parts();
+        result
+    }
+}
@@ -0,0 +1,104 @@
+use smallvec::SmallVec;
+
+use crate::{PolygonList, Trapezoidation, idx::Idx, location::Face, segment::Segment, trapezoid::Trapezoid, trapezoidation::TrapezoidationStructure};
+
+/// Identifies a trapezoid within a [Trapezoidation]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TrapezoidId(usize);
+
+impl TrapezoidId {
+    /// The position of the trapezoid in [Trapezoidation::trapezoids], for use in lookup tables
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// A read-only view of a single trapezoid of a [Trapezoidation], obtained from [Trapezoidation::trapezoids] or [Trapezoidation::trapezoid].
+///
+/// Each trapezoid is bounded above and below by horizontal lines through a vertex, and on the left and right by an edge.
+/// The outermost trapezoids are unbounded on some sides.
+pub struct TrapezoidView<'t, 'p, P: PolygonList<'p> + ?Sized> {
+    trapezoidation: &'t Trapezoidation<'p, P>,
+    faces: &'t [Face],
+    ti: Idx<Trapezoid<P::Vertex, P::Index>>,
+}
+
+impl<'t, 'p, P: PolygonList<'p> + ?Sized> Clone for TrapezoidView<'t, 'p, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'t, 'p, P: PolygonList<'p> + ?Sized> Copy for TrapezoidView<'t, 'p, P> { }
+
+impl<'t, 'p, P: PolygonList<'p> + ?Sized> TrapezoidView<'t, 'p, P> {
+    pub(crate) fn new(trapezoidation: &'t Trapezoidation<'p, P>, faces: &'t [Face], ti: Idx<Trapezoid<P::Vertex, P::Index>>) -> Self {
+        Self { trapezoidation, faces, ti }
+    }
+
+    fn t(&self) -> Trapezoid<P::Vertex, P::Index> {
+        self.trapezoidation.ts().get(self.ti)
+    }
+
+    fn edge(&self, si: Idx<Segment<P::Vertex, P::Index>>) -> (P::Index, P::Index) {
+        let (ni_from, ni_to) = self.trapezoidation.ss().ni_ordered(si);
+        let ns = self.trapezoidation.ns();
+        (ns.vertex(ni_from), ns.vertex(ni_to))
+    }
+
+    /// The trapezoid's identifier
+    pub fn id(&self) -> TrapezoidId {
+        TrapezoidId(self.ti.usize())
+    }
+
+    /// The vertex whose horizontal line bounds the trapezoid from above, or [None] if it is unbounded above
+    pub fn top(&self) -> Option<P::Index> {
+        self.t().up().map(|ni| self.trapezoidation.ns().vertex(ni))
+    }
+
+    /// The vertex whose horizontal line bounds the trapezoid from below, or [None] if it is unbounded below
+    pub fn bottom(&self) -> Option<P::Index> {
+        self.t().down().map(|ni| self.trapezoidation.ns().vertex(ni))
+    }
+
+    /// The edge bounding the trapezoid on the left, with its vertices in the order they appear in their ring,
+    /// or [None] if it is unbounded on the left
+    pub fn left(&self) -> Option<(P::Index, P::Index)> {
+        self.t().left().map(|si| self.edge(si))
+    }
+
+    /// The edge bounding the trapezoid on the right, with its vertices in the order they appear in their ring,
+    /// or [None] if it is unbounded on the right
+    pub fn right(&self) -> Option<(P::Index, P::Index)> {
+        self.t().right().map(|si| self.edge(si))
+    }
+
+    /// The trapezoids sharing part of this trapezoid's top or bottom edge.
+    ///
+    /// Neighbours are never separated by an edge, so they are either both inside or both outside the polygons.
+    /// Trapezoids on the other side of the left and right edges are not included.
+    pub fn neighbours(&self) -> impl Iterator<Item = TrapezoidId> {
+        let ns = self.trapezoidation.ns();
+        self.t().up().into_iter()
+            .chain(self.t().down())
+            // Every nexus had a valid final type when the faces were found
+            .filter_map(|ni| ns.get(ni).final_type().ok())
+            .flat_map(|nexus_type| nexus_type.neighbours(self.ti))
+            .map(|ti| TrapezoidId(ti.usize()))
+            .collect::<SmallVec<[_; 4]>>()
+            .into_iter()
+    }
+
+    /// Whether the trapezoid is inside the polygons
+    pub fn is_inside(&self) -> bool {
+        self.faces[self.ti.usize()].inside
+    }
+
+    /// The ring forming the outer boundary of the region containing the trapezoid, if it is inside the polygons.
+    ///
+    /// See [Location::Inside](crate::Location::Inside).
+    pub fn ring(&self) -> Option<usize> {
+        let face = self.faces[self.ti.usize()];
+        face.ring.filter(|_| face.inside)
+    }
+}